@@ -1,3 +1,5 @@
+use crate::condition::{json_path, Condition, Value};
+use crate::dialect::Dialect;
 use crate::query::QueryBuilder;
 
 /// Define the enum to represent a column as either a String or SelectQueryBuilder
@@ -7,6 +9,8 @@ pub enum Column<'a> {
     Text(String),
     ///  Subquery with Alias
     SubQuery(Box<dyn QueryBuilder<'a> + 'a>, String),
+    /// Expression with bound values and Alias, the expression is a raw condition
+    Expr(Condition<'a>, String),
 }
 
 /// Implement the build method to convert the enum to a string
@@ -18,6 +22,164 @@ impl<'a> Column<'a> {
             Column::SubQuery(sub_query, alias) => {
                 "(".to_string() + &sub_query.to_sql() + ") AS " + alias
             }
+            Column::Expr(expression, alias) => format!("{} AS {}", expression.build(), alias),
+        }
+    }
+
+    /// Creates a column selecting all columns (`*`).
+    pub fn all() -> Self {
+        Column::Text("*".to_string())
+    }
+
+    /// Creates a column selecting all columns of one table of a query with joins, e.g.
+    /// `orders.*`. The query expands it into the qualified columns of the table, so its
+    /// columns are decoded like columns selected one by one.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The name or alias of the table, either the table of the query or a
+    ///   joined table.
+    pub fn table_all(table: &str) -> Self {
+        Column::Text(format!("{}.*", table))
+    }
+
+    /// Creates a `COUNT(column)` aggregate column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to count, use `*` to count all rows.
+    pub fn count(column: &str) -> Self {
+        Column::Text(format!("COUNT({})", column))
+    }
+
+    /// Creates a `SUM(column)` aggregate column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to sum.
+    pub fn sum(column: &str) -> Self {
+        Column::Text(format!("SUM({})", column))
+    }
+
+    /// Creates an `AVG(column)` aggregate column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to average.
+    pub fn avg(column: &str) -> Self {
+        Column::Text(format!("AVG({})", column))
+    }
+
+    /// Creates a `MIN(column)` aggregate column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to get the minimum of.
+    pub fn min(column: &str) -> Self {
+        Column::Text(format!("MIN({})", column))
+    }
+
+    /// Creates a `MAX(column)` aggregate column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to get the maximum of.
+    pub fn max(column: &str) -> Self {
+        Column::Text(format!("MAX({})", column))
+    }
+
+    /// Creates a `COUNT(DISTINCT column)` aggregate column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to count the distinct values of.
+    pub fn count_distinct(column: &str) -> Self {
+        Column::Text(format!("COUNT(DISTINCT {})", column))
+    }
+
+    /// Creates a computed column from an SQL expression, e.g. `price * quantity AS subtotal`
+    /// or `price * ? AS total` with a bound value.
+    ///
+    /// The expression is inserted into the query as is, with a `?` placeholder for each
+    /// of its values, like `raw_where`. The values are bound in textual order together
+    /// with the values of the conditions of the query, so only they may contain user input.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The SQL expression to compute.
+    /// * `values` - The values of the placeholders, in order.
+    /// * `alias` - The alias of the computed column, matching the field of the table struct.
+    pub fn expr(expression: &str, values: Vec<Value<'a>>, alias: &str) -> Self {
+        if values.is_empty() {
+            return Column::Text(format!("{} AS {}", expression, alias));
+        }
+
+        Column::Expr(Condition::raw(expression, values), alias.to_string())
+    }
+
+    /// Creates a `CAST(column AS type)` column for SQLite, e.g.
+    /// `Column::cast("code", "INTEGER")` to compare or select a text column as a number.
+    /// It can be given an alias with `alias` and used as the left side of a condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column or expression to cast.
+    /// * `type_name` - The type to cast to, common type names are mapped, see `Dialect::cast`.
+    pub fn cast(column: &str, type_name: &str) -> Self {
+        Column::cast_with(column, type_name, Dialect::default())
+    }
+
+    /// Creates a `CAST(column AS type)` column for a specific database, see `Column::cast`.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column or expression to cast.
+    /// * `type_name` - The type to cast to, common type names are mapped, see `Dialect::cast`.
+    /// * `dialect` - The dialect the type name is mapped for.
+    pub fn cast_with(column: &str, type_name: &str, dialect: Dialect) -> Self {
+        Column::Text(dialect.cast(column, type_name))
+    }
+
+    /// Creates a column extracting a nested field of a JSON column for SQLite, e.g.
+    /// `json_extract(profile, '$.address.city')`.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The JSON column.
+    /// * `path` - The path of the field, e.g. `address.city` or `$.address.city`.
+    pub fn json_extract(column: &str, path: &str) -> Self {
+        Column::Text(Dialect::default().json_extract(column, &json_path(path)))
+    }
+
+    /// Creates a `json_array_length(column)` column with the number of elements of a
+    /// JSON array column, using SQLite's JSON1 extension. It can be used as the left
+    /// side of a condition as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The JSON array column.
+    pub fn json_array_length(column: &str) -> Self {
+        Column::Text(format!("json_array_length({})", column))
+    }
+
+    /// Gives the column an alias, e.g. `Column::sum("amount").alias("total")` for
+    /// `SUM(amount) AS total`. The alias can be used in `order_by` of the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The alias of the column, replacing any previous alias.
+    pub fn alias(self, alias: &str) -> Self {
+        match self {
+            Column::Text(text) => {
+                // The AS of a CAST is not an alias
+                let expression = match text.rsplit_once(" AS ") {
+                    Some((expression, alias)) if !alias.contains(')') => expression.to_string(),
+                    _ => text,
+                };
+                Column::Text(format!("{} AS {}", expression, alias))
+            }
+            Column::SubQuery(sub_query, _) => Column::SubQuery(sub_query, alias.to_string()),
+            Column::Expr(expression, _) => Column::Expr(expression, alias.to_string()),
         }
     }
 }
@@ -42,6 +204,7 @@ impl<'a> PartialEq<String> for Column<'a> {
         match self {
             Column::Text(text) => text == other,
             Column::SubQuery(sub_query, _) => sub_query.to_sql() == *other,
+            Column::Expr(..) => self.build() == *other,
         }
     }
 }
@@ -52,6 +215,7 @@ impl<'a> PartialEq<&str> for Column<'a> {
         match self {
             Column::Text(text) => text == other,
             Column::SubQuery(sub_query, _) => sub_query.to_sql() == *other,
+            Column::Expr(..) => self.build() == *other,
         }
     }
 }
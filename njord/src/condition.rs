@@ -27,14 +27,28 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::HashMap;
+
+use crate::dialect::Dialect;
+use crate::error::Error;
 use crate::query::QueryBuilder;
+use crate::util::placeholder_offsets;
 
 /// Represents a condition used in building SQL queries.
+///
+/// Conditions are plain values without any connection affinity. A condition can be
+/// built once, e.g. a tenant filter, and reused across select, update and delete
+/// builders by passing a reference to `where_clause`, which clones it.
 #[derive(Clone)]
 pub enum Condition<'a> {
     /// Equality condition: column = value.
+    ///
+    /// With `Value::Null`, it is rendered as `column IS NULL`, since `column = NULL`
+    /// matches no rows.
     Eq(String, Value<'a>),
     /// Inequality condition: column <> value.
+    ///
+    /// With `Value::Null`, it is rendered as `column IS NOT NULL`.
     Ne(String, Value<'a>),
     /// Less than condition: column < value.
     Lt(String, Value<'a>),
@@ -52,6 +66,63 @@ pub enum Condition<'a> {
     In(String, Vec<Value<'a>>),
     /// Not in condition: column NOT IN (value1, value2, ...).
     NotIn(String, Vec<Value<'a>>),
+    /// NULL-safe equality condition: column IS NOT DISTINCT FROM value.
+    ///
+    /// Unlike `Eq`, this matches when both sides are NULL.
+    NullSafeEq(String, Value<'a>),
+    /// JSON equality condition: json_extract(column, path) = value.
+    JsonExtractEq(String, String, Value<'a>),
+    /// Multi-column in condition: (column1, column2) IN ((value1, value2), ...).
+    ///
+    /// Use `Condition::in_tuple` to check that each row has a value for every column.
+    InTuple(Vec<String>, Vec<Vec<Value<'a>>>),
+    /// Multi-column greater than condition: (column1, column2) > (value1, value2), comparing
+    /// the columns in order, like for keyset pagination.
+    ///
+    /// Use `Condition::gt_tuple` to check that there is a value for every column.
+    GtTuple(Vec<String>, Vec<Value<'a>>),
+    /// Raw SQL condition with a `?` placeholder for each of its values, e.g. `x = ?`.
+    ///
+    /// The values are bound in textual order together with the values of the other
+    /// conditions of a query.
+    Raw(String, Vec<Value<'a>>),
+    /// Equality with a scalar subquery: column = (subquery).
+    EqSubquery(String, Box<dyn QueryBuilder<'a> + 'a>),
+    /// Inequality with a scalar subquery: column <> (subquery).
+    NeSubquery(String, Box<dyn QueryBuilder<'a> + 'a>),
+    /// Less than a scalar subquery: column < (subquery).
+    LtSubquery(String, Box<dyn QueryBuilder<'a> + 'a>),
+    /// Greater than a scalar subquery: column > (subquery).
+    GtSubquery(String, Box<dyn QueryBuilder<'a> + 'a>),
+    /// Less than or equal to a scalar subquery: column <= (subquery).
+    LeSubquery(String, Box<dyn QueryBuilder<'a> + 'a>),
+    /// Greater than or equal to a scalar subquery: column >= (subquery).
+    GeSubquery(String, Box<dyn QueryBuilder<'a> + 'a>),
+    /// Prefix condition: column LIKE 'value%'.
+    ///
+    /// The wildcards `%` and `_` in the value match literally. Like LIKE, the condition
+    /// is case insensitive for ASCII characters in SQLite and MySQL.
+    StartsWith(String, String),
+    /// Suffix condition: column LIKE '%value', see `StartsWith`.
+    EndsWith(String, String),
+    /// Substring condition: column LIKE '%value%', see `StartsWith`.
+    Contains(String, String),
+}
+
+/// Returns a JSON path starting with `$`, e.g. `$.address.city` for `address.city`.
+pub(crate) fn json_path(path: &str) -> String {
+    if path.starts_with('$') {
+        path.to_string()
+    } else {
+        format!("$.{}", path)
+    }
+}
+
+impl<'a> From<&Condition<'a>> for Condition<'a> {
+    /// Clones a condition, so a stored condition can be passed by reference to the builders.
+    fn from(condition: &Condition<'a>) -> Self {
+        condition.clone()
+    }
 }
 
 /// Required to implement support for subqueries and literals.
@@ -63,15 +134,77 @@ pub enum Value<'a> {
     LiteralList(Vec<String>),
     /// A subquery.
     Subquery(Box<dyn QueryBuilder<'a> + 'a>),
+    /// The SQL NULL value.
+    Null,
+    /// A column, e.g. `products.user_id` in the condition of a join.
+    ///
+    /// The column is rendered as an identifier and never bound as a parameter.
+    Column(String),
 }
 
 impl<'a> Value<'a> {
+    /// Checks the conditions of a subquery value, see `Condition::check`.
+    pub fn check(&self) -> Result<(), Error> {
+        match self {
+            Value::Subquery(subquery) => subquery.check(),
+            _ => Ok(()),
+        }
+    }
+
     pub fn is_numeric(&self) -> bool {
         match self {
             Value::Literal(literal) => Condition::is_numeric(literal),
             _ => false,
         }
     }
+
+    /// Converts the value into a parameter that can be bound to a placeholder.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the literal, or `None` for NULL. Lists and subqueries
+    /// cannot be bound as a single parameter and return an `Error::InvalidQuery`.
+    pub(crate) fn to_param(&self) -> Result<Option<String>, Error> {
+        match self {
+            Value::Literal(literal) => Ok(Some(literal.clone())),
+            Value::Null => Ok(None),
+            Value::LiteralList(_) | Value::Subquery(_) | Value::Column(_) => {
+                Err(Error::InvalidQuery(
+                    "only literals and NULL can be bound as parameters".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Implement PartialEq for Value, subqueries are compared by their SQL
+impl<'a> PartialEq for Value<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Literal(left), Value::Literal(right)) => left == right,
+            (Value::LiteralList(left), Value::LiteralList(right)) => left == right,
+            (Value::Subquery(left), Value::Subquery(right)) => left.to_sql() == right.to_sql(),
+            (Value::Null, Value::Null) => true,
+            (Value::Column(left), Value::Column(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Eq for Value<'a> {}
+
+/// Implement Hash for Value, consistent with its PartialEq
+impl<'a> std::hash::Hash for Value<'a> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Literal(literal) => literal.hash(state),
+            Value::LiteralList(literals) => literals.hash(state),
+            Value::Subquery(subquery) => subquery.to_sql().hash(state),
+            Value::Null => {}
+            Value::Column(column) => column.hash(state),
+        }
+    }
 }
 
 /// Implement Display for Value
@@ -80,6 +213,8 @@ impl<'a> std::fmt::Display for Value<'a> {
         match self {
             Value::Literal(literal) => write!(f, "{}", literal),
             Value::Subquery(subquery) => write!(f, "{}", subquery.to_sql()),
+            Value::Null => write!(f, "NULL"),
+            Value::Column(column) => write!(f, "{}", column),
             Value::LiteralList(literals) => {
                 let mut result = String::new();
                 for (i, literal) in literals.iter().enumerate() {
@@ -94,6 +229,67 @@ impl<'a> std::fmt::Display for Value<'a> {
     }
 }
 
+/// The style of the placeholders of bound parameters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParamStyle {
+    /// Positional placeholders in the style of the dialect, e.g. `?` or `$1`.
+    #[default]
+    Positional,
+    /// Named placeholders `:p0`, `:p1`, ..., supported by SQLite and Oracle.
+    Named,
+}
+
+/// The bound parameters of a query, collected while rendering its conditions.
+#[derive(Clone, Default)]
+pub struct Params<'a> {
+    style: ParamStyle,
+    values: Vec<Value<'a>>,
+}
+
+impl<'a> Params<'a> {
+    /// Creates an empty list of parameters with the given placeholder style.
+    pub fn new(style: ParamStyle) -> Self {
+        Params {
+            style,
+            values: Vec::new(),
+        }
+    }
+
+    /// Adds a value and returns its placeholder.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect of positional placeholders.
+    /// * `value` - The value to bind.
+    pub fn bind(&mut self, dialect: Dialect, value: Value<'a>) -> String {
+        self.values.push(value);
+        match self.style {
+            ParamStyle::Positional => dialect.placeholder(self.values.len()),
+            ParamStyle::Named => format!(":p{}", self.values.len() - 1),
+        }
+    }
+
+    /// Returns the values in the order of their placeholders.
+    pub fn values(&self) -> &[Value<'a>] {
+        &self.values
+    }
+
+    /// Consumes the parameters, returning the values in the order of their placeholders.
+    pub fn into_values(self) -> Vec<Value<'a>> {
+        self.values
+    }
+
+    /// Consumes the parameters, returning the values by the names of their named
+    /// placeholders without the leading colon, e.g. `p0`.
+    pub fn into_named(self) -> HashMap<String, Value<'a>> {
+        self.values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| (format!("p{}", index), value))
+            .collect()
+    }
+}
+
 impl<'a> Condition<'a> {
     /// Checks if the given value is numeric.
     ///
@@ -108,75 +304,684 @@ impl<'a> Condition<'a> {
         value.parse::<f64>().is_ok() || value.parse::<i64>().is_ok()
     }
 
-    /// Builds the SQL representation of the condition.
+    /// Builds the comma separated value list of an IN or NOT IN condition.
+    ///
+    /// Literals are quoted, while subqueries are spliced in as is.
+    fn build_list(values: &[Value]) -> String {
+        values
+            .iter()
+            .map(|v| match v {
+                Value::Subquery(subquery) => subquery.to_sql(),
+                _ => format!("'{}'", v),
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Combines the condition with another one using AND.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The condition that must hold as well.
+    pub fn and(self, other: Condition<'a>) -> Self {
+        Condition::And(Box::new(self), Box::new(other))
+    }
+
+    /// Combines the condition with another one using OR.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The alternative condition.
+    pub fn or(self, other: Condition<'a>) -> Self {
+        Condition::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Creates a `left = value` condition, or `left IS NULL` for `Value::Null`.
+    ///
+    /// The left side can be a column name or a `Column`, e.g. an aggregate like
+    /// `Column::count("*")` for use in a HAVING clause.
+    pub fn eq(left: impl ToString, value: Value<'a>) -> Self {
+        Condition::Eq(left.to_string(), value)
+    }
+
+    /// Creates a `left <> value` condition, or `left IS NOT NULL` for `Value::Null`,
+    /// see `Condition::eq`.
+    pub fn ne(left: impl ToString, value: Value<'a>) -> Self {
+        Condition::Ne(left.to_string(), value)
+    }
+
+    /// Creates a `left < value` condition, see `Condition::eq`.
+    pub fn lt(left: impl ToString, value: Value<'a>) -> Self {
+        Condition::Lt(left.to_string(), value)
+    }
+
+    /// Creates a `left > value` condition, see `Condition::eq`.
+    pub fn gt(left: impl ToString, value: Value<'a>) -> Self {
+        Condition::Gt(left.to_string(), value)
+    }
+
+    /// Creates a `left <= value` condition, see `Condition::eq`.
+    pub fn le(left: impl ToString, value: Value<'a>) -> Self {
+        Condition::Le(left.to_string(), value)
+    }
+
+    /// Creates a `left >= value` condition, see `Condition::eq`.
+    pub fn ge(left: impl ToString, value: Value<'a>) -> Self {
+        Condition::Ge(left.to_string(), value)
+    }
+
+    /// Creates a condition comparing a nested field of a JSON column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The JSON column.
+    /// * `path` - The path of the field, e.g. `address.city` or `$.address.city`.
+    /// * `value` - The value to compare the field with.
+    pub fn json_extract_eq(column: &str, path: &str, value: Value<'a>) -> Self {
+        Condition::JsonExtractEq(column.to_string(), json_path(path), value)
+    }
+
+    /// Creates a condition matching rows where a JSON array column contains a value,
+    /// using the `json_each` table-valued function of SQLite's JSON1 extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The JSON array column.
+    /// * `value` - The value to look for in the array.
+    pub fn json_array_contains(column: &str, value: Value<'a>) -> Self {
+        Condition::Raw(
+            format!(
+                "EXISTS (SELECT 1 FROM json_each({}) WHERE json_each.value = ?)",
+                column
+            ),
+            vec![value],
+        )
+    }
+
+    /// Creates a multi-column `(columns) IN (rows)` condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to compare.
+    /// * `rows` - The rows of values, each with one value per column.
     ///
     /// # Returns
     ///
-    /// Returns a `String` representing the SQL condition.
-    pub fn build(&self) -> String {
+    /// A `Result` containing the condition, or an `Error::InvalidQuery` if a row does
+    /// not have as many values as there are columns.
+    pub fn in_tuple(columns: &[&str], rows: Vec<Vec<Value<'a>>>) -> Result<Self, Error> {
+        if let Some(row) = rows.iter().find(|row| row.len() != columns.len()) {
+            return Err(Error::InvalidQuery(format!(
+                "tuple of {} values does not match the {} columns ({})",
+                row.len(),
+                columns.len(),
+                columns.join(", ")
+            )));
+        }
+
+        Ok(Condition::InTuple(
+            columns.iter().map(|column| column.to_string()).collect(),
+            rows,
+        ))
+    }
+
+    /// Creates a multi-column `(columns) > (values)` condition, matching the rows that come
+    /// after the values when ordered by the columns, e.g. `(created_at, id) > (?, ?)`.
+    ///
+    /// Databases without row value comparisons get the expanded form
+    /// `created_at > ? OR (created_at = ? AND id > ?)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to compare, in order.
+    /// * `values` - The values to compare with, one per column.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the condition, or an `Error::InvalidQuery` if there is not
+    /// one value for every column.
+    pub fn gt_tuple(columns: &[&str], values: Vec<Value<'a>>) -> Result<Self, Error> {
+        if columns.is_empty() || values.len() != columns.len() {
+            return Err(Error::InvalidQuery(format!(
+                "tuple of {} values does not match the {} columns ({})",
+                values.len(),
+                columns.len(),
+                columns.join(", ")
+            )));
+        }
+
+        Ok(Condition::GtTuple(
+            columns.iter().map(|column| column.to_string()).collect(),
+            values,
+        ))
+    }
+
+    /// Expands a multi-column greater than condition into comparisons of single columns,
+    /// `a > x OR (a = x AND (b > y))`.
+    fn expand_gt_tuple(columns: &[String], values: &[Value<'a>]) -> Condition<'a> {
+        let greater = Condition::Gt(columns[0].clone(), values[0].clone());
+        if columns.len() == 1 {
+            return greater;
+        }
+
+        let equal = Condition::Eq(columns[0].clone(), values[0].clone());
+        greater.or(equal.and(Condition::expand_gt_tuple(&columns[1..], &values[1..])))
+    }
+
+    /// Creates a `column = (subquery)` condition, where the subquery returns a single
+    /// value, e.g. `SELECT MAX(amount) FROM orders`.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to compare.
+    /// * `subquery` - The subquery, which must select exactly one column.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the condition, or an `Error::InvalidQuery` if the subquery
+    /// selects more than one column.
+    pub fn eq_subquery(column: &str, subquery: impl QueryBuilder<'a> + 'a) -> Result<Self, Error> {
+        let (column, subquery) = Condition::scalar_subquery(column, subquery)?;
+        Ok(Condition::EqSubquery(column, subquery))
+    }
+
+    /// Creates a `column <> (subquery)` condition, see `Condition::eq_subquery`.
+    pub fn ne_subquery(column: &str, subquery: impl QueryBuilder<'a> + 'a) -> Result<Self, Error> {
+        let (column, subquery) = Condition::scalar_subquery(column, subquery)?;
+        Ok(Condition::NeSubquery(column, subquery))
+    }
+
+    /// Creates a `column < (subquery)` condition, see `Condition::eq_subquery`.
+    pub fn lt_subquery(column: &str, subquery: impl QueryBuilder<'a> + 'a) -> Result<Self, Error> {
+        let (column, subquery) = Condition::scalar_subquery(column, subquery)?;
+        Ok(Condition::LtSubquery(column, subquery))
+    }
+
+    /// Creates a `column > (subquery)` condition, see `Condition::eq_subquery`.
+    pub fn gt_subquery(column: &str, subquery: impl QueryBuilder<'a> + 'a) -> Result<Self, Error> {
+        let (column, subquery) = Condition::scalar_subquery(column, subquery)?;
+        Ok(Condition::GtSubquery(column, subquery))
+    }
+
+    /// Creates a `column <= (subquery)` condition, see `Condition::eq_subquery`.
+    pub fn le_subquery(column: &str, subquery: impl QueryBuilder<'a> + 'a) -> Result<Self, Error> {
+        let (column, subquery) = Condition::scalar_subquery(column, subquery)?;
+        Ok(Condition::LeSubquery(column, subquery))
+    }
+
+    /// Creates a `column >= (subquery)` condition, see `Condition::eq_subquery`.
+    pub fn ge_subquery(column: &str, subquery: impl QueryBuilder<'a> + 'a) -> Result<Self, Error> {
+        let (column, subquery) = Condition::scalar_subquery(column, subquery)?;
+        Ok(Condition::GeSubquery(column, subquery))
+    }
+
+    /// Checks that a subquery selects a single column, so it returns a scalar value.
+    fn scalar_subquery(
+        column: &str,
+        subquery: impl QueryBuilder<'a> + 'a,
+    ) -> Result<(String, Box<dyn QueryBuilder<'a> + 'a>), Error> {
+        match subquery.selected_columns() {
+            Some(count) if count != 1 => Err(Error::InvalidQuery(format!(
+                "subquery compared with `{}` must select exactly one column, got {}",
+                column, count
+            ))),
+            _ => Ok((column.to_string(), Box::new(subquery))),
+        }
+    }
+
+    /// Renders a comparison with a scalar subquery, with placeholders if `params` is given.
+    fn build_subquery_comparison(
+        dialect: Dialect,
+        column: &str,
+        operator: &str,
+        subquery: &(dyn QueryBuilder<'a> + 'a),
+        params: Option<&mut Params<'a>>,
+    ) -> String {
+        let subquery = match params {
+            Some(params) => subquery.to_sql_with_params(params),
+            None => subquery.to_sql(),
+        };
+
+        format!(
+            "{} {} ({})",
+            dialect.quote_identifier(column),
+            operator,
+            subquery
+        )
+    }
+
+    /// Creates a raw SQL condition with a `?` placeholder for each value.
+    ///
+    /// # Arguments
+    ///
+    /// * `sql` - The SQL of the condition, e.g. `length(name) > ?`. Question marks in
+    ///   string literals are not placeholders.
+    /// * `values` - The values of the placeholders, in textual order.
+    pub fn raw(sql: &str, values: Vec<Value<'a>>) -> Self {
+        Condition::Raw(sql.to_string(), values)
+    }
+
+    /// Replaces the `?` placeholders of a raw condition with the rendered values.
+    ///
+    /// The builders reject raw conditions with more or fewer placeholders than values
+    /// before they render them, see `Condition::check`.
+    fn fill_placeholders(
+        sql: &str,
+        values: &[Value<'a>],
+        mut render: impl FnMut(&Value<'a>) -> String,
+    ) -> String {
+        let mut result = String::with_capacity(sql.len());
+        let mut rest = 0;
+
+        for (offset, value) in placeholder_offsets(sql).into_iter().zip(values) {
+            result.push_str(&sql[rest..offset]);
+            result.push_str(&render(value));
+            rest = offset + 1;
+        }
+        result.push_str(&sql[rest..]);
+
+        result
+    }
+
+    /// Checks that the values of the condition can be bound to its placeholders.
+    ///
+    /// The conditions of subqueries are checked as well.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()`, or `Error::InvalidQuery` if a raw condition has more
+    /// or fewer `?` placeholders than values.
+    pub fn check(&self) -> Result<(), Error> {
         match self {
-            Condition::Eq(column, value) => {
-                // If contains a dot, assume it's a table.column
-                if column.contains('.') {
-                    format!("{} = {}", column, value)
-                } else if value.is_numeric() {
-                    format!("{} = {}", column, value)
-                } else {
-                    format!("{} = '{}'", column, value)
-                }
+            Condition::Eq(_, value)
+            | Condition::Ne(_, value)
+            | Condition::Lt(_, value)
+            | Condition::Gt(_, value)
+            | Condition::Le(_, value)
+            | Condition::Ge(_, value)
+            | Condition::NullSafeEq(_, value)
+            | Condition::JsonExtractEq(_, _, value) => value.check(),
+            Condition::And(left, right) | Condition::Or(left, right) => {
+                left.check()?;
+                right.check()
             }
-            Condition::Ne(column, value) => {
-                if value.is_numeric() {
-                    format!("{} <> {}", column, value)
-                } else {
-                    format!("{} <> '{}'", column, value)
+            Condition::In(_, values)
+            | Condition::NotIn(_, values)
+            | Condition::GtTuple(_, values) => values.iter().try_for_each(Value::check),
+            Condition::InTuple(_, rows) => rows.iter().flatten().try_for_each(Value::check),
+            Condition::Raw(sql, values) => {
+                let placeholders = placeholder_offsets(sql).len();
+                if placeholders != values.len() {
+                    return Err(Error::InvalidQuery(format!(
+                        "the raw condition `{}` has {} placeholders but {} values",
+                        sql,
+                        placeholders,
+                        values.len()
+                    )));
                 }
+                values.iter().try_for_each(Value::check)
             }
-            Condition::Lt(column, value) => {
-                if value.is_numeric() {
-                    format!("{} < {}", column, value)
-                } else {
-                    format!("{} < '{}'", column, value)
-                }
+            Condition::EqSubquery(_, subquery)
+            | Condition::NeSubquery(_, subquery)
+            | Condition::LtSubquery(_, subquery)
+            | Condition::GtSubquery(_, subquery)
+            | Condition::LeSubquery(_, subquery)
+            | Condition::GeSubquery(_, subquery) => subquery.check(),
+            Condition::StartsWith(..) | Condition::EndsWith(..) | Condition::Contains(..) => Ok(()),
+        }
+    }
+
+    /// Returns the LIKE pattern of a `StartsWith`, `EndsWith` or `Contains` condition.
+    ///
+    /// The wildcards in the value are escaped with `!`, which is used instead of a
+    /// backslash because MySQL treats backslashes in literals as escapes as well. `[` is
+    /// escaped for SQL Server, where it starts a character class.
+    fn like_pattern(&self) -> Option<(&str, String)> {
+        let (column, value, prefix, suffix) = match self {
+            Condition::StartsWith(column, value) => (column, value, "", "%"),
+            Condition::EndsWith(column, value) => (column, value, "%", ""),
+            Condition::Contains(column, value) => (column, value, "%", "%"),
+            _ => return None,
+        };
+
+        let mut pattern = String::from(prefix);
+        for c in value.chars() {
+            if matches!(c, '!' | '%' | '_' | '[') {
+                pattern.push('!');
             }
-            Condition::Gt(column, value) => {
-                if value.is_numeric() {
-                    format!("{} > {}", column, value)
-                } else {
-                    format!("{} > '{}'", column, value)
-                }
+            pattern.push(c);
+        }
+        pattern.push_str(suffix);
+
+        Some((column, pattern))
+    }
+
+    /// Renders a single value of a comparison.
+    ///
+    /// Numeric literals and NULL are inserted as is, columns are quoted as identifiers,
+    /// subqueries are wrapped in parentheses and other values are quoted.
+    fn build_value(value: &Value, dialect: Dialect) -> String {
+        match value {
+            Value::Null => "NULL".to_string(),
+            Value::Column(column) => dialect.quote_identifier(column),
+            Value::Subquery(subquery) => format!("({})", subquery.to_sql()),
+            _ if value.is_numeric() => value.to_string(),
+            _ => format!("'{}'", value),
+        }
+    }
+
+    /// Renders a single value of a comparison as a placeholder, adding the value to
+    /// the bound parameters.
+    ///
+    /// NULL, lists and columns cannot be bound and are inserted like in `build_value`, the
+    /// values of subqueries are added to the bound parameters as well.
+    fn build_param(value: &Value<'a>, dialect: Dialect, params: &mut Params<'a>) -> String {
+        match value {
+            Value::Subquery(subquery) => format!("({})", subquery.to_sql_with_params(params)),
+            Value::Null | Value::LiteralList(_) | Value::Column(_) => {
+                Condition::build_value(value, dialect)
             }
-            Condition::Le(column, value) => {
-                if value.is_numeric() {
-                    format!("{} <= {}", column, value)
-                } else {
-                    format!("{} <= '{}'", column, value)
-                }
+            _ => params.bind(dialect, value.clone()),
+        }
+    }
+
+    /// Builds the SQL representation of the condition in the default dialect.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `String` representing the SQL condition.
+    pub fn build(&self) -> String {
+        self.build_with(Dialect::default())
+    }
+
+    /// Builds the SQL representation of the condition for a specific database.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect used for conditions whose syntax differs between databases.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `String` representing the SQL condition.
+    pub fn build_with(&self, dialect: Dialect) -> String {
+        let comparison = |column: &str, operator: &str, value: &Value| {
+            let column = dialect.quote_identifier(column);
+            match value {
+                Value::Column(_) => format!(
+                    "{} {} {}",
+                    column,
+                    operator,
+                    Condition::build_value(value, dialect)
+                ),
+                _ if value.is_numeric() => format!("{} {} {}", column, operator, value),
+                _ => format!("{} {} '{}'", column, operator, value),
             }
-            Condition::Ge(column, value) => {
-                if value.is_numeric() {
-                    format!("{} >= {}", column, value)
-                } else {
-                    format!("{} >= '{}'", column, value)
-                }
+        };
+
+        match self {
+            Condition::Eq(column, Value::Null) => {
+                format!("{} IS NULL", dialect.quote_identifier(column))
             }
-            Condition::And(left, right) => format!("({}) AND ({})", left.build(), right.build()),
-            Condition::Or(left, right) => format!("({}) OR ({})", left.build(), right.build()),
+            Condition::Ne(column, Value::Null) => {
+                format!("{} IS NOT NULL", dialect.quote_identifier(column))
+            }
+            Condition::Eq(column, value) => comparison(column, "=", value),
+            Condition::Ne(column, value) => comparison(column, "<>", value),
+            Condition::Lt(column, value) => comparison(column, "<", value),
+            Condition::Gt(column, value) => comparison(column, ">", value),
+            Condition::Le(column, value) => comparison(column, "<=", value),
+            Condition::Ge(column, value) => comparison(column, ">=", value),
+            Condition::And(left, right) => format!(
+                "({}) AND ({})",
+                left.build_with(dialect),
+                right.build_with(dialect)
+            ),
+            Condition::Or(left, right) => format!(
+                "({}) OR ({})",
+                left.build_with(dialect),
+                right.build_with(dialect)
+            ),
             Condition::In(column, values) => {
-                let values = values
-                    .iter()
-                    .map(|v| format!("'{}'", v))
-                    .collect::<Vec<String>>()
-                    .join(", ");
+                let column = dialect.quote_identifier(column);
+                let values = Condition::build_list(values);
                 format!("{} IN ({})", column, values)
             }
             Condition::NotIn(column, values) => {
+                let column = dialect.quote_identifier(column);
+                let values = Condition::build_list(values);
+                format!("{} NOT IN ({})", column, values)
+            }
+            Condition::NullSafeEq(column, value) => {
+                dialect.null_safe_eq(
+                    &dialect.quote_identifier(column),
+                    &Condition::build_value(value, dialect),
+                )
+            }
+            Condition::JsonExtractEq(column, path, value) => format!(
+                "{} = {}",
+                dialect.json_extract(&dialect.quote_identifier(column), path),
+                Condition::build_value(value, dialect)
+            ),
+            Condition::InTuple(columns, rows) => {
+                let columns = columns
+                    .iter()
+                    .map(|column| dialect.quote_identifier(column))
+                    .collect::<Vec<String>>();
+                let rows = rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|value| Condition::build_value(value, dialect))
+                            .collect()
+                    })
+                    .collect::<Vec<Vec<String>>>();
+                dialect.in_tuple(&columns, &rows)
+            }
+            Condition::GtTuple(columns, values) if !dialect.supports_row_values() => {
+                Condition::expand_gt_tuple(columns, values).build_with(dialect)
+            }
+            Condition::GtTuple(columns, values) => {
+                let columns = columns
+                    .iter()
+                    .map(|column| dialect.quote_identifier(column))
+                    .collect::<Vec<String>>();
                 let values = values
                     .iter()
-                    .map(|v| format!("'{}'", v))
+                    .map(|value| Condition::build_value(value, dialect))
+                    .collect::<Vec<String>>();
+                format!("({}) > ({})", columns.join(", "), values.join(", "))
+            }
+            Condition::Raw(sql, values) => Condition::fill_placeholders(sql, values, |value| {
+                Condition::build_value(value, dialect)
+            }),
+            Condition::EqSubquery(column, subquery) => {
+                Condition::build_subquery_comparison(dialect, column, "=", subquery.as_ref(), None)
+            }
+            Condition::NeSubquery(column, subquery) => {
+                Condition::build_subquery_comparison(dialect, column, "<>", subquery.as_ref(), None)
+            }
+            Condition::LtSubquery(column, subquery) => {
+                Condition::build_subquery_comparison(dialect, column, "<", subquery.as_ref(), None)
+            }
+            Condition::GtSubquery(column, subquery) => {
+                Condition::build_subquery_comparison(dialect, column, ">", subquery.as_ref(), None)
+            }
+            Condition::LeSubquery(column, subquery) => {
+                Condition::build_subquery_comparison(dialect, column, "<=", subquery.as_ref(), None)
+            }
+            Condition::GeSubquery(column, subquery) => {
+                Condition::build_subquery_comparison(dialect, column, ">=", subquery.as_ref(), None)
+            }
+            Condition::StartsWith(..) | Condition::EndsWith(..) | Condition::Contains(..) => {
+                let (column, pattern) = self.like_pattern().unwrap_or_default();
+                format!(
+                    "{} LIKE '{}' ESCAPE '!'",
+                    dialect.quote_identifier(column),
+                    pattern.replace('\'', "''")
+                )
+            }
+        }
+    }
+
+    /// Builds the SQL representation of the condition with placeholders for its values.
+    ///
+    /// The values are added to `params` in the order of their placeholders, so a
+    /// condition can be rendered after other parts of a query that bind parameters.
+    /// Literals are always bound, compare two columns with `Value::Column`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect of the placeholders and of the condition syntax.
+    /// * `params` - The bound parameters of the query.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `String` representing the SQL condition.
+    pub fn build_with_params(&self, dialect: Dialect, params: &mut Params<'a>) -> String {
+        let comparison =
+            |column: &str, operator: &str, value: &Value<'a>, params: &mut Params<'a>| {
+                format!(
+                    "{} {} {}",
+                    dialect.quote_identifier(column),
+                    operator,
+                    Condition::build_param(value, dialect, params)
+                )
+            };
+
+        match self {
+            Condition::Eq(column, Value::Null) => {
+                format!("{} IS NULL", dialect.quote_identifier(column))
+            }
+            Condition::Ne(column, Value::Null) => {
+                format!("{} IS NOT NULL", dialect.quote_identifier(column))
+            }
+            Condition::Eq(column, value) => comparison(column, "=", value, params),
+            Condition::Ne(column, value) => comparison(column, "<>", value, params),
+            Condition::Lt(column, value) => comparison(column, "<", value, params),
+            Condition::Gt(column, value) => comparison(column, ">", value, params),
+            Condition::Le(column, value) => comparison(column, "<=", value, params),
+            Condition::Ge(column, value) => comparison(column, ">=", value, params),
+            Condition::And(left, right) => {
+                let left = left.build_with_params(dialect, params);
+                let right = right.build_with_params(dialect, params);
+                format!("({}) AND ({})", left, right)
+            }
+            Condition::Or(left, right) => {
+                let left = left.build_with_params(dialect, params);
+                let right = right.build_with_params(dialect, params);
+                format!("({}) OR ({})", left, right)
+            }
+            Condition::In(column, values) | Condition::NotIn(column, values) => {
+                let operator = if matches!(self, Condition::In(..)) {
+                    "IN"
+                } else {
+                    "NOT IN"
+                };
+                let values = values
+                    .iter()
+                    .map(|value| match value {
+                        Value::Subquery(subquery) => subquery.to_sql_with_params(params),
+                        _ => Condition::build_param(value, dialect, params),
+                    })
                     .collect::<Vec<String>>()
                     .join(", ");
-                format!("{} NOT IN ({})", column, values)
+                format!(
+                    "{} {} ({})",
+                    dialect.quote_identifier(column),
+                    operator,
+                    values
+                )
+            }
+            Condition::NullSafeEq(column, value) => dialect.null_safe_eq(
+                &dialect.quote_identifier(column),
+                &Condition::build_param(value, dialect, params),
+            ),
+            Condition::JsonExtractEq(column, path, value) => format!(
+                "{} = {}",
+                dialect.json_extract(&dialect.quote_identifier(column), path),
+                Condition::build_param(value, dialect, params)
+            ),
+            Condition::InTuple(columns, rows) => {
+                let columns = columns
+                    .iter()
+                    .map(|column| dialect.quote_identifier(column))
+                    .collect::<Vec<String>>();
+                let rows = rows
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|value| Condition::build_param(value, dialect, params))
+                            .collect()
+                    })
+                    .collect::<Vec<Vec<String>>>();
+                dialect.in_tuple(&columns, &rows)
+            }
+            Condition::GtTuple(columns, values) if !dialect.supports_row_values() => {
+                Condition::expand_gt_tuple(columns, values).build_with_params(dialect, params)
+            }
+            Condition::GtTuple(columns, values) => {
+                let columns = columns
+                    .iter()
+                    .map(|column| dialect.quote_identifier(column))
+                    .collect::<Vec<String>>();
+                let values = values
+                    .iter()
+                    .map(|value| Condition::build_param(value, dialect, params))
+                    .collect::<Vec<String>>();
+                format!("({}) > ({})", columns.join(", "), values.join(", "))
+            }
+            Condition::Raw(sql, values) => Condition::fill_placeholders(sql, values, |value| {
+                Condition::build_param(value, dialect, params)
+            }),
+            Condition::EqSubquery(column, subquery) => Condition::build_subquery_comparison(
+                dialect,
+                column,
+                "=",
+                subquery.as_ref(),
+                Some(params),
+            ),
+            Condition::NeSubquery(column, subquery) => Condition::build_subquery_comparison(
+                dialect,
+                column,
+                "<>",
+                subquery.as_ref(),
+                Some(params),
+            ),
+            Condition::LtSubquery(column, subquery) => Condition::build_subquery_comparison(
+                dialect,
+                column,
+                "<",
+                subquery.as_ref(),
+                Some(params),
+            ),
+            Condition::GtSubquery(column, subquery) => Condition::build_subquery_comparison(
+                dialect,
+                column,
+                ">",
+                subquery.as_ref(),
+                Some(params),
+            ),
+            Condition::LeSubquery(column, subquery) => Condition::build_subquery_comparison(
+                dialect,
+                column,
+                "<=",
+                subquery.as_ref(),
+                Some(params),
+            ),
+            Condition::GeSubquery(column, subquery) => Condition::build_subquery_comparison(
+                dialect,
+                column,
+                ">=",
+                subquery.as_ref(),
+                Some(params),
+            ),
+            Condition::StartsWith(..) | Condition::EndsWith(..) | Condition::Contains(..) => {
+                let (column, pattern) = self.like_pattern().unwrap_or_default();
+                format!(
+                    "{} LIKE {} ESCAPE '!'",
+                    dialect.quote_identifier(column),
+                    params.bind(dialect, Value::Literal(pattern))
+                )
             }
         }
     }
@@ -0,0 +1,143 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+/// The settings of a connection that the query builders read when they build and execute
+/// statements on it.
+///
+/// A connection is configured by wrapping it in a `ConfiguredConnection`, connections
+/// that are used directly have the default settings.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let config = ConnectionConfig {
+///     table_prefix: "app_".to_string(),
+///     ..Default::default()
+/// };
+/// let conn = ConfiguredConnection::new(sqlite::open(path)?, config);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionConfig {
+    /// The prefix prepended to the name of every table, e.g. `app_` for `app_users`,
+    /// so several applications can share a database.
+    pub table_prefix: String,
+    /// The minimum time of a statement that is logged as a slow query, `None` disables
+    /// the logging. Slow queries are only logged by the SQLite backend.
+    pub slow_query_threshold: Option<Duration>,
+}
+
+/// The settings of connections that are not wrapped in a `ConfiguredConnection`.
+pub(crate) static DEFAULT_CONFIG: ConnectionConfig = ConnectionConfig {
+    table_prefix: String::new(),
+    slow_query_threshold: None,
+};
+
+/// A connection of any backend with its `ConnectionConfig`.
+///
+/// The wrapper is passed to the query builders in place of the connection.
+pub struct ConfiguredConnection<C> {
+    conn: C,
+    config: ConnectionConfig,
+}
+
+impl<C> ConfiguredConnection<C> {
+    /// Wraps a connection with its settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - The database connection.
+    /// * `config` - The settings of the connection.
+    pub fn new(conn: C, config: ConnectionConfig) -> Self {
+        ConfiguredConnection { conn, config }
+    }
+
+    /// Returns the settings of the connection.
+    pub fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    /// Returns the wrapped connection.
+    pub fn get_ref(&self) -> &C {
+        &self.conn
+    }
+
+    /// Returns the wrapped connection mutably.
+    pub fn get_mut(&mut self) -> &mut C {
+        &mut self.conn
+    }
+
+    /// Returns the wrapped connection, dropping its settings.
+    pub fn into_inner(self) -> C {
+        self.conn
+    }
+}
+
+/// A connection of type `C` that the query builders of the MySQL, MariaDB, Oracle and
+/// SQL Server backends accept, either the connection itself with the default settings
+/// or a `ConfiguredConnection` of it.
+pub trait Session<C> {
+    /// Returns the connection the statements are executed on.
+    fn connection(&self) -> &C;
+
+    /// Returns the connection the statements are executed on mutably.
+    fn connection_mut(&mut self) -> &mut C;
+
+    /// Returns the settings of the connection.
+    fn config(&self) -> &ConnectionConfig;
+}
+
+impl<C> Session<C> for C {
+    fn connection(&self) -> &C {
+        self
+    }
+
+    fn connection_mut(&mut self) -> &mut C {
+        self
+    }
+
+    fn config(&self) -> &ConnectionConfig {
+        &DEFAULT_CONFIG
+    }
+}
+
+impl<C> Session<C> for ConfiguredConnection<C> {
+    fn connection(&self) -> &C {
+        &self.conn
+    }
+
+    fn connection_mut(&mut self) -> &mut C {
+        &mut self.conn
+    }
+
+    fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+}
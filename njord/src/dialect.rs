@@ -0,0 +1,447 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+/// Represents how identifiers are rendered, see `Dialect::normalize_identifier`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IdentifierCase {
+    /// Identifiers are quoted as written, table names are left unquoted.
+    #[default]
+    Preserve,
+    /// Identifiers are folded to the case the database folds unquoted identifiers to,
+    /// uppercase for Oracle and lowercase for PostgreSQL, so the quoted identifiers
+    /// match the unquoted identifiers.
+    Fold,
+    /// Identifiers and table names are quoted, so the database keeps their case.
+    Quote,
+    /// Identifiers are rendered as written without quotes, opting out of quoting, e.g.
+    /// for tables created with unquoted names in a database that folds their case.
+    /// Columns of conditions are still quoted, `Condition::raw` renders SQL as written.
+    Unquoted,
+}
+
+/// Represents the SQL dialect of a database backend.
+///
+/// Used when rendering conditions that differ in syntax between databases.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// SQLite, the default dialect.
+    #[default]
+    Sqlite,
+    /// MySQL.
+    MySql,
+    /// MariaDB.
+    MariaDb,
+    /// Oracle.
+    Oracle,
+    /// Microsoft SQL Server.
+    MsSql,
+    /// PostgreSQL, only for rendering SQL, there is no PostgreSQL backend.
+    Postgres,
+}
+
+impl Dialect {
+    /// Renders a NULL-safe equality comparison, where NULL matches NULL.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - The left-hand side of the comparison, usually a column.
+    /// * `right` - The rendered right-hand side of the comparison.
+    pub fn null_safe_eq(&self, left: &str, right: &str) -> String {
+        match self {
+            Dialect::Sqlite => format!("{} IS {}", left, right),
+            Dialect::MySql | Dialect::MariaDb => format!("{} <=> {}", left, right),
+            // DECODE considers two NULLs to be equal
+            Dialect::Oracle => format!("DECODE({}, {}, 1, 0) = 1", left, right),
+            Dialect::MsSql | Dialect::Postgres => {
+                format!("{} IS NOT DISTINCT FROM {}", left, right)
+            }
+        }
+    }
+
+    /// Renders the pagination of a query.
+    ///
+    /// SQLite, MySQL, MariaDB and PostgreSQL use `LIMIT` and `OFFSET`, Oracle and SQL Server
+    /// use `OFFSET ... ROWS FETCH NEXT ... ROWS ONLY`, which requires an ORDER BY clause on
+    /// SQL Server.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum number of rows.
+    /// * `offset` - The number of rows to skip.
+    pub fn pagination(&self, limit: Option<usize>, offset: Option<usize>) -> String {
+        match self {
+            Dialect::Sqlite | Dialect::MySql | Dialect::MariaDb | Dialect::Postgres => format!(
+                "{} {}",
+                limit.map(|l| format!("LIMIT {}", l)).unwrap_or_default(),
+                offset.map(|o| format!("OFFSET {}", o)).unwrap_or_default()
+            ),
+            Dialect::Oracle | Dialect::MsSql => match (limit, offset) {
+                (None, None) => String::new(),
+                (limit, offset) => {
+                    let offset_str = format!("OFFSET {} ROWS", offset.unwrap_or(0));
+                    match limit {
+                        Some(limit) => format!("{} FETCH NEXT {} ROWS ONLY", offset_str, limit),
+                        None => offset_str,
+                    }
+                }
+            },
+        }
+    }
+
+    /// Renders the placeholder of a bound parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The 1-based position of the parameter in the query.
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            Dialect::Sqlite | Dialect::MySql | Dialect::MariaDb => "?".to_string(),
+            Dialect::Oracle => format!(":{}", index),
+            Dialect::MsSql => format!("@P{}", index),
+            Dialect::Postgres => format!("${}", index),
+        }
+    }
+
+    /// Quotes an identifier, e.g. a column or table name, so reserved words like `order`
+    /// can be used as identifiers.
+    ///
+    /// Identifiers are quoted with `"` for SQLite, Oracle and PostgreSQL, `` ` `` for MySQL
+    /// and MariaDB and `[]` for SQL Server. Each part of a qualified name like
+    /// `orders.order` is quoted separately. Anything that is not a plain identifier, e.g.
+    /// an expression or `*`, is returned as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The identifier to quote.
+    pub fn quote_identifier(&self, identifier: &str) -> String {
+        if !identifier.split('.').all(is_plain_identifier) {
+            return identifier.to_string();
+        }
+
+        identifier
+            .split('.')
+            .map(|part| self.quote(part))
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+
+    /// Renders an identifier according to an `IdentifierCase`.
+    ///
+    /// With `Fold`, each part of the identifier is folded to uppercase for Oracle and to
+    /// lowercase for PostgreSQL before it is quoted, other databases don't fold identifiers.
+    /// With `Unquoted`, the identifier is returned as is. Like with `quote_identifier`,
+    /// anything that is not a plain identifier is returned as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The identifier to render.
+    /// * `case` - How to render the identifier.
+    pub fn normalize_identifier(&self, identifier: &str, case: IdentifierCase) -> String {
+        match case {
+            IdentifierCase::Preserve | IdentifierCase::Quote => self.quote_identifier(identifier),
+            IdentifierCase::Fold => self.quote_identifier(&self.fold_case(identifier)),
+            IdentifierCase::Unquoted => identifier.to_string(),
+        }
+    }
+
+    /// Folds an identifier to the case the database folds unquoted identifiers to.
+    fn fold_case(&self, identifier: &str) -> String {
+        if !identifier.split('.').all(is_plain_identifier) {
+            return identifier.to_string();
+        }
+        match self {
+            Dialect::Oracle => identifier.to_uppercase(),
+            Dialect::Postgres => identifier.to_lowercase(),
+            _ => identifier.to_string(),
+        }
+    }
+
+    /// Quotes a single part of an identifier.
+    fn quote(&self, part: &str) -> String {
+        match self {
+            Dialect::Sqlite | Dialect::Oracle | Dialect::Postgres => format!("\"{}\"", part),
+            Dialect::MySql | Dialect::MariaDb => format!("`{}`", part),
+            Dialect::MsSql => format!("[{}]", part),
+        }
+    }
+
+    /// Renders a multi-column IN condition, e.g. `(a, b) IN ((1, 'x'), (2, 'y'))`.
+    ///
+    /// SQLite only accepts row values as a `VALUES` list on the right-hand side, SQL Server
+    /// has no row values, so the condition is emulated with an OR of ANDs. An empty list
+    /// of rows matches nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The quoted columns.
+    /// * `rows` - The rendered values of each row, in the order of the columns.
+    pub fn in_tuple(&self, columns: &[String], rows: &[Vec<String>]) -> String {
+        if rows.is_empty() {
+            return "1 = 0".to_string();
+        }
+
+        if *self == Dialect::MsSql {
+            let rows = rows
+                .iter()
+                .map(|row| {
+                    let equalities = columns
+                        .iter()
+                        .zip(row)
+                        .map(|(column, value)| format!("{} = {}", column, value))
+                        .collect::<Vec<String>>()
+                        .join(" AND ");
+                    format!("({})", equalities)
+                })
+                .collect::<Vec<String>>()
+                .join(" OR ");
+            return format!("({})", rows);
+        }
+
+        let rows = rows
+            .iter()
+            .map(|row| format!("({})", row.join(", ")))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let rows = match self {
+            Dialect::Sqlite => format!("VALUES {}", rows),
+            _ => rows,
+        };
+
+        format!("({}) IN ({})", columns.join(", "), rows)
+    }
+
+    /// Returns `true` if the database compares row values, e.g. `(a, b) > (1, 2)`.
+    ///
+    /// Oracle only compares row values for equality, SQL Server has no row values.
+    pub fn supports_row_values(&self) -> bool {
+        !matches!(self, Dialect::Oracle | Dialect::MsSql)
+    }
+
+    /// Returns `true` if the ORDER BY columns of a SELECT DISTINCT query must be selected.
+    ///
+    /// SQLite and MariaDB order by any column of the table.
+    pub fn requires_selected_distinct_order(&self) -> bool {
+        !matches!(self, Dialect::Sqlite | Dialect::MariaDb)
+    }
+
+    /// Renders the extraction of a scalar value from a JSON column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The JSON column.
+    /// * `path` - The JSON path, e.g. `$.address.city`.
+    pub fn json_extract(&self, column: &str, path: &str) -> String {
+        match self {
+            Dialect::Sqlite => format!("json_extract({}, '{}')", column, path),
+            Dialect::MySql => format!("JSON_EXTRACT({}, '{}')", column, path),
+            Dialect::MariaDb | Dialect::Oracle | Dialect::MsSql => {
+                format!("JSON_VALUE({}, '{}')", column, path)
+            }
+            Dialect::Postgres => {
+                let keys = path
+                    .trim_start_matches('$')
+                    .trim_start_matches('.')
+                    .replace('.', ",");
+                format!("{}::jsonb #>> '{{{}}}'", column, keys)
+            }
+        }
+    }
+
+    /// Renders a `CAST(expression AS type)` expression.
+    ///
+    /// The common type names `INTEGER`, `TEXT`, `REAL` and `BLOB` (and aliases like `INT`,
+    /// `VARCHAR`, `DOUBLE` or `BINARY`) are mapped to the type the database casts to,
+    /// e.g. `SIGNED` for MySQL. Other type names are used as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The column or expression to cast.
+    /// * `type_name` - The type to cast to.
+    pub fn cast(&self, expression: &str, type_name: &str) -> String {
+        let type_name = match type_name.to_uppercase().as_str() {
+            "INTEGER" | "INT" | "BIGINT" => match self {
+                Dialect::Sqlite => "INTEGER",
+                Dialect::MySql | Dialect::MariaDb => "SIGNED",
+                Dialect::Oracle => "NUMBER(19)",
+                Dialect::Postgres | Dialect::MsSql => "BIGINT",
+            },
+            "TEXT" | "VARCHAR" | "STRING" => match self {
+                Dialect::Sqlite | Dialect::Postgres => "TEXT",
+                Dialect::MySql | Dialect::MariaDb => "CHAR",
+                Dialect::Oracle => "VARCHAR2(4000)",
+                Dialect::MsSql => "NVARCHAR(MAX)",
+            },
+            "REAL" | "FLOAT" | "DOUBLE" => match self {
+                Dialect::Sqlite => "REAL",
+                Dialect::MySql | Dialect::MariaDb => "DOUBLE",
+                Dialect::Oracle => "BINARY_DOUBLE",
+                Dialect::Postgres => "DOUBLE PRECISION",
+                Dialect::MsSql => "FLOAT",
+            },
+            "BLOB" | "BINARY" => match self {
+                Dialect::Sqlite => "BLOB",
+                Dialect::MySql | Dialect::MariaDb => "BINARY",
+                Dialect::Oracle => "RAW(2000)",
+                Dialect::Postgres => "BYTEA",
+                Dialect::MsSql => "VARBINARY(MAX)",
+            },
+            _ => type_name,
+        };
+
+        format!("CAST({} AS {})", expression, type_name)
+    }
+}
+
+/// Returns `true` if the part of an identifier is a plain name that can be quoted.
+fn is_plain_identifier(part: &str) -> bool {
+    part.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::condition::{Condition, Value};
+
+    #[test]
+    fn test_null_safe_eq() {
+        let condition = Condition::NullSafeEq("email".to_string(), Value::Null);
+
+        assert_eq!(condition.build_with(Dialect::Sqlite), "\"email\" IS NULL");
+        assert_eq!(condition.build_with(Dialect::MySql), "`email` <=> NULL");
+        assert_eq!(
+            condition.build_with(Dialect::Oracle),
+            "DECODE(\"email\", NULL, 1, 0) = 1"
+        );
+        assert_eq!(
+            condition.build_with(Dialect::MsSql),
+            "[email] IS NOT DISTINCT FROM NULL"
+        );
+    }
+
+    #[test]
+    fn test_placeholder() {
+        assert_eq!(Dialect::Sqlite.placeholder(2), "?");
+        assert_eq!(Dialect::Postgres.placeholder(2), "$2");
+        assert_eq!(Dialect::Oracle.placeholder(2), ":2");
+        assert_eq!(Dialect::MsSql.placeholder(2), "@P2");
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(Dialect::Sqlite.quote_identifier("order"), "\"order\"");
+        assert_eq!(
+            Dialect::MySql.quote_identifier("orders.order"),
+            "`orders`.`order`"
+        );
+        assert_eq!(Dialect::MsSql.quote_identifier("select"), "[select]");
+        assert_eq!(Dialect::Sqlite.quote_identifier("username"), "\"username\"");
+        assert_eq!(Dialect::Sqlite.quote_identifier("COUNT(order)"), "COUNT(order)");
+    }
+
+    #[test]
+    fn test_cast() {
+        assert_eq!(Dialect::Sqlite.cast("code", "int"), "CAST(code AS INTEGER)");
+        assert_eq!(Dialect::MySql.cast("code", "INTEGER"), "CAST(code AS SIGNED)");
+        assert_eq!(Dialect::Oracle.cast("code", "TEXT"), "CAST(code AS VARCHAR2(4000))");
+        assert_eq!(
+            Dialect::Postgres.cast("code", "NUMERIC(10, 2)"),
+            "CAST(code AS NUMERIC(10, 2))"
+        );
+    }
+
+    #[test]
+    fn test_json_extract_eq() {
+        let condition = Condition::json_extract_eq(
+            "profile",
+            "address.city",
+            Value::Literal("Stockholm".to_string()),
+        );
+
+        assert_eq!(
+            condition.build_with(Dialect::Sqlite),
+            "json_extract(\"profile\", '$.address.city') = 'Stockholm'"
+        );
+        assert_eq!(
+            condition.build_with(Dialect::MySql),
+            "JSON_EXTRACT(`profile`, '$.address.city') = 'Stockholm'"
+        );
+        assert_eq!(
+            condition.build_with(Dialect::Oracle),
+            "JSON_VALUE(\"profile\", '$.address.city') = 'Stockholm'"
+        );
+    }
+
+    #[test]
+    fn test_in_tuple() {
+        let condition = Condition::in_tuple(
+            &["id", "name"],
+            vec![
+                vec![Value::Literal("1".to_string()), Value::Literal("x".to_string())],
+                vec![Value::Literal("2".to_string()), Value::Literal("y".to_string())],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            condition.build_with(Dialect::Sqlite),
+            "(\"id\", \"name\") IN (VALUES (1, 'x'), (2, 'y'))"
+        );
+        assert_eq!(
+            condition.build_with(Dialect::Postgres),
+            "(\"id\", \"name\") IN ((1, 'x'), (2, 'y'))"
+        );
+        assert_eq!(
+            condition.build_with(Dialect::MsSql),
+            "(([id] = 1 AND [name] = 'x') OR ([id] = 2 AND [name] = 'y'))"
+        );
+    }
+
+    #[test]
+    fn test_gt_tuple() {
+        let condition = Condition::gt_tuple(
+            &["created_at", "id"],
+            vec![
+                Value::Literal("2024-01-01".to_string()),
+                Value::Literal("3".to_string()),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            condition.build_with(Dialect::Sqlite),
+            "(\"created_at\", \"id\") > ('2024-01-01', 3)"
+        );
+        assert_eq!(
+            condition.build_with(Dialect::MsSql),
+            "([created_at] > '2024-01-01') OR (([created_at] = '2024-01-01') AND ([id] > 3))"
+        );
+        assert!(Condition::gt_tuple(&["created_at", "id"], vec![Value::Null]).is_err());
+    }
+}
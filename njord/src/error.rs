@@ -0,0 +1,331 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+
+use crate::retry::Transient;
+
+/// The underlying error of an `Error`, usually the error of the database driver.
+pub type Source = Box<dyn std::error::Error + Send + Sync>;
+
+/// Represents errors that can occur across all database backends.
+///
+/// Every backend maps its driver errors into these kinds, the driver error is
+/// available through `source()`.
+#[derive(Debug)]
+pub enum Error {
+    /// The connection to the database could not be established or was lost.
+    ConnectionFailed(Source),
+    /// The database rejected or failed to execute a query.
+    QueryFailed(Source),
+    /// A value returned by the database could not be decoded into a field.
+    Decode(Source),
+    /// A constraint was violated, e.g. a unique or foreign key constraint.
+    Conflict(Source),
+    /// The database was deadlocked and aborted the statement.
+    Deadlock(Source),
+    /// The operation timed out, e.g. because the database was busy or locked.
+    Timeout(Source),
+    /// The statement writes to the database, but the connection is read-only.
+    ReadOnly(Source),
+    /// A value returned by the database has a different type than the field it is decoded into.
+    TypeMismatch(String),
+    /// An integer value does not fit in the integer type of the database column.
+    IntegerOverflow(String),
+    /// A selected column of a query with joins is ambiguous, e.g. an `id` column of two tables.
+    AmbiguousColumn(String),
+    /// The row was changed since it was read, e.g. the version of an optimistic update did not match.
+    StaleData(String),
+    /// A selected column is neither aggregated nor part of the GROUP BY clause.
+    InvalidGroupBy(String),
+    /// An ORDER BY column of a SELECT DISTINCT query is not selected.
+    InvalidDistinctOrder(String),
+    /// The query could not be built.
+    InvalidQuery(String),
+    /// The feature is not supported by the database backend.
+    Unsupported(String),
+    /// A file, e.g. a migration, could not be read.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ConnectionFailed(e) => write!(f, "connection failed: {}", e),
+            Error::QueryFailed(e) => write!(f, "query failed: {}", e),
+            Error::Decode(e) => write!(f, "failed to decode value: {}", e),
+            Error::Conflict(e) => write!(f, "constraint violation: {}", e),
+            Error::Deadlock(e) => write!(f, "deadlock detected: {}", e),
+            Error::Timeout(e) => write!(f, "operation timed out: {}", e),
+            Error::ReadOnly(e) => write!(f, "read-only connection: {}", e),
+            Error::TypeMismatch(message) => write!(f, "type mismatch: {}", message),
+            Error::IntegerOverflow(message) => write!(f, "integer overflow: {}", message),
+            Error::AmbiguousColumn(column) => write!(
+                f,
+                "ambiguous column `{}`, qualify it with the table name and give it an alias with `AS`",
+                column
+            ),
+            Error::StaleData(message) => write!(f, "stale data: {}", message),
+            Error::InvalidGroupBy(column) => write!(
+                f,
+                "column `{}` must appear in the GROUP BY clause or be used in an aggregate function",
+                column
+            ),
+            Error::InvalidDistinctOrder(column) => write!(
+                f,
+                "ORDER BY column `{}` must be selected in a SELECT DISTINCT query",
+                column
+            ),
+            Error::InvalidQuery(message) => write!(f, "invalid query: {}", message),
+            Error::Unsupported(message) => write!(f, "unsupported: {}", message),
+            Error::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::ConnectionFailed(e)
+            | Error::QueryFailed(e)
+            | Error::Decode(e)
+            | Error::Conflict(e)
+            | Error::Deadlock(e)
+            | Error::Timeout(e)
+            | Error::ReadOnly(e) => Some(e.as_ref()),
+            Error::Io(e) => Some(e),
+            Error::TypeMismatch(_)
+            | Error::IntegerOverflow(_)
+            | Error::AmbiguousColumn(_)
+            | Error::StaleData(_)
+            | Error::InvalidGroupBy(_)
+            | Error::InvalidDistinctOrder(_)
+            | Error::InvalidQuery(_)
+            | Error::Unsupported(_) => None,
+        }
+    }
+}
+
+impl Transient for Error {
+    fn is_transient(&self) -> bool {
+        matches!(self, Error::Deadlock(_) | Error::Timeout(_))
+    }
+}
+
+impl From<std::io::Error> for Error {
+    /// Converts a `std::io::Error` into an `Error`.
+    fn from(error: std::io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for Error {
+    /// Converts a `rusqlite::Error` into an `Error`.
+    fn from(error: rusqlite::Error) -> Self {
+        use rusqlite::ErrorCode;
+
+        match error.sqlite_error_code() {
+            Some(ErrorCode::ConstraintViolation) => Error::Conflict(Box::new(error)),
+            Some(ErrorCode::DatabaseBusy) | Some(ErrorCode::DatabaseLocked) => {
+                Error::Timeout(Box::new(error))
+            }
+            Some(ErrorCode::ReadOnly) => Error::ReadOnly(Box::new(error)),
+            Some(ErrorCode::CannotOpen) | Some(ErrorCode::NotADatabase) => {
+                Error::ConnectionFailed(Box::new(error))
+            }
+            _ => match error {
+                rusqlite::Error::FromSqlConversionFailure(..)
+                | rusqlite::Error::IntegralValueOutOfRange(..)
+                | rusqlite::Error::InvalidColumnType(..) => Error::Decode(Box::new(error)),
+                _ => Error::QueryFailed(Box::new(error)),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<crate::sqlite::SqliteError> for Error {
+    /// Converts a `SqliteError` into an `Error`.
+    fn from(error: crate::sqlite::SqliteError) -> Self {
+        use crate::sqlite::SqliteError;
+
+        match error {
+            SqliteError::SelectError(e)
+            | SqliteError::InsertError(e)
+            | SqliteError::UpdateError(e)
+            | SqliteError::DeleteError(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "mariadb"))]
+impl From<mysql::Error> for Error {
+    /// Converts a `mysql::Error` into an `Error`.
+    fn from(error: mysql::Error) -> Self {
+        match error {
+            // 1062: duplicate entry, 1451/1452: foreign key constraint fails
+            mysql::Error::MySqlError(ref e) if matches!(e.code, 1062 | 1451 | 1452) => {
+                Error::Conflict(Box::new(error))
+            }
+            mysql::Error::MySqlError(ref e) if e.code == 1213 => Error::Deadlock(Box::new(error)),
+            // 1205: lock wait timeout exceeded, 3024: maximum statement execution time exceeded
+            mysql::Error::MySqlError(ref e) if matches!(e.code, 1205 | 3024) => {
+                Error::Timeout(Box::new(error))
+            }
+            // 1290: running with --read-only, 1792: read-only transaction
+            mysql::Error::MySqlError(ref e) if matches!(e.code, 1290 | 1792) => {
+                Error::ReadOnly(Box::new(error))
+            }
+            mysql::Error::MySqlError(_) => Error::QueryFailed(Box::new(error)),
+            mysql::Error::FromValueError(_) | mysql::Error::FromRowError(_) => {
+                Error::Decode(error.to_string().into())
+            }
+            _ => Error::ConnectionFailed(Box::new(error)),
+        }
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl From<crate::mysql::MySqlError> for Error {
+    /// Converts a `MySqlError` into an `Error`.
+    fn from(error: crate::mysql::MySqlError) -> Self {
+        use crate::mysql::MySqlError;
+
+        match error {
+            MySqlError::SelectError(e)
+            | MySqlError::InsertError(e)
+            | MySqlError::UpdateError(e)
+            | MySqlError::DeleteError(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(feature = "mariadb")]
+impl From<crate::mariadb::MariaDBError> for Error {
+    /// Converts a `MariaDBError` into an `Error`.
+    fn from(error: crate::mariadb::MariaDBError) -> Self {
+        use crate::mariadb::MariaDBError;
+
+        match error {
+            MariaDBError::SelectError(e)
+            | MariaDBError::InsertError(e)
+            | MariaDBError::UpdateError(e)
+            | MariaDBError::DeleteError(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(feature = "oracle")]
+impl From<oracle::Error> for Error {
+    /// Converts an `oracle::Error` into an `Error`.
+    fn from(error: oracle::Error) -> Self {
+        use oracle::ErrorKind;
+
+        match error.kind() {
+            ErrorKind::OciError | ErrorKind::DpiError => {
+                match error.db_error().map(|e| e.code()) {
+                    // ORA-00001: unique constraint, ORA-02291/02292: integrity constraint
+                    Some(1) | Some(2291) | Some(2292) => Error::Conflict(Box::new(error)),
+                    // ORA-00060: deadlock detected
+                    Some(60) => Error::Deadlock(Box::new(error)),
+                    // ORA-01013: user requested cancel, raised when the call timeout is exceeded
+                    Some(1013) => Error::Timeout(Box::new(error)),
+                    // ORA-01017: invalid credentials, ORA-03113/03114/12xxx: connection errors
+                    Some(1017) | Some(3113) | Some(3114) | Some(12150..=12999) => {
+                        Error::ConnectionFailed(Box::new(error))
+                    }
+                    _ => Error::QueryFailed(Box::new(error)),
+                }
+            }
+            ErrorKind::NullValue
+            | ErrorKind::ParseError
+            | ErrorKind::OutOfRange
+            | ErrorKind::InvalidTypeConversion => Error::Decode(Box::new(error)),
+            _ => Error::QueryFailed(Box::new(error)),
+        }
+    }
+}
+
+#[cfg(feature = "oracle")]
+impl From<crate::oracle::OracleError> for Error {
+    /// Converts an `OracleError` into an `Error`.
+    fn from(error: crate::oracle::OracleError) -> Self {
+        use crate::oracle::OracleError;
+
+        match error {
+            OracleError::SelectError(e)
+            | OracleError::InsertError(e)
+            | OracleError::UpdateError(e)
+            | OracleError::DeleteError(e) => e.into(),
+        }
+    }
+}
+
+#[cfg(feature = "mssql")]
+impl From<tiberius::error::Error> for Error {
+    /// Converts a `tiberius::error::Error` into an `Error`.
+    fn from(error: tiberius::error::Error) -> Self {
+        use tiberius::error::Error as MSSQLLibError;
+
+        match error {
+            // 2601/2627: duplicate key, 547: constraint conflict
+            MSSQLLibError::Server(ref e) if matches!(e.code(), 547 | 2601 | 2627) => {
+                Error::Conflict(Box::new(error))
+            }
+            MSSQLLibError::Server(ref e) if e.code() == 1205 => Error::Deadlock(Box::new(error)),
+            // 1222: lock request time out period exceeded
+            MSSQLLibError::Server(ref e) if e.code() == 1222 => Error::Timeout(Box::new(error)),
+            MSSQLLibError::Io { .. } | MSSQLLibError::Tls(_) | MSSQLLibError::Routing { .. } => {
+                Error::ConnectionFailed(Box::new(error))
+            }
+            MSSQLLibError::Conversion(_)
+            | MSSQLLibError::Utf8
+            | MSSQLLibError::Utf16
+            | MSSQLLibError::ParseInt(_) => Error::Decode(Box::new(error)),
+            _ => Error::QueryFailed(Box::new(error)),
+        }
+    }
+}
+
+#[cfg(feature = "mssql")]
+impl From<crate::mssql::MSSQLError> for Error {
+    /// Converts a `MSSQLError` into an `Error`.
+    fn from(error: crate::mssql::MSSQLError) -> Self {
+        use crate::mssql::MSSQLError;
+
+        match error {
+            MSSQLError::SelectError(e)
+            | MSSQLError::InsertError(e)
+            | MSSQLError::UpdateError(e)
+            | MSSQLError::DeleteError(e) => e.into(),
+            MSSQLError::InvalidQuery => Error::InvalidQuery("invalid query".to_string()),
+        }
+    }
+}
@@ -29,11 +29,23 @@
 
 pub mod column;
 pub mod condition;
+pub mod config;
+pub mod dialect;
+pub mod error;
 pub mod keys;
 pub mod query;
+pub mod reconnect;
+pub mod retry;
+pub mod schema;
 pub mod table;
+pub mod types;
 pub mod util;
 
+pub use error::Error;
+
+#[cfg(feature = "sqlite")]
+pub mod migration;
+
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
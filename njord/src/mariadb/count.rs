@@ -0,0 +1,149 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    condition::Condition, config::Session, dialect::Dialect, error::Error,
+    mariadb::util::generate_where_condition_str, table::Table,
+};
+
+use log::debug;
+use mysql::{prelude::Queryable, PooledConn};
+
+/// Constructs a new query counting the rows matching the WHERE clause.
+///
+/// # Arguments
+///
+/// * `conn` - A `PooledConn` to the MariaDB database.
+/// * `columns` - The columns of the counted rows, used by `distinct`. An empty list
+///   stands for all columns.
+///
+/// # Returns
+///
+/// A `CountQueryBuilder` instance.
+pub fn count(conn: &mut dyn Session<PooledConn>, columns: Vec<String>) -> CountQueryBuilder<'_> {
+    CountQueryBuilder::new(conn, columns)
+}
+
+/// A builder for constructing queries counting the matching rows.
+pub struct CountQueryBuilder<'a> {
+    conn: &'a mut dyn Session<PooledConn>,
+    columns: Vec<String>,
+    table_name: Option<String>,
+    where_condition: Option<Condition<'a>>,
+    distinct: bool,
+}
+
+impl<'a> CountQueryBuilder<'a> {
+    /// Creates a new `CountQueryBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `PooledConn` to the MariaDB database.
+    /// * `columns` - The columns of the counted rows, used by `distinct`.
+    pub fn new(conn: &'a mut dyn Session<PooledConn>, columns: Vec<String>) -> Self {
+        CountQueryBuilder {
+            conn,
+            columns,
+            table_name: None,
+            where_condition: None,
+            distinct: false,
+        }
+    }
+
+    /// Sets the table to count the rows of, prefixed with the table prefix of the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table to count the rows of.
+    pub fn from<T: Table>(mut self, table: &T) -> Self {
+        self.table_name = Some(table.get_prefixed_name(&self.conn.config().table_prefix));
+        self
+    }
+
+    /// Sets the WHERE clause condition the rows must match.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition to be applied in the WHERE clause.
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
+        self
+    }
+
+    /// Counts the distinct combinations of the columns instead of all matching rows.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Builds the query string, this function should be used internally.
+    pub fn build_query(&self) -> String {
+        let table_name = self.table_name.clone().unwrap_or_default();
+        let where_condition_str = generate_where_condition_str(self.where_condition.clone());
+
+        if !self.distinct {
+            return format!(
+                "SELECT COUNT(*) FROM {} {}",
+                table_name, where_condition_str
+            );
+        }
+
+        let columns_str = if self.columns.is_empty() {
+            "*".to_string()
+        } else {
+            self.columns
+                .iter()
+                .map(|column| Dialect::MariaDb.quote_identifier(column))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        // The derived table needs an alias, given without AS for Oracle
+        format!(
+            "SELECT COUNT(*) FROM (SELECT DISTINCT {} FROM {} {}) njord_distinct",
+            columns_str, table_name, where_condition_str
+        )
+    }
+
+    /// Builds and executes the query.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of matching rows, or distinct combinations
+    /// of the columns, or an `Error` if an error occurs during the execution.
+    pub fn build(self) -> Result<usize, Error> {
+        let query = self.build_query();
+
+        debug!("{}", query);
+
+        let count: Option<u64> = self.conn.connection_mut().query_first(&query)?;
+
+        Ok(count.unwrap_or(0) as usize)
+    }
+}
@@ -29,7 +29,7 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{query::QueryBuilder, table::Table};
+use crate::{config::Session, query::QueryBuilder, table::Table};
 
 use mysql::{prelude::Queryable, PooledConn};
 use rusqlite::Error as RusqliteError;
@@ -48,19 +48,19 @@ use std::fmt::Error;
 ///
 /// * `conn` - A `PooledConn` to the MariaDB database.
 /// * `table_rows` - A vector of objects implementing the `Table` trait representing
-///                  the rows to be inserted into the database.
+///   the rows to be inserted into the database.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the joined SQL statements
 /// if the insertion is successful, or a `RusqliteError` if an error occurs.
 pub fn insert<T: Table>(
-    conn: &mut PooledConn,
+    conn: &mut dyn Session<PooledConn>,
     table_rows: Vec<T>,
 ) -> Result<String, RusqliteError> {
     let mut statements: Vec<String> = Vec::new();
     for (index, table_row) in table_rows.iter().enumerate() {
-        match generate_statement(table_row, index == 0) {
+        match generate_statement(table_row, index == 0, &conn.config().table_prefix) {
             Ok(statement) => statements.push(statement),
             Err(_) => return Err(RusqliteError::InvalidQuery),
         }
@@ -69,7 +69,7 @@ pub fn insert<T: Table>(
     let joined_statements = statements.join(", ");
 
     // FIXME: Convert to transaction
-    match conn.query_drop(&joined_statements) {
+    match conn.connection_mut().query_drop(&joined_statements) {
         Ok(_) => info!("Inserted into table, done."),
         Err(err) => eprintln!("Error: {}", err),
     }
@@ -80,6 +80,35 @@ pub fn insert<T: Table>(
     Ok(joined_statements)
 }
 
+/// Inserts a row with the default values of all columns into a MariaDB table, using
+/// `INSERT INTO t () VALUES ()`, the MariaDB equivalent of `DEFAULT VALUES`.
+///
+/// # Arguments
+///
+/// * `conn` - A `PooledConn` to the MariaDB database.
+/// * `table` - An instance of the table to insert the row into.
+///
+/// # Returns
+///
+/// A `Result` containing the id of the new row if the insertion is successful,
+/// or an `Error` if a column without default is NOT NULL.
+pub fn insert_default<T: Table>(
+    conn: &mut dyn Session<PooledConn>,
+    table: &T,
+) -> Result<u64, crate::error::Error> {
+    let table_name = table
+        .get_prefixed_name(&conn.config().table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
+    let statement = format!("INSERT INTO {} () VALUES ()", table_name);
+
+    debug!("{}", statement);
+
+    conn.connection_mut().query_drop(&statement)?;
+
+    Ok(conn.connection_mut().last_insert_id())
+}
+
 /// Generates an SQL INSERT INTO statement for a given table row.
 ///
 /// # Arguments
@@ -91,15 +120,16 @@ pub fn insert<T: Table>(
 /// A `Result` containing a `String` representing the generated SQL statement
 /// if the operation is successful, or a `RusqliteError` if an error occurs.
 pub fn into<'a, T: Table + Default>(
-    conn: &'a mut PooledConn,
+    conn: &'a mut dyn Session<PooledConn>,
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
 ) -> Result<String, RusqliteError> {
-    let statement = generate_insert_into_statement::<T>(columns, subquery);
+    let statement =
+        generate_insert_into_statement::<T>(columns, subquery, &conn.config().table_prefix);
     let sql = statement?;
 
     // FIXME: Convert to transaction
-    let _ = conn.query_drop(&sql);
+    let _ = conn.connection_mut().query_drop(&sql);
 
     info!("Inserted into table, done.");
 
@@ -113,6 +143,7 @@ pub fn into<'a, T: Table + Default>(
 ///
 /// * `columns` - A `Vec` of column names.
 /// * `subquery` - A `QueryBuilder` object representing the subquery.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
@@ -121,11 +152,15 @@ pub fn into<'a, T: Table + Default>(
 fn generate_insert_into_statement<'a, T: Table + Default>(
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
+    table_prefix: &str,
 ) -> Result<String, RusqliteError> {
     let columns_str = columns.join(", ");
     let subquery_str = subquery.to_sql();
     let table_row = T::default();
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+    let table_name = table_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
 
     let sql = format!(
         "INSERT INTO {} ({}) {}",
@@ -144,15 +179,20 @@ fn generate_insert_into_statement<'a, T: Table + Default>(
 /// # Arguments
 ///
 /// * `table_row` - An object implementing the `Table` trait representing
-///                 a single row of data to be inserted.
+///   a single row of data to be inserted.
 /// * `first_statement` - A boolean flag indicating whether this is the first
-///                       statement to be generated.
+///   statement to be generated.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the generated SQL statement
 /// if successful, or a `Error` if an error occurs during the generation process.
-fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<String, Error> {
+fn generate_statement<T: Table>(
+    table_row: &T,
+    first_statement: bool,
+    table_prefix: &str,
+) -> Result<String, Error> {
     // Generate strings for columns and values
     let mut columns_str = String::new();
     let mut values_str = String::new();
@@ -176,7 +216,10 @@ fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<
     }
 
     // Sanitize table name from unwanted quotations or backslashes
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+    let table_name = table_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
 
     // Remove the trailing comma and space
     if !columns_str.is_empty() {
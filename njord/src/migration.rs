@@ -0,0 +1,344 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection};
+
+use crate::error::Error;
+
+/// The table keeping track of the applied migrations.
+const MIGRATION_HISTORY_TABLE: &str = "CREATE TABLE IF NOT EXISTS migration_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    version TEXT NOT NULL UNIQUE,
+    applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);";
+
+/// A migration with the SQL to apply and to roll it back.
+///
+/// In a migrations directory, every migration is a directory named after its version,
+/// e.g. `00000000000001_create_users`, containing an `up.sql` and a `down.sql` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migration {
+    /// The version of the migration, i.e. the name of its directory.
+    pub version: String,
+    /// The SQL applying the migration.
+    pub up: String,
+    /// The SQL rolling back the migration.
+    pub down: String,
+}
+
+/// Where the `Migrator` reads the migrations from.
+enum MigrationSource {
+    /// A migrations directory on disk.
+    Directory(PathBuf),
+    /// Migrations embedded in the binary.
+    Embedded(Vec<Migration>),
+}
+
+impl Migration {
+    /// Returns the numeric prefix of the version used for ordering, e.g. `00000000000001`.
+    fn number(&self) -> &str {
+        version_number(&self.version)
+    }
+}
+
+/// Applies and rolls back migrations on a SQLite database.
+///
+/// Migrations are applied in version order. Every migration runs in its own transaction
+/// together with the insert into `migration_history`, so a failing migration leaves
+/// neither its changes nor its history row behind.
+///
+/// # Example
+///
+/// ```rust
+/// use njord::migration::Migrator;
+/// use njord::sqlite;
+///
+/// let conn = sqlite::open_in_memory().unwrap();
+/// let migrator = Migrator::new(&conn, "migrations");
+///
+/// // there is no migrations directory, so there is nothing to apply
+/// assert!(migrator.pending().is_err());
+/// ```
+pub struct Migrator<'a> {
+    conn: &'a Connection,
+    source: MigrationSource,
+}
+
+impl<'a> Migrator<'a> {
+    /// Creates a new `Migrator` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    /// * `migrations_dir` - The directory containing the migrations.
+    pub fn new(conn: &'a Connection, migrations_dir: impl AsRef<Path>) -> Self {
+        Migrator {
+            conn,
+            source: MigrationSource::Directory(migrations_dir.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Creates a new `Migrator` instance from migrations embedded in the binary,
+    /// e.g. with `include_str!`, so no migrations directory has to be shipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    /// * `migrations` - The migrations as `(version, up, down)` tuples, in any order.
+    pub fn from_embedded(conn: &'a Connection, migrations: &[(&str, &str, &str)]) -> Self {
+        let migrations = migrations
+            .iter()
+            .map(|(version, up, down)| Migration {
+                version: version.to_string(),
+                up: up.to_string(),
+                down: down.to_string(),
+            })
+            .collect();
+
+        Migrator {
+            conn,
+            source: MigrationSource::Embedded(migrations),
+        }
+    }
+
+    /// Returns all migrations in version order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the migrations, or an `Error` if the migrations
+    /// directory could not be read.
+    pub fn migrations(&self) -> Result<Vec<Migration>, Error> {
+        let mut migrations = match &self.source {
+            MigrationSource::Directory(migrations_dir) => read_migrations_dir(migrations_dir)?,
+            MigrationSource::Embedded(migrations) => migrations.clone(),
+        };
+
+        migrations.sort_by(|a, b| a.number().cmp(b.number()).then(a.version.cmp(&b.version)));
+
+        Ok(migrations)
+    }
+
+    /// Returns the versions of the applied migrations in version order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the applied versions, or an `Error` if the
+    /// migration history could not be read.
+    pub fn applied(&self) -> Result<Vec<String>, Error> {
+        self.conn.execute_batch(MIGRATION_HISTORY_TABLE)?;
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT version FROM migration_history ORDER BY version")?;
+        let versions = stmt
+            .query_map([], |row| row.get::<usize, String>(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+
+        Ok(versions)
+    }
+
+    /// Returns the migrations that have not been applied yet, in version order.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the pending migrations, or an `Error` if the
+    /// migrations or the migration history could not be read.
+    pub fn pending(&self) -> Result<Vec<Migration>, Error> {
+        let applied = self.applied()?;
+
+        Ok(self
+            .migrations()?
+            .into_iter()
+            .filter(|migration| !applied.contains(&migration.version))
+            .collect())
+    }
+
+    /// Returns all migrations in version order, together with whether they are applied.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the migrations and their applied state, or an `Error` if
+    /// the migrations or the migration history could not be read.
+    pub fn status(&self) -> Result<Vec<(Migration, bool)>, Error> {
+        let applied = self.applied()?;
+
+        Ok(self
+            .migrations()?
+            .into_iter()
+            .map(|migration| {
+                let is_applied = applied.contains(&migration.version);
+                (migration, is_applied)
+            })
+            .collect())
+    }
+
+    /// Applies all pending migrations by executing their `up.sql` files.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the versions of the applied migrations, or an `Error`
+    /// if a migration failed. Migrations before the failing one stay applied.
+    pub fn run(&self) -> Result<Vec<String>, Error> {
+        self.run_steps(usize::MAX)
+    }
+
+    /// Applies the next pending migrations in version order, at most `steps` of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - The maximum number of migrations to apply.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the versions of the applied migrations, or an `Error`
+    /// if a migration failed. Migrations before the failing one stay applied.
+    pub fn run_steps(&self, steps: usize) -> Result<Vec<String>, Error> {
+        let mut applied = Vec::new();
+
+        for migration in self.pending()?.into_iter().take(steps) {
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute_batch(&migration.up)?;
+            tx.execute(
+                "INSERT INTO migration_history (version) VALUES (?1)",
+                params![migration.version],
+            )?;
+            tx.commit()?;
+
+            applied.push(migration.version);
+        }
+
+        Ok(applied)
+    }
+
+    /// Rolls back all applied migrations newer than the given version by executing
+    /// their `down.sql` files, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - The version to roll back to, either the full version or its numeric
+    ///   prefix (e.g. `00000000000001`). The migration itself stays applied.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the versions of the rolled back migrations, or an `Error`
+    /// if a migration failed.
+    pub fn rollback(&self, to: &str) -> Result<Vec<String>, Error> {
+        let target = version_number(to);
+
+        self.roll_back_while(|migration| migration.number() > target)
+    }
+
+    /// Rolls back all applied migrations by executing their `down.sql` files, newest
+    /// first, so the migration history is empty afterwards.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the versions of the rolled back migrations, or an `Error`
+    /// if a migration failed. Migrations newer than the failing one stay rolled back.
+    pub fn reset(&self) -> Result<Vec<String>, Error> {
+        self.roll_back_while(|_| true)
+    }
+
+    /// Clears the migration history without executing any `down.sql` file, so the
+    /// schema is left as is and all migrations are pending again.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of removed history rows, or an `Error` if the
+    /// migration history could not be cleared.
+    pub fn clear_history(&self) -> Result<usize, Error> {
+        self.conn.execute_batch(MIGRATION_HISTORY_TABLE)?;
+
+        Ok(self.conn.execute("DELETE FROM migration_history", [])?)
+    }
+
+    /// Rolls back the applied migrations newest first, as long as `predicate` returns
+    /// `true` for them.
+    fn roll_back_while(
+        &self,
+        predicate: impl Fn(&Migration) -> bool,
+    ) -> Result<Vec<String>, Error> {
+        let applied = self.applied()?;
+
+        let mut rolled_back = Vec::new();
+
+        for migration in self.migrations()?.into_iter().rev() {
+            if !predicate(&migration) {
+                break;
+            }
+            if !applied.contains(&migration.version) {
+                continue;
+            }
+
+            let tx = self.conn.unchecked_transaction()?;
+            tx.execute_batch(&migration.down)?;
+            // the down.sql of the initial setup drops the history table itself
+            tx.execute_batch(MIGRATION_HISTORY_TABLE)?;
+            tx.execute(
+                "DELETE FROM migration_history WHERE version = ?1",
+                params![migration.version],
+            )?;
+            tx.commit()?;
+
+            rolled_back.push(migration.version);
+        }
+
+        Ok(rolled_back)
+    }
+}
+
+/// Reads the migrations of a migrations directory.
+fn read_migrations_dir(migrations_dir: &Path) -> Result<Vec<Migration>, Error> {
+    let mut migrations = Vec::new();
+
+    for entry in fs::read_dir(migrations_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(version) = entry.file_name().to_str() {
+            migrations.push(Migration {
+                version: version.to_string(),
+                up: fs::read_to_string(entry.path().join("up.sql"))?,
+                down: fs::read_to_string(entry.path().join("down.sql"))?,
+            });
+        }
+    }
+
+    Ok(migrations)
+}
+
+/// Returns the numeric prefix of a migration version, e.g. `00000000000001`
+/// for `00000000000001_create_users`.
+fn version_number(version: &str) -> &str {
+    version.split('_').next().unwrap_or(version)
+}
@@ -31,10 +31,11 @@
 
 use crate::{
     condition::Condition,
+    config::Session,
     mssql::util::{generate_where_condition_str, remove_quotes_and_backslashes},
 };
 
-use log::{debug, info};
+use log::debug;
 
 use crate::table::Table;
 
@@ -83,12 +84,14 @@ impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
         self
     }
 
     /// Builds and executes the DELETE query.
+    ///
+    /// The table name is prefixed with the table prefix of the connection.
     /// 
     /// # Arguments
     ///
@@ -97,11 +100,11 @@ impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
     /// # Returns
     ///
     /// A `Result` indicating success or failure of the deletion operation.
-    pub async fn build(self, conn: &mut Connection) -> Result<(), String> {
+    pub async fn build(self, conn: &mut (dyn Session<Connection> + Send)) -> Result<(), String> {
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(&conn.config().table_prefix))
             .unwrap_or("".to_string());
 
         // Sanitize table name from unwanted quotations or backslashes
@@ -113,9 +116,15 @@ impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
 
         debug!("{}", query);
 
-        match conn.client.execute(&query, &[]).await {
+        match conn.connection_mut().client.execute(&query, &[]).await {
             Ok(_) => Ok(()),
             Err(err) => Err(err.to_string()),
         }
     }
 }
+
+impl<'a, T: Table + Default> Default for DeleteQueryBuilder<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
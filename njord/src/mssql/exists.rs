@@ -0,0 +1,128 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    condition::Condition, config::Session, error::Error, mssql::util::generate_where_condition_str,
+    table::Table,
+};
+
+use log::info;
+
+use super::Connection;
+
+/// Constructs a new query checking whether a row matching the WHERE clause exists.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the MSSQL database connection.
+///
+/// # Returns
+///
+/// An `ExistsQueryBuilder` instance.
+pub fn exists(conn: &mut (dyn Session<Connection> + Send)) -> ExistsQueryBuilder<'_> {
+    ExistsQueryBuilder::new(conn)
+}
+
+/// A builder for constructing queries checking whether a matching row exists.
+pub struct ExistsQueryBuilder<'a> {
+    conn: &'a mut (dyn Session<Connection> + Send),
+    table_name: Option<String>,
+    where_condition: Option<Condition<'a>>,
+}
+
+impl<'a> ExistsQueryBuilder<'a> {
+    /// Creates a new `ExistsQueryBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A mutable reference to the MSSQL database connection.
+    pub fn new(conn: &'a mut (dyn Session<Connection> + Send)) -> Self {
+        ExistsQueryBuilder {
+            conn,
+            table_name: None,
+            where_condition: None,
+        }
+    }
+
+    /// Sets the table to look for rows in.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table to look for rows in.
+    pub fn from<T: Table>(mut self, table: &T) -> Self {
+        self.table_name = Some(table.get_prefixed_name(&self.conn.config().table_prefix));
+        self
+    }
+
+    /// Sets the WHERE clause condition the rows must match.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition to be applied in the WHERE clause.
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
+        self
+    }
+
+    /// Builds the query string, this function should be used internally.
+    pub fn build_query(&self) -> String {
+        let table_name = self.table_name.clone().unwrap_or_default();
+        let where_condition_str = generate_where_condition_str(self.where_condition.clone());
+
+        // SQL Server does not allow EXISTS as a select expression
+        format!(
+            "SELECT CASE WHEN EXISTS(SELECT 1 FROM {} {}) THEN 1 ELSE 0 END",
+            table_name, where_condition_str
+        )
+    }
+
+    /// Builds and executes the query.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `true` if at least one row matches the condition,
+    /// or an `Error` if an error occurs during the execution.
+    pub async fn build(self) -> Result<bool, Error> {
+        let query = self.build_query();
+
+        info!("{}", query);
+
+        let row = self
+            .conn
+            .connection_mut()
+            .client
+            .query(query, &[])
+            .await?
+            .into_row()
+            .await?;
+        let exists = row.and_then(|row| row.get::<i32, _>(0)).unwrap_or(0);
+
+        Ok(exists == 1)
+    }
+}
@@ -29,7 +29,7 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{mssql::MSSQLError, query::QueryBuilder, table::Table};
+use crate::{config::Session, mssql::MSSQLError, query::QueryBuilder, table::Table};
 
 use log::{debug, info};
 use std::fmt::Error;
@@ -47,19 +47,19 @@ use super::Connection;
 ///
 /// * `conn` - A `Connection` to the MSSQL database.
 /// * `table_rows` - A vector of objects implementing the `Table` trait representing
-///                  the rows to be inserted into the database.
+///   the rows to be inserted into the database.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the joined SQL statements
 /// if the insertion is successful, or a `RusqliteError` if an error occurs.
 pub async fn insert<T: Table>(
-    conn: &mut Connection,
+    conn: &mut (dyn Session<Connection> + Send),
     table_rows: Vec<T>,
 ) -> Result<String, MSSQLError> {
     let mut statements: Vec<String> = Vec::new();
     for (index, table_row) in table_rows.iter().enumerate() {
-        match generate_statement(table_row, index == 0) {
+        match generate_statement(table_row, index == 0, &conn.config().table_prefix) {
             Ok(statement) => statements.push(statement),
             Err(_) => return Err(MSSQLError::InvalidQuery),
         }
@@ -69,7 +69,7 @@ pub async fn insert<T: Table>(
 
     debug!("{}", joined_statements);
 
-    match conn.client.query(&joined_statements, &[]).await {
+    match conn.connection_mut().client.query(&joined_statements, &[]).await {
         Ok(_) => Ok("Inserted into table, done.".to_string()),
         Err(err) => {
             eprintln!("Error: {}", err);
@@ -88,16 +88,17 @@ pub async fn insert<T: Table>(
 ///
 /// A `Result` containing a `String` representing the generated SQL statement
 /// if the operation is successful, or a `RusqliteError` if an error occurs.
-pub fn into<'a, T: Table + Default>(
-    conn: &'a mut Connection,
+pub async fn into<'a, T: Table + Default>(
+    conn: &'a mut (dyn Session<Connection> + Send),
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
 ) -> Result<String, MSSQLError> {
-    let statement = generate_insert_into_statement::<T>(columns, subquery);
+    let statement =
+        generate_insert_into_statement::<T>(columns, subquery, &conn.config().table_prefix);
     let sql = statement.unwrap();
 
     // FIXME: Convert to transaction
-    let _ = conn.client.execute(&sql, &[]);
+    let _ = conn.connection_mut().client.execute(&sql, &[]).await;
 
     info!("Inserted into table, done.");
 
@@ -111,6 +112,7 @@ pub fn into<'a, T: Table + Default>(
 ///
 /// * `columns` - A `Vec` of column names.
 /// * `subquery` - A `QueryBuilder` object representing the subquery.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
@@ -119,11 +121,15 @@ pub fn into<'a, T: Table + Default>(
 fn generate_insert_into_statement<'a, T: Table + Default>(
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
+    table_prefix: &str,
 ) -> Result<String, MSSQLError> {
     let columns_str = columns.join(", ");
     let subquery_str = subquery.to_sql();
     let table_row = T::default();
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+    let table_name = table_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
 
     let sql = format!(
         "INSERT INTO {} ({}) {}",
@@ -142,15 +148,20 @@ fn generate_insert_into_statement<'a, T: Table + Default>(
 /// # Arguments
 ///
 /// * `table_row` - An object implementing the `Table` trait representing
-///                 a single row of data to be inserted.
+///   a single row of data to be inserted.
 /// * `first_statement` - A boolean flag indicating whether this is the first
-///                       statement to be generated.
+///   statement to be generated.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the generated SQL statement
 /// if successful, or a `Error` if an error occurs during the generation process.
-fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<String, Error> {
+fn generate_statement<T: Table>(
+    table_row: &T,
+    first_statement: bool,
+    table_prefix: &str,
+) -> Result<String, Error> {
     // Generate strings for columns and values
     let mut columns_str = String::new();
     let mut values_str = String::new();
@@ -174,7 +185,10 @@ fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<
     }
 
     // Sanitize table name from unwanted quotations or backslashes
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+    let table_name = table_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
 
     // Remove the trailing comma and space
     if !columns_str.is_empty() {
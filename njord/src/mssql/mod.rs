@@ -29,19 +29,25 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+pub mod count;
 pub mod delete;
 pub mod error;
+pub mod exists;
 pub mod insert;
 pub mod select;
 pub mod update;
 mod util;
 
-use tiberius::{error::Error, Client, Config};
+use tiberius::{error::Error, Client, Config, ToSql};
 use tokio::net::TcpStream;
 use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
 
+use crate::condition::Value;
+
+pub use count::count;
 pub use delete::delete;
 pub use error::MSSQLError;
+pub use exists::exists;
 pub use insert::insert;
 pub use select::select;
 pub use update::update;
@@ -97,3 +103,33 @@ pub async fn raw_execute(conn: &mut Connection, sql: &str) -> Result<(), MSSQLEr
         Err(e) => Err(e.into()),
     }
 }
+
+/// Executes a raw SQL statement with positional parameters and returns the number of affected rows.
+///
+/// Parameters are bound to the `@P1`, `@P2`, ... placeholders in order.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `sql` - The SQL statement to execute.
+/// * `params` - The values to bind to the placeholders.
+///
+/// # Returns
+///
+/// A `Result` containing the number of affected rows if successful,
+/// or an `Error` if an error occurs during the execution.
+pub async fn execute(
+    conn: &mut Connection,
+    sql: &str,
+    params: Vec<Value<'_>>,
+) -> Result<usize, crate::Error> {
+    let params = params
+        .iter()
+        .map(|value| value.to_param())
+        .collect::<Result<Vec<Option<String>>, crate::Error>>()?;
+    let params: Vec<&dyn ToSql> = params.iter().map(|param| param as &dyn ToSql).collect();
+
+    let result = conn.client.execute(sql, &params).await?;
+
+    Ok(result.total() as usize)
+}
@@ -32,6 +32,7 @@
 use crate::{
     column::Column,
     condition::Condition,
+    config::Session,
     mssql::util::{
         generate_group_by_str, generate_having_str, generate_order_by_str,
         generate_where_condition_str,
@@ -44,7 +45,7 @@ use log::info;
 use tiberius::{error::Error, ColumnData};
 
 use crate::table::Table;
-use crate::util::{Join, JoinType};
+use crate::util::{table_reference, ungrouped_column, Join, JoinConstraint, JoinType};
 
 use super::Connection;
 
@@ -75,6 +76,7 @@ pub struct SelectQueryBuilder<'a, T: Table + Default> {
     except_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     union_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     joins: Option<Vec<Join<'a>>>,
+    ties_limit: Option<usize>,
 }
 
 impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
@@ -96,6 +98,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
             except_clauses: None,
             union_clauses: None,
             joins: None,
+            ties_limit: None,
         }
     }
 
@@ -130,8 +133,26 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
+        self
+    }
+
+    /// Adds an optional condition to the WHERE clause.
+    ///
+    /// `None` leaves the WHERE clause unchanged, `Some` combines the condition with an
+    /// existing WHERE condition using AND.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The optional condition to be applied in the WHERE clause.
+    pub fn where_opt(mut self, condition: Option<Condition<'a>>) -> Self {
+        if let Some(condition) = condition {
+            self.where_condition = match self.where_condition {
+                Some(existing) => Some(existing.and(condition)),
+                None => Some(condition),
+            };
+        }
         self
     }
 
@@ -160,8 +181,8 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the HAVING clause.
-    pub fn having(mut self, condition: Condition<'a>) -> Self {
-        self.having_condition = Some(condition);
+    pub fn having(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.having_condition = Some(condition.into());
         self
     }
 
@@ -240,8 +261,26 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         self
     }
 
+    /// Limits the result to the first `count` rows, including the rows that tie with
+    /// the last row according to the ORDER BY clause.
+    ///
+    /// Requires an `order_by`, since which rows tie depends on the order.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of rows to return, not counting ties.
+    pub fn limit_with_ties(mut self, count: usize) -> Self {
+        self.ties_limit = Some(count);
+        self
+    }
+
     /// Builds the query string, this function should be used internally.
     pub fn build_query(&self) -> String {
+        self.render("")
+    }
+
+    /// Renders the query with the table names prefixed with `table_prefix`.
+    fn render(&self, table_prefix: &str) -> String {
         let columns_str = self
             .columns
             .iter()
@@ -252,7 +291,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(table_prefix))
             .unwrap_or("".to_string());
 
         // Generate JOIN clauses, if any
@@ -266,12 +305,35 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
                         JoinType::Right => "RIGHT JOIN",
                         JoinType::Full => "FULL OUTER JOIN",
                     };
+                    let constraint_str = match &join.constraint {
+                        JoinConstraint::On(condition) => format!(
+                            "ON {}",
+                            generate_where_condition_str(Some(condition.clone())).replace("WHERE", "")
+                        ),
+                        // SQL Server has no USING, join on the equality of the columns instead
+                        JoinConstraint::Using(columns) => format!(
+                            "ON {}",
+                            columns
+                                .iter()
+                                .map(|column| format!(
+                                    "{}.{} = {}.{}",
+                                    table_name,
+                                    column,
+                                    join.alias.clone().unwrap_or_else(|| join.table.get_prefixed_name(table_prefix)),
+                                    column
+                                ))
+                                .collect::<Vec<String>>()
+                                .join(" AND ")
+                        ),
+                    };
                     format!(
-                        "{} {} ON {}",
+                        "{} {} {}",
                         join_type_str,
-                        join.table.get_name(),
-                        generate_where_condition_str(Some(join.on_condition.clone()))
-                            .replace("WHERE", "")
+                        table_reference(
+                            &join.table.get_prefixed_name(table_prefix),
+                            join.alias.as_deref()
+                        ),
+                        constraint_str
                     )
                 })
                 .collect(),
@@ -292,9 +354,15 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
             String::new()
         };
 
+        let top_str = match self.ties_limit {
+            Some(count) => format!("TOP {} WITH TIES ", count),
+            None => String::new(),
+        };
+
         let mut query = format!(
-            "SELECT {}{} FROM {} {} {} {} {} {}",
+            "SELECT {}{}{} FROM {} {} {} {} {} {}",
             distinct_str,
+            top_str,
             columns_str,
             table_name,
             join_clause,
@@ -307,7 +375,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         // Handle EXCEPT clauses
         if let Some(except_clauses) = &self.except_clauses {
             for except_query in except_clauses {
-                let except_sql = except_query.build_query();
+                let except_sql = except_query.render(table_prefix);
                 query = format!("{} EXCEPT {}", query, except_sql);
             }
         }
@@ -315,7 +383,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         // Handle UNION clauses
         if let Some(union_clauses) = &self.union_clauses {
             for union_query in union_clauses {
-                let union_sql = union_query.build_query();
+                let union_sql = union_query.render(table_prefix);
                 query = format!("{} UNION {}", query, union_sql);
             }
         }
@@ -325,16 +393,30 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
 
     /// Builds and executes the SELECT query.
     ///
+    /// The table names are prefixed with the table prefix of the connection.
+    ///
     /// # Returns
     ///
     /// A `Result` containing a vector of selected table rows if successful,
-    /// or a `rusqlite::Error` if an error occurs during the execution.
-    pub async fn build(&mut self, conn: &mut Connection) -> Result<Vec<T>, Error> {
-        let final_query = self.build_query();
+    /// or an `Error` if the query is invalid or an error occurs during the execution.
+    pub async fn build(&mut self, conn: &mut (dyn Session<Connection> + Send)) -> Result<Vec<T>, crate::error::Error> {
+        if self.ties_limit.is_some() && self.order_by.is_none() {
+            return Err(crate::error::Error::InvalidQuery(
+                "`limit_with_ties` requires an `order_by`".to_string(),
+            ));
+        }
+
+        if let Some(group_by) = &self.group_by {
+            if let Some(column) = ungrouped_column(&self.columns, group_by) {
+                return Err(crate::error::Error::InvalidGroupBy(column));
+            }
+        }
+
+        let final_query = self.render(&conn.config().table_prefix);
 
         info!("{}", final_query);
 
-        raw_execute(&final_query, conn).await
+        Ok(raw_execute(&final_query, conn.connection_mut()).await?)
     }
 }
 
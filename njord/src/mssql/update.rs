@@ -33,10 +33,11 @@ use std::collections::HashMap;
 
 use crate::{
     condition::Condition,
+    config::Session,
     mssql::util::{generate_where_condition_str, remove_quotes_and_backslashes},
 };
 
-use log::{debug, info};
+use log::debug;
 
 use crate::table::Table;
 
@@ -52,13 +53,16 @@ use super::{select::SelectQueryBuilder, Connection};
 /// # Returns
 ///
 /// An `UpdateQueryBuilder` instance.
-pub fn update<T: Table + Default>(conn: &mut Connection, table: T) -> UpdateQueryBuilder<T> {
+pub fn update<T: Table + Default>(
+    conn: &mut (dyn Session<Connection> + Send),
+    table: T,
+) -> UpdateQueryBuilder<'_, T> {
     UpdateQueryBuilder::new(conn, table)
 }
 
 /// A builder for constructing UPDATE queries.
 pub struct UpdateQueryBuilder<'a, T: Table + Default> {
-    conn: &'a mut Connection,
+    conn: &'a mut (dyn Session<Connection> + Send),
     table: Option<T>,
     columns: Vec<String>,
     sub_queries: HashMap<String, SelectQueryBuilder<'a, T>>,
@@ -72,7 +76,7 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     ///
     /// * `conn` - A `Connection` to the MSSQL database.
     /// * `table` - An instance of the table to be updated.
-    pub fn new(conn: &'a mut Connection, table: T) -> Self {
+    pub fn new(conn: &'a mut (dyn Session<Connection> + Send), table: T) -> Self {
         UpdateQueryBuilder {
             conn,
             table: Some(table),
@@ -107,13 +111,15 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
         self
     }
 
     /// Builds and executes the UPDATE query.
     ///
+    /// The table name is prefixed with the table prefix of the connection.
+    ///
     /// # Returns
     ///
     /// A `Result` indicating success or failure of the update operation.
@@ -121,7 +127,7 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(&self.conn.config().table_prefix))
             .unwrap_or("".to_string());
 
         // Sanitize table name from unwanted quotations or backslashes
@@ -172,7 +178,13 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
 
         debug!("{}", query);
 
-        match self.conn.client.execute(query.as_str(), &[]).await {
+        match self
+            .conn
+            .connection_mut()
+            .client
+            .execute(query.as_str(), &[])
+            .await
+        {
             Ok(_) => Ok("Success!".to_string()),
             Err(_) => Err("Could not execute...".to_string()),
         }
@@ -0,0 +1,90 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{error::Error, schema::ColumnDef};
+
+use log::info;
+use mysql::{prelude::Queryable, PooledConn};
+
+/// Lists the tables of the current MySql database, backed by `information_schema`.
+///
+/// # Arguments
+///
+/// * `conn` - A `PooledConn` to the MySql database.
+///
+/// # Returns
+///
+/// A `Result` containing the table names in alphabetical order, or an `Error` if the
+/// schema could not be read.
+pub fn list_tables(conn: &mut PooledConn) -> Result<Vec<String>, Error> {
+    let query = "SELECT table_name FROM information_schema.tables \
+        WHERE table_schema = DATABASE() AND table_type = 'BASE TABLE' ORDER BY table_name";
+    info!("{}", query);
+
+    Ok(conn.query(query)?)
+}
+
+/// Lists the columns of a table in the current MySql database, backed by
+/// `information_schema`.
+///
+/// # Arguments
+///
+/// * `conn` - A `PooledConn` to the MySql database.
+/// * `name` - The name of the table.
+///
+/// # Returns
+///
+/// A `Result` containing the columns in their declared order, or an `Error` if the
+/// schema could not be read. A table that does not exist has no columns.
+pub fn table_columns(conn: &mut PooledConn, name: &str) -> Result<Vec<ColumnDef>, Error> {
+    let query = "SELECT column_name, column_type, is_nullable, column_default, column_key \
+        FROM information_schema.columns \
+        WHERE table_schema = DATABASE() AND table_name = ? ORDER BY ordinal_position";
+    info!("{}", query);
+
+    let columns = conn.exec_map(
+        query,
+        (name,),
+        |(name, data_type, is_nullable, default_value, column_key): (
+            String,
+            String,
+            String,
+            Option<String>,
+            String,
+        )| ColumnDef {
+            name,
+            data_type,
+            nullable: is_nullable == "YES",
+            default_value,
+            primary_key: column_key == "PRI",
+        },
+    )?;
+
+    Ok(columns)
+}
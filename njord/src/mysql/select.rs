@@ -32,6 +32,7 @@
 use crate::{
     column::Column,
     condition::Condition,
+    config::Session,
     mysql::util::{
         generate_group_by_str, generate_having_str, generate_limit_str, generate_offset_str,
         generate_order_by_str, generate_where_condition_str,
@@ -45,7 +46,7 @@ use mysql::prelude::*;
 use mysql::{Error, PooledConn, Value};
 
 use crate::table::Table;
-use crate::util::{Join, JoinType};
+use crate::util::{table_reference, Join, JoinConstraint, JoinType, LockMode};
 
 /// Constructs a new SELECT query builder.
 ///
@@ -75,6 +76,7 @@ pub struct SelectQueryBuilder<'a, T: Table + Default> {
     except_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     union_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     joins: Option<Vec<Join<'a>>>,
+    lock_mode: Option<LockMode>,
 }
 
 impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
@@ -97,6 +99,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
             except_clauses: None,
             union_clauses: None,
             joins: None,
+            lock_mode: None,
         }
     }
 
@@ -131,8 +134,44 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
+        self
+    }
+
+    /// Adds an optional condition to the WHERE clause.
+    ///
+    /// `None` leaves the WHERE clause unchanged, `Some` combines the condition with an
+    /// existing WHERE condition using AND.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The optional condition to be applied in the WHERE clause.
+    pub fn where_opt(mut self, condition: Option<Condition<'a>>) -> Self {
+        if let Some(condition) = condition {
+            self.where_condition = match self.where_condition {
+                Some(existing) => Some(existing.and(condition)),
+                None => Some(condition),
+            };
+        }
+        self
+    }
+
+    /// Locks the selected rows with `FOR UPDATE`.
+    pub fn for_update(mut self) -> Self {
+        self.lock_mode = Some(LockMode::Wait);
+        self
+    }
+
+    /// Locks the selected rows with `FOR UPDATE NOWAIT`, failing if a row is already locked.
+    pub fn nowait(mut self) -> Self {
+        self.lock_mode = Some(LockMode::NoWait);
+        self
+    }
+
+    /// Locks the selected rows with `FOR UPDATE SKIP LOCKED`, leaving out rows that are already locked.
+    pub fn skip_locked(mut self) -> Self {
+        self.lock_mode = Some(LockMode::SkipLocked);
         self
     }
 
@@ -181,8 +220,8 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the HAVING clause.
-    pub fn having(mut self, condition: Condition<'a>) -> Self {
-        self.having_condition = Some(condition);
+    pub fn having(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.having_condition = Some(condition.into());
         self
     }
 
@@ -263,6 +302,11 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
 
     /// Builds the query string, this function should be used internally.
     pub fn build_query(&self) -> String {
+        self.render("")
+    }
+
+    /// Renders the query with the table names prefixed with `table_prefix`.
+    fn render(&self, table_prefix: &str) -> String {
         let columns_str = self
             .columns
             .iter()
@@ -273,7 +317,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(table_prefix))
             .unwrap_or("".to_string());
 
         // Generate JOIN clauses, if any
@@ -287,12 +331,21 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
                         JoinType::Right => "RIGHT JOIN",
                         JoinType::Full => "FULL OUTER JOIN",
                     };
+                    let constraint_str = match &join.constraint {
+                        JoinConstraint::On(condition) => format!(
+                            "ON {}",
+                            generate_where_condition_str(Some(condition.clone())).replace("WHERE", "")
+                        ),
+                        JoinConstraint::Using(columns) => format!("USING ({})", columns.join(", ")),
+                    };
                     format!(
-                        "{} {} ON {}",
+                        "{} {} {}",
                         join_type_str,
-                        join.table.get_name(),
-                        generate_where_condition_str(Some(join.on_condition.clone()))
-                            .replace("WHERE", "")
+                        table_reference(
+                            &join.table.get_prefixed_name(table_prefix),
+                            join.alias.as_deref()
+                        ),
+                        constraint_str
                     )
                 })
                 .collect(),
@@ -316,7 +369,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         };
 
         let mut query = format!(
-            "SELECT {}{} FROM {} {} {} {} {} {} {}",
+            "SELECT {}{} FROM {} {} {} {} {} {} {} {}",
             distinct_str,
             columns_str,
             table_name,
@@ -325,13 +378,18 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
             group_by_str,
             having_str,
             order_by_str,
-            format!("{} {}", limit_str, offset_str),
+            limit_str,
+            offset_str,
         );
 
+        if let Some(lock_mode) = &self.lock_mode {
+            query = format!("{} {}", query, lock_mode.to_sql());
+        }
+
         // Handle EXCEPT clauses
         if let Some(except_clauses) = &self.except_clauses {
             for except_query in except_clauses {
-                let except_sql = except_query.build_query();
+                let except_sql = except_query.render(table_prefix);
                 query = format!("{} EXCEPT {}", query, except_sql);
             }
         }
@@ -339,7 +397,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         // Handle UNION clauses
         if let Some(union_clauses) = &self.union_clauses {
             for union_query in union_clauses {
-                let union_sql = union_query.build_query();
+                let union_sql = union_query.render(table_prefix);
                 query = format!("{} UNION {}", query, union_sql);
             }
         }
@@ -349,6 +407,8 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
 
     /// Builds and executes the SELECT query.
     ///
+    /// The table names are prefixed with the table prefix of the connection.
+    ///
     /// # Arguments
     ///
     /// * `conn` - A mutable reference to the database connection.
@@ -357,12 +417,12 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     ///
     /// A `Result` containing a vector of selected table rows if successful,
     /// or a `rusqlite::Error` if an error occurs during the execution.
-    pub fn build(&mut self, conn: &mut PooledConn) -> Result<Vec<T>, Error> {
-        let final_query = self.build_query();
+    pub fn build(&mut self, conn: &mut dyn Session<PooledConn>) -> Result<Vec<T>, Error> {
+        let final_query = self.render(&conn.config().table_prefix);
 
         info!("{}", final_query);
 
-        raw_execute(&final_query, conn)
+        raw_execute(&final_query, conn.connection_mut())
     }
 }
 
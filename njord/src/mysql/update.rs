@@ -33,13 +33,14 @@ use std::collections::HashMap;
 
 use crate::{
     condition::Condition,
+    config::Session,
     mysql::util::{
         generate_limit_str, generate_offset_str, generate_order_by_str,
         generate_where_condition_str, remove_quotes_and_backslashes,
     },
 };
 
-use log::{debug, info};
+use log::debug;
 use mysql::{prelude::Queryable, PooledConn};
 
 use crate::table::Table;
@@ -113,8 +114,8 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
         self
     }
 
@@ -150,18 +151,21 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
 
     /// Builds and executes the UPDATE query.
     ///
+    /// The table name is prefixed with the table prefix of the connection.
+    ///
     /// # Arguments
     ///
     /// * `conn` - A mutable reference to the MySQL connection.
     /// 
     /// # Returns
     ///
-    /// A `Result` indicating success or failure of the update operation.
-    pub fn build(self, conn: &mut PooledConn) -> Result<(), String> {
+    /// A `Result` containing the number of updated rows if successful,
+    /// or an `Error` if an error occurs.
+    pub fn build(self, conn: &mut dyn Session<PooledConn>) -> Result<usize, crate::Error> {
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(&conn.config().table_prefix))
             .unwrap_or("".to_string());
 
         // Sanitize table name from unwanted quotations or backslashes
@@ -209,18 +213,19 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
 
         // Construct the query based on defined variables above
         let query = format!(
-            "UPDATE {} SET {} {} {} {}",
+            "UPDATE {} SET {} {} {} {} {}",
             table_name_str,
             set,
             where_condition_str,
             order_by_str,
-            format!("{} {}", limit_str, offset_str),
+            limit_str,
+            offset_str,
         );
 
         debug!("{}", query);
 
-        let _ = conn.query_drop(query.as_str());
+        conn.connection_mut().query_drop(query.as_str())?;
 
-        Ok(())
+        Ok(conn.connection_mut().affected_rows() as usize)
     }
 }
@@ -32,6 +32,7 @@
 use std::collections::HashMap;
 
 use crate::condition::Condition;
+use crate::dialect::Dialect;
 
 /// Generates an SQL WHERE clause string based on the provided condition.
 ///
@@ -47,7 +48,7 @@ use crate::condition::Condition;
 /// A String representing the generated SQL WHERE clause.
 pub fn generate_where_condition_str(condition: Option<Condition>) -> String {
     if let Some(condition) = condition {
-        format!("WHERE {}", condition.build())
+        format!("WHERE {}", condition.build_with(Dialect::MySql))
     } else {
         String::new()
     }
@@ -152,10 +153,9 @@ pub fn generate_offset_str(offset: Option<usize>) -> String {
 ///
 /// A String representing the generated SQL HAVING clause.
 pub fn generate_having_str(group_by: bool, having_condition: Option<&Condition>) -> String {
-    if group_by && having_condition.is_some() {
-        format!("HAVING {}", having_condition.unwrap().build())
-    } else {
-        String::new()
+    match having_condition {
+        Some(condition) if group_by => format!("HAVING {}", condition.build_with(Dialect::MySql)),
+        _ => String::new(),
     }
 }
 
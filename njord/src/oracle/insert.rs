@@ -29,7 +29,7 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{query::QueryBuilder, table::Table};
+use crate::{config::Session, query::QueryBuilder, table::Table};
 
 use oracle::Connection;
 use rusqlite::Error as RusqliteError;
@@ -48,19 +48,19 @@ use std::fmt::Error;
 ///
 /// * `conn` - A `Connection` to the Oracle database.
 /// * `table_rows` - A vector of objects implementing the `Table` trait representing
-///                  the rows to be inserted into the database.
+///   the rows to be inserted into the database.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the joined SQL statements
 /// if the insertion is successful, or a `RusqliteError` if an error occurs.
 pub fn insert<T: Table>(
-    conn: &mut Connection,
+    conn: &mut dyn Session<Connection>,
     table_rows: Vec<T>,
 ) -> Result<String, RusqliteError> {
     let mut statements: Vec<String> = Vec::new();
     for (index, table_row) in table_rows.iter().enumerate() {
-        match generate_statement(table_row, index == 0) {
+        match generate_statement(table_row, index == 0, &conn.config().table_prefix) {
             Ok(statement) => statements.push(statement),
             Err(_) => return Err(RusqliteError::InvalidQuery),
         }
@@ -70,12 +70,12 @@ pub fn insert<T: Table>(
 
     debug!("{}", joined_statements);
 
-    let _ = match conn.execute(&joined_statements, &[]) {
+    match conn.connection_mut().execute(&joined_statements, &[]) {
         Ok(_) => info!("Inserted into table, done."),
         Err(err) => eprintln!("Error: {}", err),
     };
 
-    let _ = conn.commit();
+    let _ = conn.connection_mut().commit();
 
     info!("Inserted into table, done.");
 
@@ -94,15 +94,16 @@ pub fn insert<T: Table>(
 /// A `Result` containing a `String` representing the generated SQL statement
 /// if the operation is successful, or a `RusqliteError` if an error occurs.
 pub fn into<'a, T: Table + Default>(
-    conn: &'a mut Connection,
+    conn: &'a mut dyn Session<Connection>,
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
 ) -> Result<String, RusqliteError> {
-    let statement = generate_insert_into_statement::<T>(columns, subquery);
+    let statement =
+        generate_insert_into_statement::<T>(columns, subquery, &conn.config().table_prefix);
     let sql = statement.unwrap();
 
     // FIXME: Convert to transaction
-    let _ = conn.execute(&sql, &[]);
+    let _ = conn.connection_mut().execute(&sql, &[]);
 
     info!("Inserted into table, done.");
 
@@ -116,6 +117,7 @@ pub fn into<'a, T: Table + Default>(
 ///
 /// * `columns` - A `Vec` of column names.
 /// * `subquery` - A `QueryBuilder` object representing the subquery.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
@@ -124,11 +126,15 @@ pub fn into<'a, T: Table + Default>(
 fn generate_insert_into_statement<'a, T: Table + Default>(
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
+    table_prefix: &str,
 ) -> Result<String, RusqliteError> {
     let columns_str = columns.join(", ");
     let subquery_str = subquery.to_sql();
     let table_row = T::default();
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+    let table_name = table_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
 
     let sql = format!(
         "INSERT INTO {} ({}) {}",
@@ -147,15 +153,20 @@ fn generate_insert_into_statement<'a, T: Table + Default>(
 /// # Arguments
 ///
 /// * `table_row` - An object implementing the `Table` trait representing
-///                 a single row of data to be inserted.
+///   a single row of data to be inserted.
 /// * `first_statement` - A boolean flag indicating whether this is the first
-///                       statement to be generated.
+///   statement to be generated.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the generated SQL statement
 /// if successful, or a `Error` if an error occurs during the generation process.
-fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<String, Error> {
+fn generate_statement<T: Table>(
+    table_row: &T,
+    first_statement: bool,
+    table_prefix: &str,
+) -> Result<String, Error> {
     // Generate strings for columns and values
     let mut columns_str = String::new();
     let mut values_str = String::new();
@@ -179,7 +190,10 @@ fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<
     }
 
     // Sanitize table name from unwanted quotations or backslashes
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+    let table_name = table_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
 
     // Remove the trailing comma and space
     if !columns_str.is_empty() {
@@ -29,17 +29,25 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use oracle::{Connection, Error};
+use std::time::Duration;
 
+use oracle::{sql_type::ToSql, Connection, Error, Row};
+
+use crate::condition::Value;
+
+pub mod count;
 pub mod delete;
 pub mod error;
+pub mod exists;
 pub mod insert;
 pub mod select;
 pub mod update;
 mod util;
 
+pub use count::count;
 pub use delete::delete;
 pub use error::OracleError;
+pub use exists::exists;
 pub use insert::insert;
 pub use select::select;
 pub use update::update;
@@ -74,6 +82,35 @@ pub fn open(username: &str, password: &str, connect_string: &str) -> Result<Conn
     }
 }
 
+/// Open a database connection with a call timeout.
+///
+/// Every round trip to the database, like executing a query or fetching rows, that takes
+/// longer than `call_timeout` is cancelled and fails with `Error::Timeout`, so a blocked
+/// query cannot hang the caller indefinitely. The timeout can be overridden for a single
+/// query with `call_timeout` on the select builder.
+///
+/// # Arguments
+///
+/// * `username` - A reference to the username for the Oracle database.
+/// * `password` - A reference to the password for the Oracle database.
+/// * `connect_string` - A reference to the connect string for the Oracle database.
+/// * `call_timeout` - The maximum duration of a round trip to the database.
+///
+/// # Returns
+///
+/// Returns a `Result` containing a `Connection` if the operation was successful, or an `Error` if an error occurred.
+pub fn open_with_call_timeout(
+    username: &str,
+    password: &str,
+    connect_string: &str,
+    call_timeout: Duration,
+) -> Result<Connection, crate::Error> {
+    let conn = open(username, password, connect_string)?;
+    conn.set_call_timeout(Some(call_timeout))?;
+
+    Ok(conn)
+}
+
 /// Executes a raw SQL query and returns a vector of table rows.
 ///
 /// # Arguments
@@ -91,3 +128,71 @@ pub fn raw_execute(conn: &Connection, sql: &str) -> Result<(), OracleError> {
         Err(e) => Err(e.into()),
     }
 }
+
+/// Executes a raw SQL statement with positional parameters and returns the number of affected rows.
+///
+/// Parameters are bound to the `:1`, `:2`, ... placeholders in order.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `sql` - The SQL statement to execute.
+/// * `params` - The values to bind to the placeholders.
+///
+/// # Returns
+///
+/// A `Result` containing the number of affected rows if successful,
+/// or an `Error` if an error occurs during the execution.
+pub fn execute(conn: &Connection, sql: &str, params: Vec<Value>) -> Result<usize, crate::Error> {
+    let params = params
+        .iter()
+        .map(|value| value.to_param())
+        .collect::<Result<Vec<Option<String>>, crate::Error>>()?;
+    let params: Vec<&dyn ToSql> = params.iter().map(|param| param as &dyn ToSql).collect();
+
+    let stmt = conn.execute(sql, &params)?;
+
+    Ok(stmt.row_count()? as usize)
+}
+
+/// Calls a stored procedure and returns the result sets it returns with
+/// `DBMS_SQL.RETURN_RESULT`, one per cursor.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `name` - The name of the procedure, e.g. `get_orders`.
+/// * `params` - The values of the arguments of the procedure.
+///
+/// # Returns
+///
+/// A `Result` containing the rows of every returned cursor in the order they were
+/// returned, or an `Error` if an error occurs during the call.
+pub fn call_proc(
+    conn: &Connection,
+    name: &str,
+    params: Vec<Value>,
+) -> Result<Vec<Vec<Row>>, crate::Error> {
+    let params = params
+        .iter()
+        .map(|value| value.to_param())
+        .collect::<Result<Vec<Option<String>>, crate::Error>>()?;
+    let params: Vec<&dyn ToSql> = params.iter().map(|param| param as &dyn ToSql).collect();
+
+    let placeholders = (1..=params.len())
+        .map(|index| format!(":{}", index))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let sql = format!("BEGIN {}({}); END;", name, placeholders);
+
+    let mut stmt = conn.statement(&sql).build()?;
+    stmt.execute(&params)?;
+
+    let mut result_sets = Vec::new();
+    while let Some(mut cursor) = stmt.implicit_result()? {
+        let rows = cursor.query()?.collect::<Result<Vec<Row>, Error>>()?;
+        result_sets.push(rows);
+    }
+
+    Ok(result_sets)
+}
@@ -32,19 +32,20 @@
 use crate::{
     column::Column,
     condition::Condition,
+    config::Session,
     oracle::util::{
         generate_group_by_str, generate_having_str, generate_order_by_str,
         generate_where_condition_str,
     },
     query::QueryBuilder,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use log::{debug, info};
 use oracle::{Connection, Error};
 
 use crate::table::Table;
-use crate::util::{Join, JoinType};
+use crate::util::{match_column_field, ungrouped_column, Join, JoinConstraint, JoinType};
 
 /// Constructs a new SELECT query builder.
 ///
@@ -72,6 +73,8 @@ pub struct SelectQueryBuilder<'a, T: Table + Default> {
     except_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     union_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     joins: Option<Vec<Join<'a>>>,
+    ties_limit: Option<usize>,
+    call_timeout: Option<Duration>,
 }
 
 impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
@@ -92,6 +95,8 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
             except_clauses: None,
             union_clauses: None,
             joins: None,
+            ties_limit: None,
+            call_timeout: None,
         }
     }
 
@@ -126,8 +131,26 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
+        self
+    }
+
+    /// Adds an optional condition to the WHERE clause.
+    ///
+    /// `None` leaves the WHERE clause unchanged, `Some` combines the condition with an
+    /// existing WHERE condition using AND.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The optional condition to be applied in the WHERE clause.
+    pub fn where_opt(mut self, condition: Option<Condition<'a>>) -> Self {
+        if let Some(condition) = condition {
+            self.where_condition = match self.where_condition {
+                Some(existing) => Some(existing.and(condition)),
+                None => Some(condition),
+            };
+        }
         self
     }
 
@@ -156,8 +179,8 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the HAVING clause.
-    pub fn having(mut self, condition: Condition<'a>) -> Self {
-        self.having_condition = Some(condition);
+    pub fn having(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.having_condition = Some(condition.into());
         self
     }
 
@@ -236,8 +259,39 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         self
     }
 
+    /// Limits the result to the first `count` rows, including the rows that tie with
+    /// the last row according to the ORDER BY clause.
+    ///
+    /// Requires an `order_by`, since which rows tie depends on the order.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of rows to return, not counting ties.
+    pub fn limit_with_ties(mut self, count: usize) -> Self {
+        self.ties_limit = Some(count);
+        self
+    }
+
+    /// Overrides the call timeout of the connection for this query, failing with
+    /// `Error::Timeout` if a round trip to the database takes longer.
+    ///
+    /// The call timeout of the connection is restored after the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum duration of a round trip to the database.
+    pub fn call_timeout(mut self, timeout: Duration) -> Self {
+        self.call_timeout = Some(timeout);
+        self
+    }
+
     /// Builds the query string, this function should be used internally.
     pub fn build_query(&self) -> String {
+        self.render("")
+    }
+
+    /// Renders the query with the table names prefixed with `table_prefix`.
+    fn render(&self, table_prefix: &str) -> String {
         let columns_str = self
             .columns
             .iter()
@@ -248,7 +302,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(table_prefix))
             .unwrap_or("".to_string());
 
         // Generate JOIN clauses, if any
@@ -262,13 +316,21 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
                         JoinType::Right => "RIGHT JOIN",
                         JoinType::Full => "FULL OUTER JOIN",
                     };
-                    format!(
-                        "{} {} ON {}",
-                        join_type_str,
-                        join.table.get_name(),
-                        generate_where_condition_str(Some(join.on_condition.clone()))
-                            .replace("WHERE", "")
-                    )
+                    let constraint_str = match &join.constraint {
+                        JoinConstraint::On(condition) => format!(
+                            "ON {}",
+                            generate_where_condition_str(Some(condition.clone())).replace("WHERE", "")
+                        ),
+                        JoinConstraint::Using(columns) => format!("USING ({})", columns.join(", ")),
+                    };
+                    // Oracle doesn't accept AS before a table alias
+                    let table_str = match &join.alias {
+                        Some(alias) => {
+                            format!("{} {}", join.table.get_prefixed_name(table_prefix), alias)
+                        }
+                        None => join.table.get_prefixed_name(table_prefix),
+                    };
+                    format!("{} {} {}", join_type_str, table_str, constraint_str)
                 })
                 .collect(),
             None => Vec::new(),
@@ -288,8 +350,13 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
             String::new()
         };
 
+        let fetch_str = match self.ties_limit {
+            Some(count) => format!("FETCH FIRST {} ROWS WITH TIES", count),
+            None => String::new(),
+        };
+
         let mut query = format!(
-            "SELECT {}{} FROM {} {} {} {} {} {}",
+            "SELECT {}{} FROM {} {} {} {} {} {} {}",
             distinct_str,
             columns_str,
             table_name,
@@ -298,12 +365,13 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
             group_by_str,
             having_str,
             order_by_str,
+            fetch_str,
         );
 
         // Handle EXCEPT clauses
         if let Some(except_clauses) = &self.except_clauses {
             for except_query in except_clauses {
-                let except_sql = except_query.build_query();
+                let except_sql = except_query.render(table_prefix);
                 query = format!("{} EXCEPT {}", query, except_sql);
             }
         }
@@ -311,7 +379,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         // Handle UNION clauses
         if let Some(union_clauses) = &self.union_clauses {
             for union_query in union_clauses {
-                let union_sql = union_query.build_query();
+                let union_sql = union_query.render(table_prefix);
                 query = format!("{} UNION {}", query, union_sql);
             }
         }
@@ -321,6 +389,8 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
 
     /// Builds and executes the SELECT query.
     ///
+    /// The table names are prefixed with the table prefix of the connection.
+    ///
     /// # Arguments
     ///
     /// * `conn` - A mutable reference to the database connection.
@@ -328,13 +398,35 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Returns
     ///
     /// A `Result` containing a vector of selected table rows if successful,
-    /// or a `rusqlite::Error` if an error occurs during the execution.
-    pub fn build(self, conn: &Connection) -> Result<Vec<T>, Error> {
-        let final_query = self.build_query();
+    /// or an `Error` if the query is invalid or an error occurs during the execution.
+    pub fn build(self, conn: &dyn Session<Connection>) -> Result<Vec<T>, crate::error::Error> {
+        if self.ties_limit.is_some() && self.order_by.is_none() {
+            return Err(crate::error::Error::InvalidQuery(
+                "`limit_with_ties` requires an `order_by`".to_string(),
+            ));
+        }
+
+        if let Some(group_by) = &self.group_by {
+            if let Some(column) = ungrouped_column(&self.columns, group_by) {
+                return Err(crate::error::Error::InvalidGroupBy(column));
+            }
+        }
+
+        let final_query = self.render(&conn.config().table_prefix);
 
         debug!("{}", final_query);
 
-        raw_execute(&final_query, conn)
+        let conn = conn.connection();
+        let Some(timeout) = self.call_timeout else {
+            return Ok(raw_execute(&final_query, conn)?);
+        };
+
+        let previous_timeout = conn.call_timeout()?;
+        conn.set_call_timeout(Some(timeout))?;
+        let result = raw_execute(&final_query, conn);
+        conn.set_call_timeout(previous_timeout)?;
+
+        Ok(result?)
     }
 }
 
@@ -366,15 +458,19 @@ pub fn raw_execute<T: Table + Default>(sql: &str, conn: &Connection) -> Result<V
 
     info!("{}", final_query);
 
-    let mut stmt = conn.statement(&final_query).build()?;
+    let mut stmt = conn.statement(final_query).build()?;
     let rows = stmt.query(&[])?;
 
     let mut results: Vec<T> = Vec::new();
 
+    // Oracle returns unquoted column names in uppercase, they are matched to the fields
+    // case insensitively
+    let fields = T::default().get_column_fields();
     let mut columns: Vec<String> = Vec::new();
 
     for info in rows.column_info() {
-        columns.push(info.name().to_string().to_lowercase());
+        let column = match_column_field(&fields, info.name()).unwrap_or(info.name());
+        columns.push(column.to_string());
     }
 
     for row_result in rows {
@@ -382,13 +478,9 @@ pub fn raw_execute<T: Table + Default>(sql: &str, conn: &Connection) -> Result<V
 
         // print column values
         for (idx, val) in row_result?.sql_values().iter().enumerate() {
-            let column_value_str = match val.oracle_type().unwrap() {
-                _ => format!("{}", val),
-            };
-
-            let col = columns[idx].clone();
+            let column_value_str = format!("{}", val);
 
-            instance.set_column_value(col.to_lowercase().as_str(), &column_value_str);
+            instance.set_column_value(&columns[idx], &column_value_str);
         }
 
         results.push(instance);
@@ -29,11 +29,34 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use crate::condition::{Condition, Params};
+use crate::dialect::Dialect;
+use crate::error::Error;
+
 /// The `QueryBuilder` trait.
 ///
 /// Primarily used for subqueries within conditions.
 pub trait QueryBuilder<'a>: QueryBuilderClone<'a> {
     fn to_sql(&self) -> String;
+
+    /// Returns the number of selected columns, if known.
+    fn selected_columns(&self) -> Option<usize> {
+        None
+    }
+
+    /// Renders the query with placeholders for its values, adding them to `params`.
+    ///
+    /// Queries that can't bind parameters insert their values, like `to_sql`.
+    fn to_sql_with_params(&self, _params: &mut Params<'a>) -> String {
+        self.to_sql()
+    }
+
+    /// Checks the query for errors before it is rendered as a subquery.
+    ///
+    /// Queries that aren't checked return `Ok`.
+    fn check(&self) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 /// A helper trait to enable cloning of `Box<dyn QueryBuilder>`
@@ -55,3 +78,156 @@ impl<'a> Clone for Box<dyn QueryBuilder<'a> + 'a> {
         self.clone_box()
     }
 }
+
+/// The clauses of a SELECT statement, assembled the same way for every dialect.
+///
+/// Backends render the parts that differ between them, like the quoting of the columns,
+/// before and leave the conditions, the placeholders and the pagination to the dialect.
+pub struct SelectStatement<'q, 'a> {
+    /// Adds `DISTINCT` to the selected columns.
+    pub distinct: bool,
+    /// The selected columns, separated by commas.
+    pub columns: String,
+    /// The table to select from.
+    pub from: String,
+    /// The JOIN clauses, if any.
+    pub joins: String,
+    /// The condition of the WHERE clause, if any.
+    pub where_condition: Option<&'q Condition<'a>>,
+    /// The GROUP BY clause, if any.
+    pub group_by: String,
+    /// The condition of the HAVING clause, only rendered with a GROUP BY clause.
+    pub having_condition: Option<&'q Condition<'a>>,
+    /// The ORDER BY clause of the columns, if any.
+    pub order_by: String,
+    /// The expressions ordered by before the columns, as raw conditions with their
+    /// direction, e.g. `CASE WHEN name = ? THEN 0 ELSE 1 END` and `ASC`.
+    pub order_by_expressions: &'q [(Condition<'a>, String)],
+    /// The maximum number of rows.
+    pub limit: Option<usize>,
+    /// The number of rows to skip.
+    pub offset: Option<usize>,
+}
+
+impl<'q, 'a> SelectStatement<'q, 'a> {
+    /// Renders the statement for a dialect.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect of the conditions, placeholders and pagination.
+    /// * `params` - The bound parameters of the query. If given, the values of the
+    ///   conditions are rendered as placeholders and added to it, otherwise they are
+    ///   inserted into the statement.
+    ///
+    /// # Returns
+    ///
+    /// A `String` containing the SELECT statement.
+    pub fn render(&self, dialect: Dialect, mut params: Option<&mut Params<'a>>) -> String {
+        let mut render_condition = |condition: &Condition<'a>| match params.as_deref_mut() {
+            Some(params) => condition.build_with_params(dialect, params),
+            None => condition.build_with(dialect),
+        };
+
+        let where_str = self
+            .where_condition
+            .map(|condition| format!("WHERE {}", render_condition(condition)))
+            .unwrap_or_default();
+        let having_str = match self.having_condition {
+            Some(condition) if !self.group_by.is_empty() => {
+                format!("HAVING {}", render_condition(condition))
+            }
+            _ => String::new(),
+        };
+        // The values of the expressions are bound after the ones of the conditions
+        let expressions: Vec<String> = self
+            .order_by_expressions
+            .iter()
+            .map(|(expression, direction)| format!("{} {}", render_condition(expression), direction))
+            .collect();
+        let order_by_str = merge_order_by(&self.order_by, &expressions);
+
+        format!(
+            "SELECT {}{} FROM {} {} {} {} {} {} {}",
+            if self.distinct { "DISTINCT " } else { "" },
+            self.columns,
+            self.from,
+            self.joins,
+            where_str,
+            self.group_by,
+            having_str,
+            order_by_str,
+            dialect.pagination(self.limit, self.offset),
+        )
+    }
+}
+
+/// Adds rendered expressions, e.g. `CASE ... END ASC`, before the columns of an ORDER BY
+/// clause.
+pub(crate) fn merge_order_by(order_by: &str, expressions: &[String]) -> String {
+    match order_by.strip_prefix("ORDER BY ") {
+        _ if expressions.is_empty() => order_by.to_string(),
+        Some(columns) => format!("ORDER BY {}, {}", expressions.join(", "), columns),
+        None => format!("ORDER BY {}", expressions.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::condition::Value;
+
+    fn statement<'q, 'a>(condition: &'q Condition<'a>) -> SelectStatement<'q, 'a> {
+        SelectStatement {
+            distinct: false,
+            columns: "id, name".to_string(),
+            from: "users".to_string(),
+            joins: String::new(),
+            where_condition: Some(condition),
+            group_by: String::new(),
+            having_condition: None,
+            order_by: "ORDER BY id ASC".to_string(),
+            order_by_expressions: &[],
+            limit: Some(10),
+            offset: Some(20),
+        }
+    }
+
+    #[test]
+    fn test_render_sqlite() {
+        let condition = Condition::eq("name", Value::Literal("mjovanc".to_string()));
+        let mut params = Params::default();
+
+        let sql = statement(&condition).render(Dialect::Sqlite, Some(&mut params));
+
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users  WHERE \"name\" = ?   ORDER BY id ASC LIMIT 10 OFFSET 20"
+        );
+        assert_eq!(params.values().len(), 1);
+    }
+
+    #[test]
+    fn test_render_oracle() {
+        let condition = Condition::eq("name", Value::Literal("mjovanc".to_string()));
+        let mut params = Params::default();
+
+        let sql = statement(&condition).render(Dialect::Oracle, Some(&mut params));
+
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM users  WHERE \"name\" = :1   ORDER BY id ASC \
+             OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY"
+        );
+        assert_eq!(params.values().len(), 1);
+    }
+
+    #[test]
+    fn test_render_inlined_values() {
+        let condition = Condition::eq("name", Value::Literal("mjovanc".to_string()));
+
+        let sql = statement(&condition).render(Dialect::Postgres, None);
+
+        assert!(sql.contains("WHERE \"name\" = 'mjovanc'"));
+        assert!(sql.ends_with("LIMIT 10 OFFSET 20"));
+    }
+}
@@ -0,0 +1,221 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::error::Error;
+
+/// The default maximum number of reconnects for a single operation.
+const DEFAULT_MAX_RECONNECTS: u32 = 3;
+
+/// A connection that is re-established when it is lost.
+///
+/// The connection is opened with the `connect` function, which holds the original
+/// configuration, e.g. the connection URL. When an operation fails with an
+/// `Error::ConnectionFailed`, the connection is dropped, opened again and the operation
+/// is retried, up to `max_reconnects` times. Operations are retried as a whole, so they
+/// should be idempotent or run in a transaction.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut conn = ReconnectingConnection::new(|| mysql::open(url))?.max_reconnects(5);
+/// let users = conn.run(|conn| mysql::select(columns.clone()).from(User::default()).build(conn))?;
+/// ```
+pub struct ReconnectingConnection<C, F>
+where
+    F: FnMut() -> Result<C, Error>,
+{
+    connect: F,
+    conn: Option<C>,
+    max_reconnects: u32,
+    reconnects: u32,
+}
+
+impl<C, F> ReconnectingConnection<C, F>
+where
+    F: FnMut() -> Result<C, Error>,
+{
+    /// Opens the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `connect` - The function opening a new connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ReconnectingConnection`, or the `Error` of `connect`.
+    pub fn new(mut connect: F) -> Result<Self, Error> {
+        let conn = connect()?;
+
+        Ok(ReconnectingConnection {
+            connect,
+            conn: Some(conn),
+            max_reconnects: DEFAULT_MAX_RECONNECTS,
+            reconnects: 0,
+        })
+    }
+
+    /// Sets the maximum number of reconnects for a single operation, 3 by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_reconnects` - The maximum number of reconnects, `0` disables reconnecting.
+    pub fn max_reconnects(mut self, max_reconnects: u32) -> Self {
+        self.max_reconnects = max_reconnects;
+        self
+    }
+
+    /// Returns the number of times the connection was re-established.
+    pub fn reconnects(&self) -> u32 {
+        self.reconnects
+    }
+
+    /// Runs an operation on the connection, reconnecting if the connection was lost.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - The operation to run.
+    ///
+    /// # Returns
+    ///
+    /// The result of the operation, or the last `Error` if the connection could not be
+    /// re-established within `max_reconnects` attempts.
+    pub fn run<T, O>(&mut self, mut op: O) -> Result<T, Error>
+    where
+        O: FnMut(&mut C) -> Result<T, Error>,
+    {
+        let mut attempts = 0;
+
+        loop {
+            let result = match self.conn.as_mut() {
+                Some(conn) => op(conn),
+                None => match (self.connect)() {
+                    Ok(conn) => {
+                        self.reconnects += 1;
+                        op(self.conn.insert(conn))
+                    }
+                    Err(e) => Err(e),
+                },
+            };
+
+            match result {
+                Err(Error::ConnectionFailed(_)) if attempts < self.max_reconnects => {
+                    self.conn = None;
+                    attempts += 1;
+                }
+                Err(Error::ConnectionFailed(e)) => {
+                    self.conn = None;
+                    return Err(Error::ConnectionFailed(e));
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// A fake connection whose socket can be closed by the test.
+    struct FakeConnection {
+        open: Rc<Cell<bool>>,
+    }
+
+    impl FakeConnection {
+        fn query(&self) -> Result<i32, Error> {
+            if self.open.get() {
+                Ok(1)
+            } else {
+                Err(Error::ConnectionFailed("connection reset by peer".into()))
+            }
+        }
+    }
+
+    #[test]
+    fn reconnects_after_connection_loss() {
+        let socket = Rc::new(Cell::new(true));
+        let connects = Rc::new(Cell::new(0));
+
+        let mut conn = ReconnectingConnection::new(|| {
+            connects.set(connects.get() + 1);
+            socket.set(true);
+            Ok(FakeConnection {
+                open: Rc::clone(&socket),
+            })
+        })
+        .unwrap();
+
+        assert_eq!(conn.run(|conn| conn.query()).unwrap(), 1);
+
+        socket.set(false);
+
+        assert_eq!(conn.run(|conn| conn.query()).unwrap(), 1);
+        assert_eq!(conn.reconnects(), 1);
+        assert_eq!(connects.get(), 2);
+    }
+
+    #[test]
+    fn gives_up_after_max_reconnects() {
+        let attempts = Cell::new(0);
+
+        let mut conn = ReconnectingConnection::new(|| {
+            Ok(FakeConnection {
+                open: Rc::new(Cell::new(false)),
+            })
+        })
+        .unwrap()
+        .max_reconnects(2);
+
+        let result = conn.run(|conn| {
+            attempts.set(attempts.get() + 1);
+            conn.query()
+        });
+
+        assert!(matches!(result, Err(Error::ConnectionFailed(_))));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn does_not_reconnect_on_query_errors() {
+        let mut conn = ReconnectingConnection::new(|| {
+            Ok(FakeConnection {
+                open: Rc::new(Cell::new(true)),
+            })
+        })
+        .unwrap();
+
+        let result: Result<(), Error> =
+            conn.run(|_| Err(Error::InvalidQuery("syntax error".to_string())));
+
+        assert!(matches!(result, Err(Error::InvalidQuery(_))));
+        assert_eq!(conn.reconnects(), 0);
+    }
+}
@@ -0,0 +1,199 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::thread;
+use std::time::Duration;
+
+/// The `Transient` trait.
+///
+/// Implemented by error types that can tell whether an operation failed because of a
+/// temporary condition (e.g. a busy database or a deadlock) and is worth retrying.
+pub trait Transient {
+    fn is_transient(&self) -> bool;
+}
+
+/// Retries an operation on transient errors with exponential backoff.
+///
+/// The operation is retried as long as it fails with an error where `is_transient()`
+/// returns `true`, waiting `backoff` before the second attempt and doubling the wait
+/// for every attempt after that.
+///
+/// # Arguments
+///
+/// * `max_attempts` - The maximum number of attempts, including the first one.
+/// * `backoff` - The time to wait before the first retry.
+/// * `op` - The operation to run.
+///
+/// # Returns
+///
+/// The result of the first successful attempt, or the last error if the error is not
+/// transient or all attempts failed.
+pub fn with_retry<T, E, F>(max_attempts: u32, backoff: Duration, op: F) -> Result<T, E>
+where
+    E: Transient,
+    F: FnMut() -> Result<T, E>,
+{
+    with_retry_on(max_attempts, backoff, |e: &E| e.is_transient(), op)
+}
+
+/// Retries an operation with exponential backoff on a custom set of errors.
+///
+/// Works like `with_retry`, but uses `is_transient` to decide whether an error should
+/// be retried, e.g. to match a specific set of error codes.
+///
+/// # Arguments
+///
+/// * `max_attempts` - The maximum number of attempts, including the first one.
+/// * `backoff` - The time to wait before the first retry.
+/// * `is_transient` - Returns `true` if the error should be retried.
+/// * `op` - The operation to run.
+///
+/// # Returns
+///
+/// The result of the first successful attempt, or the last error if the error is not
+/// transient or all attempts failed.
+pub fn with_retry_on<T, E, P, F>(
+    max_attempts: u32,
+    backoff: Duration,
+    is_transient: P,
+    mut op: F,
+) -> Result<T, E>
+where
+    P: Fn(&E) -> bool,
+    F: FnMut() -> Result<T, E>,
+{
+    let mut attempt = 1;
+    let mut delay = backoff;
+
+    loop {
+        match op() {
+            Ok(result) => return Ok(result),
+            Err(e) if attempt < max_attempts && is_transient(&e) => {
+                thread::sleep(delay);
+                delay = delay.saturating_mul(2);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Transient for rusqlite::Error {
+    fn is_transient(&self) -> bool {
+        matches!(
+            self.sqlite_error_code(),
+            Some(rusqlite::ErrorCode::DatabaseBusy) | Some(rusqlite::ErrorCode::DatabaseLocked)
+        )
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Transient for crate::sqlite::SqliteError {
+    fn is_transient(&self) -> bool {
+        match self {
+            crate::sqlite::SqliteError::SelectError(e)
+            | crate::sqlite::SqliteError::InsertError(e)
+            | crate::sqlite::SqliteError::UpdateError(e)
+            | crate::sqlite::SqliteError::DeleteError(e) => e.is_transient(),
+        }
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "mariadb"))]
+impl Transient for mysql::Error {
+    fn is_transient(&self) -> bool {
+        // 1205: lock wait timeout exceeded, 1213: deadlock found
+        matches!(self, mysql::Error::MySqlError(e) if e.code == 1205 || e.code == 1213)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[cfg(feature = "sqlite")]
+    fn busy_error() -> rusqlite::Error {
+        rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+            Some("database is locked".to_string()),
+        )
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn retries_busy_error() {
+        let attempts = Cell::new(0);
+
+        let result = with_retry(3, Duration::from_millis(1), || {
+            attempts.set(attempts.get() + 1);
+            if attempts.get() == 1 {
+                Err(busy_error())
+            } else {
+                Ok(42)
+            }
+        });
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.get(), 2);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let attempts = Cell::new(0);
+
+        let result: Result<(), rusqlite::Error> = with_retry(3, Duration::from_millis(1), || {
+            attempts.set(attempts.get() + 1);
+            Err(busy_error())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[test]
+    fn does_not_retry_other_errors() {
+        let attempts = Cell::new(0);
+
+        let result: Result<(), &str> = with_retry_on(
+            3,
+            Duration::from_millis(1),
+            |e| *e == "busy",
+            || {
+                attempts.set(attempts.get() + 1);
+                Err("syntax error")
+            },
+        );
+
+        assert_eq!(result, Err("syntax error"));
+        assert_eq!(attempts.get(), 1);
+    }
+}
@@ -0,0 +1,333 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{dialect::Dialect, error::Error, schema::ColumnDef};
+
+use super::{init::execute_atomically, schema::table_columns, Executor};
+
+use log::debug;
+
+/// Constructs a new ALTER TABLE builder.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `table_name` - The name of the table to alter.
+///
+/// # Returns
+///
+/// An `AlterTableBuilder` instance.
+pub fn alter_table<'a>(conn: &'a dyn Executor, table_name: &str) -> AlterTableBuilder<'a> {
+    AlterTableBuilder::new(conn, table_name)
+}
+
+/// A change of the columns of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlterOperation {
+    /// Adds a column.
+    AddColumn(ColumnDef),
+    /// Drops the column with the given name.
+    DropColumn(String),
+    /// Renames a column from the first to the second name.
+    RenameColumn(String, String),
+    /// Changes the type, nullability, default or primary key of the column with the
+    /// same name to the given definition.
+    ChangeColumn(ColumnDef),
+}
+
+/// A builder for changing the columns of a table.
+///
+/// The changes are applied in order with `ALTER TABLE` statements. SQLite can't add a
+/// primary key column, drop a column of the primary key or change a column, so then the
+/// table is rebuilt
+/// instead: a new table is created with the changed columns, the rows are copied and the
+/// old table is replaced. A rebuilt table keeps its columns, but loses its indexes,
+/// foreign keys and check constraints.
+pub struct AlterTableBuilder<'a> {
+    conn: &'a dyn Executor,
+    table_name: String,
+    operations: Vec<AlterOperation>,
+}
+
+impl<'a> AlterTableBuilder<'a> {
+    /// Creates a new `AlterTableBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    /// * `table_name` - The name of the table to alter.
+    pub fn new(conn: &'a dyn Executor, table_name: &str) -> Self {
+        AlterTableBuilder {
+            conn,
+            table_name: table_name.to_string(),
+            operations: Vec::new(),
+        }
+    }
+
+    /// Adds a column to the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The definition of the new column.
+    pub fn add_column(mut self, column: ColumnDef) -> Self {
+        self.operations.push(AlterOperation::AddColumn(column));
+        self
+    }
+
+    /// Drops a column of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The name of the column.
+    pub fn drop_column(mut self, column: &str) -> Self {
+        self.operations
+            .push(AlterOperation::DropColumn(column.to_string()));
+        self
+    }
+
+    /// Renames a column of the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The current name of the column.
+    /// * `to` - The new name of the column.
+    pub fn rename_column(mut self, from: &str, to: &str) -> Self {
+        self.operations.push(AlterOperation::RenameColumn(
+            from.to_string(),
+            to.to_string(),
+        ));
+        self
+    }
+
+    /// Changes the definition of a column of the table, which rebuilds the table.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The new definition of the column with the same name.
+    pub fn change_column(mut self, column: ColumnDef) -> Self {
+        self.operations.push(AlterOperation::ChangeColumn(column));
+        self
+    }
+
+    /// Returns the changes made to the table, in order.
+    pub fn operations(&self) -> &[AlterOperation] {
+        &self.operations
+    }
+
+    /// Builds the statements applying the changes to the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the statements, or an `Error` if the columns of the table
+    /// could not be read for a rebuild.
+    pub fn build_statements(&self) -> Result<Vec<String>, Error> {
+        if self.needs_rebuild()? {
+            return self.rebuild_statements();
+        }
+
+        let table_name = Dialect::Sqlite.quote_identifier(&self.table_name);
+        let statements = self
+            .operations
+            .iter()
+            .map(|operation| match operation {
+                AlterOperation::AddColumn(column) => format!(
+                    "ALTER TABLE {} ADD COLUMN {};",
+                    table_name,
+                    column_definition(column)
+                ),
+                AlterOperation::DropColumn(column) => format!(
+                    "ALTER TABLE {} DROP COLUMN {};",
+                    table_name,
+                    Dialect::Sqlite.quote_identifier(column)
+                ),
+                AlterOperation::RenameColumn(from, to) => format!(
+                    "ALTER TABLE {} RENAME COLUMN {} TO {};",
+                    table_name,
+                    Dialect::Sqlite.quote_identifier(from),
+                    Dialect::Sqlite.quote_identifier(to)
+                ),
+                AlterOperation::ChangeColumn(_) => {
+                    unreachable!("changing a column rebuilds the table")
+                }
+            })
+            .collect();
+
+        Ok(statements)
+    }
+
+    /// Builds and executes the statements in a single transaction.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` if the table was altered, or an `Error` if a change
+    /// failed, in which case none of the changes are applied.
+    pub fn build(self) -> Result<(), Error> {
+        let statements = self.build_statements()?;
+        debug!("{}", statements.join("\n"));
+
+        execute_atomically(self.conn, &statements)
+    }
+
+    /// Checks if a change can't be made with `ALTER TABLE`.
+    fn needs_rebuild(&self) -> Result<bool, Error> {
+        let drops_column = self
+            .operations
+            .iter()
+            .any(|operation| matches!(operation, AlterOperation::DropColumn(_)));
+        let adds_primary_key = self.operations.iter().any(|operation| {
+            matches!(operation, AlterOperation::AddColumn(column) if column.primary_key)
+        });
+        let changes_column = self
+            .operations
+            .iter()
+            .any(|operation| matches!(operation, AlterOperation::ChangeColumn(_)));
+
+        if adds_primary_key || changes_column {
+            return Ok(true);
+        }
+        if !drops_column {
+            return Ok(false);
+        }
+
+        let primary_key: Vec<String> = table_columns(self.conn, &self.table_name)?
+            .into_iter()
+            .filter(|column| column.primary_key)
+            .map(|column| column.name)
+            .collect();
+
+        Ok(self.operations.iter().any(|operation| {
+            matches!(operation, AlterOperation::DropColumn(column) if primary_key.contains(column))
+        }))
+    }
+
+    /// Builds the statements rebuilding the table with the changed columns.
+    fn rebuild_statements(&self) -> Result<Vec<String>, Error> {
+        // The new columns, together with the old column their values are copied from
+        let mut columns: Vec<(ColumnDef, Option<String>)> =
+            table_columns(self.conn, &self.table_name)?
+                .into_iter()
+                .map(|column| {
+                    let name = column.name.clone();
+                    (column, Some(name))
+                })
+                .collect();
+
+        for operation in &self.operations {
+            match operation {
+                AlterOperation::AddColumn(column) => columns.push((column.clone(), None)),
+                AlterOperation::DropColumn(name) => {
+                    columns.retain(|(column, _)| &column.name != name)
+                }
+                AlterOperation::RenameColumn(from, to) => {
+                    for (column, _) in columns.iter_mut().filter(|(c, _)| &c.name == from) {
+                        column.name = to.clone();
+                    }
+                }
+                AlterOperation::ChangeColumn(changed) => {
+                    for (column, _) in columns.iter_mut().filter(|(c, _)| c.name == changed.name) {
+                        *column = changed.clone();
+                    }
+                }
+            }
+        }
+
+        let table_name = Dialect::Sqlite.quote_identifier(&self.table_name);
+        let new_table_name = format!("njord_new_{}", self.table_name);
+        let quoted_new_table_name = Dialect::Sqlite.quote_identifier(&new_table_name);
+
+        let primary_key: Vec<String> = columns
+            .iter()
+            .filter(|(column, _)| column.primary_key)
+            .map(|(column, _)| Dialect::Sqlite.quote_identifier(&column.name))
+            .collect();
+        let mut definitions: Vec<String> = columns
+            .iter()
+            .map(|(column, _)| {
+                column_definition(&ColumnDef {
+                    primary_key: false,
+                    ..column.clone()
+                })
+            })
+            .collect();
+        if !primary_key.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", primary_key.join(", ")));
+        }
+
+        let (targets, sources): (Vec<String>, Vec<String>) = columns
+            .iter()
+            .filter_map(|(column, source)| {
+                source.as_ref().map(|source| {
+                    (
+                        Dialect::Sqlite.quote_identifier(&column.name),
+                        Dialect::Sqlite.quote_identifier(source),
+                    )
+                })
+            })
+            .unzip();
+
+        Ok(vec![
+            format!(
+                "CREATE TABLE {} ({});",
+                quoted_new_table_name,
+                definitions.join(", ")
+            ),
+            format!(
+                "INSERT INTO {} ({}) SELECT {} FROM {};",
+                quoted_new_table_name,
+                targets.join(", "),
+                sources.join(", "),
+                table_name
+            ),
+            format!("DROP TABLE {};", table_name),
+            format!(
+                "ALTER TABLE {} RENAME TO {};",
+                quoted_new_table_name, table_name
+            ),
+        ])
+    }
+}
+
+/// Renders the definition of a column in a `CREATE TABLE` or `ADD COLUMN` statement.
+fn column_definition(column: &ColumnDef) -> String {
+    let mut definition = format!(
+        "{} {}",
+        Dialect::Sqlite.quote_identifier(&column.name),
+        column.data_type
+    );
+    if !column.nullable {
+        definition.push_str(" NOT NULL");
+    }
+    if let Some(default_value) = &column.default_value {
+        definition.push_str(&format!(" DEFAULT {}", default_value));
+    }
+    if column.primary_key {
+        definition.push_str(" PRIMARY KEY");
+    }
+    definition
+}
@@ -0,0 +1,353 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Instant;
+
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Batch, Connection, Row, Statement};
+
+use crate::config::{ConfiguredConnection, ConnectionConfig, DEFAULT_CONFIG};
+use crate::error::Error;
+
+use log::warn;
+
+/// A connection the statements of the query builders are executed on.
+///
+/// It is implemented by `rusqlite::Connection`, which has the default settings, and by
+/// `ConfiguredConnection`, `ReadOnlyConnection` and `DryRunConnection`. All of them are
+/// passed to the query builders like a connection, e.g. `sqlite::insert(&conn, rows)`.
+pub trait Executor {
+    /// Returns the settings of the connection, e.g. its table prefix.
+    fn config(&self) -> &ConnectionConfig;
+
+    /// Executes a statement, binding `params` to its placeholders in order.
+    ///
+    /// Returns the number of rows the statement changed.
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<usize, Error>;
+
+    /// Executes one or more statements without parameters.
+    fn execute_batch(&self, sql: &str) -> Result<(), Error>;
+
+    /// Runs a query, binding `params` to its placeholders in order, and calls `f` with
+    /// every result row.
+    fn query(
+        &self,
+        sql: &str,
+        params: &[Value],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error>;
+
+    /// Runs a query, binding `params` to its named placeholders, and calls `f` with every
+    /// result row.
+    ///
+    /// The names of `params` may be given with or without the leading `:`. A placeholder
+    /// without a value fails with `Error::InvalidQuery`.
+    fn query_named(
+        &self,
+        sql: &str,
+        params: &[(String, Value)],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error>;
+
+    /// Returns the rowid of the last inserted row.
+    fn last_insert_rowid(&self) -> i64;
+
+    /// Returns the number of rows changed by the last statement.
+    fn changes(&self) -> u64;
+
+    /// Returns `true` if no transaction is open.
+    fn is_autocommit(&self) -> bool;
+}
+
+/// Runs a query and collects its rows mapped by `f`, see `Executor::query`.
+pub(crate) fn query_map<T>(
+    conn: &dyn Executor,
+    sql: &str,
+    params: &[Value],
+    mut f: impl FnMut(&Row<'_>) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    let mut rows = Vec::new();
+    conn.query(sql, params, &mut |row| {
+        rows.push(f(row)?);
+        Ok(())
+    })?;
+
+    Ok(rows)
+}
+
+/// Runs a query and returns its first row mapped by `f`, or `None` if it has no rows.
+pub(crate) fn query_row<T>(
+    conn: &dyn Executor,
+    sql: &str,
+    params: &[Value],
+    f: impl FnMut(&Row<'_>) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    Ok(query_map(conn, sql, params, f)?.into_iter().next())
+}
+
+/// Executes the statements of an `Executor` on a SQLite connection.
+pub(crate) struct Database<'a> {
+    conn: &'a Connection,
+    config: &'a ConnectionConfig,
+    read_only: bool,
+}
+
+impl<'a> Database<'a> {
+    /// Creates a `Database` executing statements on `conn` with the settings of `config`.
+    pub(crate) fn new(conn: &'a Connection, config: &'a ConnectionConfig) -> Self {
+        Database {
+            conn,
+            config,
+            read_only: false,
+        }
+    }
+
+    /// Rejects statements that write to the database with `Error::ReadOnly` before they
+    /// are executed.
+    pub(crate) fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// See `Executor::execute`.
+    pub(crate) fn execute(&self, sql: &str, params: &[Value]) -> Result<usize, Error> {
+        self.timed(sql, || {
+            let mut stmt = self.prepare(sql)?;
+            Ok(stmt.execute(params_from_iter(params))?)
+        })
+    }
+
+    /// See `Executor::execute_batch`.
+    pub(crate) fn execute_batch(&self, sql: &str) -> Result<(), Error> {
+        self.timed(sql, || {
+            if !self.read_only {
+                return Ok(self.conn.execute_batch(sql)?);
+            }
+
+            let mut batch = Batch::new(self.conn, sql);
+            while let Some(mut stmt) = batch.next()? {
+                self.check_read_only(&stmt)?;
+                stmt.raw_execute()?;
+            }
+            Ok(())
+        })
+    }
+
+    /// See `Executor::query`.
+    pub(crate) fn query(
+        &self,
+        sql: &str,
+        params: &[Value],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.timed(sql, || {
+            let mut stmt = self.prepare(sql)?;
+            let mut rows = stmt.query(params_from_iter(params))?;
+            while let Some(row) = rows.next()? {
+                f(row)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// See `Executor::query_named`.
+    pub(crate) fn query_named(
+        &self,
+        sql: &str,
+        params: &[(String, Value)],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.timed(sql, || {
+            let mut stmt = self.prepare(sql)?;
+
+            for index in 1..=stmt.parameter_count() {
+                let placeholder = match stmt.parameter_name(index) {
+                    Some(name) => name.to_string(),
+                    None => {
+                        return Err(Error::InvalidQuery(format!(
+                            "positional parameter {} used in a query with named parameters",
+                            index
+                        )))
+                    }
+                };
+
+                let value = params
+                    .iter()
+                    .find(|(name, _)| {
+                        name == placeholder.trim_start_matches([':', '@', '$'])
+                            || *name == placeholder
+                    })
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| {
+                        Error::InvalidQuery(format!(
+                            "missing value for parameter `{}`",
+                            placeholder
+                        ))
+                    })?;
+
+                stmt.raw_bind_parameter(index, value)?;
+            }
+
+            let mut rows = stmt.raw_query();
+            while let Some(row) = rows.next()? {
+                f(row)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Prepares a statement, rejecting it if it writes to a read-only database.
+    fn prepare(&self, sql: &str) -> Result<Statement<'a>, Error> {
+        let stmt = self.conn.prepare(sql)?;
+        self.check_read_only(&stmt)?;
+
+        Ok(stmt)
+    }
+
+    /// Returns `Error::ReadOnly` if the database is read-only and `stmt` writes to it.
+    fn check_read_only(&self, stmt: &Statement<'_>) -> Result<(), Error> {
+        if self.read_only && !stmt.readonly() {
+            return Err(Error::ReadOnly(
+                format!(
+                    "`{}` writes to the database",
+                    stmt.expanded_sql().unwrap_or_default()
+                )
+                .into(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Runs an operation executing `sql`, logging it as a slow query if it took at least
+    /// the slow query threshold of the connection.
+    ///
+    /// Slow queries are logged as warnings with the `njord::slow_query` target.
+    fn timed<T>(&self, sql: &str, op: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+        let start = Instant::now();
+        let result = op();
+
+        if let Some(threshold) = self.config.slow_query_threshold {
+            let elapsed = start.elapsed();
+            if elapsed >= threshold {
+                warn!(target: "njord::slow_query", "slow query took {:?}: {}", elapsed, sql);
+            }
+        }
+
+        result
+    }
+}
+
+impl Executor for Connection {
+    fn config(&self) -> &ConnectionConfig {
+        &DEFAULT_CONFIG
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<usize, Error> {
+        Database::new(self, &DEFAULT_CONFIG).execute(sql, params)
+    }
+
+    fn execute_batch(&self, sql: &str) -> Result<(), Error> {
+        Database::new(self, &DEFAULT_CONFIG).execute_batch(sql)
+    }
+
+    fn query(
+        &self,
+        sql: &str,
+        params: &[Value],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        Database::new(self, &DEFAULT_CONFIG).query(sql, params, f)
+    }
+
+    fn query_named(
+        &self,
+        sql: &str,
+        params: &[(String, Value)],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        Database::new(self, &DEFAULT_CONFIG).query_named(sql, params, f)
+    }
+
+    fn last_insert_rowid(&self) -> i64 {
+        Connection::last_insert_rowid(self)
+    }
+
+    fn changes(&self) -> u64 {
+        Connection::changes(self)
+    }
+
+    fn is_autocommit(&self) -> bool {
+        Connection::is_autocommit(self)
+    }
+}
+
+impl Executor for ConfiguredConnection<Connection> {
+    fn config(&self) -> &ConnectionConfig {
+        ConfiguredConnection::config(self)
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<usize, Error> {
+        Database::new(self.get_ref(), self.config()).execute(sql, params)
+    }
+
+    fn execute_batch(&self, sql: &str) -> Result<(), Error> {
+        Database::new(self.get_ref(), self.config()).execute_batch(sql)
+    }
+
+    fn query(
+        &self,
+        sql: &str,
+        params: &[Value],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        Database::new(self.get_ref(), self.config()).query(sql, params, f)
+    }
+
+    fn query_named(
+        &self,
+        sql: &str,
+        params: &[(String, Value)],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        Database::new(self.get_ref(), self.config()).query_named(sql, params, f)
+    }
+
+    fn last_insert_rowid(&self) -> i64 {
+        self.get_ref().last_insert_rowid()
+    }
+
+    fn changes(&self) -> u64 {
+        self.get_ref().changes()
+    }
+
+    fn is_autocommit(&self) -> bool {
+        self.get_ref().is_autocommit()
+    }
+}
@@ -0,0 +1,235 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{
+    condition::{Condition, Value},
+    dialect::Dialect,
+    error::Error,
+    sqlite::{
+        connection::{query_map, query_row},
+        util::{generate_where_condition_str, to_condition_value},
+        Executor,
+    },
+    table::Table,
+};
+
+use std::collections::HashMap;
+
+use log::debug;
+use rusqlite::types::Value as SqliteValue;
+
+/// Constructs a new query counting the rows matching the WHERE clause.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `columns` - The columns of the counted rows, used by `distinct`. An empty list
+///   stands for all columns.
+///
+/// # Returns
+///
+/// A `CountQueryBuilder` instance.
+pub fn count(conn: &dyn Executor, columns: Vec<String>) -> CountQueryBuilder<'_> {
+    CountQueryBuilder::new(conn, columns)
+}
+
+/// A builder for constructing queries counting the matching rows.
+pub struct CountQueryBuilder<'a> {
+    conn: &'a dyn Executor,
+    columns: Vec<String>,
+    table_name: Option<String>,
+    where_condition: Option<Condition<'a>>,
+    distinct: bool,
+    soft_delete_column: Option<String>,
+    with_deleted: bool,
+}
+
+impl<'a> CountQueryBuilder<'a> {
+    /// Creates a new `CountQueryBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    /// * `columns` - The columns of the counted rows, used by `distinct`.
+    pub fn new(conn: &'a dyn Executor, columns: Vec<String>) -> Self {
+        CountQueryBuilder {
+            conn,
+            columns,
+            table_name: None,
+            where_condition: None,
+            distinct: false,
+            soft_delete_column: None,
+            with_deleted: false,
+        }
+    }
+
+    /// Sets the table to count the rows of, prefixed with the table prefix of the connection.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - An instance of the table to count the rows of.
+    pub fn from<T: Table>(mut self, table: &T) -> Self {
+        self.table_name = Some(table.get_prefixed_name(&self.conn.config().table_prefix));
+        self.soft_delete_column = table.get_soft_delete_column().map(str::to_string);
+        self
+    }
+
+    /// Sets the WHERE clause condition the rows must match.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition to be applied in the WHERE clause.
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
+        self
+    }
+
+    /// Counts the distinct combinations of the columns instead of all matching rows.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Includes the soft deleted rows of a table with the `#[soft_delete]` attribute,
+    /// which are not counted by default.
+    pub fn with_deleted(mut self) -> Self {
+        self.with_deleted = true;
+        self
+    }
+
+    /// Generates the WHERE clause, leaving out soft deleted rows unless `with_deleted` is set.
+    fn where_condition_str(&self) -> String {
+        let where_condition = match (&self.soft_delete_column, self.with_deleted) {
+            (Some(column), false) => {
+                let not_deleted = Condition::NullSafeEq(column.clone(), Value::Null);
+                Some(match self.where_condition.clone() {
+                    Some(condition) => condition.and(not_deleted),
+                    None => not_deleted,
+                })
+            }
+            _ => self.where_condition.clone(),
+        };
+        generate_where_condition_str(where_condition)
+    }
+
+    /// Builds the query string, this function should be used internally.
+    pub fn build_query(&self) -> String {
+        let table_name = self.table_name.clone().unwrap_or_default();
+        let where_condition_str = self.where_condition_str();
+
+        if !self.distinct {
+            return format!(
+                "SELECT COUNT(*) FROM {} {}",
+                table_name, where_condition_str
+            );
+        }
+
+        let columns_str = if self.columns.is_empty() {
+            "*".to_string()
+        } else {
+            self.columns
+                .iter()
+                .map(|column| Dialect::Sqlite.quote_identifier(column))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        // The derived table needs an alias, given without AS for Oracle
+        format!(
+            "SELECT COUNT(*) FROM (SELECT DISTINCT {} FROM {} {}) njord_distinct",
+            columns_str, table_name, where_condition_str
+        )
+    }
+
+    /// Builds and executes the query.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of matching rows, or distinct combinations
+    /// of the columns, or an `Error` if an error occurs during the execution.
+    pub fn build(self) -> Result<usize, Error> {
+        if let Some(condition) = &self.where_condition {
+            condition.check()?;
+        }
+        let query = self.build_query();
+
+        debug!("{}", query);
+
+        let count: Option<i64> = query_row(self.conn, &query, &[], |row| Ok(row.get(0)?))?;
+
+        Ok(count.unwrap_or_default() as usize)
+    }
+
+    /// Builds the query string counting the matching rows per value of a column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to group the rows by.
+    pub fn build_counts_by_query(&self, column: &str) -> String {
+        let table_name = self.table_name.clone().unwrap_or_default();
+        let column = Dialect::Sqlite.quote_identifier(column);
+
+        format!(
+            "SELECT {}, COUNT(*) FROM {} {} GROUP BY {}",
+            column,
+            table_name,
+            self.where_condition_str(),
+            column
+        )
+    }
+
+    /// Executes a query counting the matching rows per value of a column, using
+    /// `SELECT column, COUNT(*) ... GROUP BY column`.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to group the rows by.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of matching rows per value of the column, with
+    /// `Value::Null` for the rows where it is NULL, or an `Error` if an error occurs
+    /// during the execution.
+    pub fn counts_by(self, column: &str) -> Result<HashMap<Value<'static>, u64>, Error> {
+        if let Some(condition) = &self.where_condition {
+            condition.check()?;
+        }
+        let query = self.build_counts_by_query(column);
+
+        debug!("{}", query);
+
+        let counts = query_map(self.conn, &query, &[], |row| {
+            let value = to_condition_value(row.get::<usize, SqliteValue>(0)?);
+            let count: i64 = row.get(1)?;
+            Ok((value, count as u64))
+        })?;
+
+        Ok(counts.into_iter().collect())
+    }
+}
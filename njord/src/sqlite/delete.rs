@@ -30,16 +30,19 @@
 use std::collections::HashMap;
 
 use crate::{
-    condition::Condition,
+    condition::{Condition, Value},
+    dialect::Dialect,
+    error::Error,
+    sqlite::raw::to_sqlite_value,
     sqlite::util::{
-        generate_limit_str, generate_offset_str, generate_order_by_str,
-        generate_where_condition_str, remove_quotes_and_backslashes,
+        generate_limited_where_str, remove_quotes_and_backslashes, supports_update_delete_limit,
     },
+    sqlite::Executor,
 };
 
-use rusqlite::{Connection, Result};
+use rusqlite::types::Value as SqliteValue;
 
-use log::{debug, info};
+use log::debug;
 
 use crate::table::Table;
 
@@ -52,6 +55,79 @@ pub fn delete<T: Table + Default>() -> DeleteQueryBuilder<'static, T> {
     DeleteQueryBuilder::new()
 }
 
+/// The maximum number of keys bound to a single `delete_by_pks` statement, the lowest
+/// limit of bound parameters of SQLite versions.
+const MAX_KEYS_PER_STATEMENT: usize = 999;
+
+/// Deletes the rows of a table by a list of primary keys.
+///
+/// The rows are deleted with `DELETE FROM t WHERE pk IN (...)` statements of at most
+/// 999 keys each, all within a single savepoint, so either all or none of the rows are
+/// deleted. For tables with a soft delete column, the rows that are not deleted yet are
+/// updated to set the column to the current time instead.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `table` - An instance of the table to delete the rows from, prefixed with the table
+///   prefix of the connection.
+/// * `keys` - The primary keys of the rows to delete.
+///
+/// # Returns
+///
+/// A `Result` containing the total number of deleted rows, or an `Error` if the table has
+/// no primary key or a statement fails.
+pub fn delete_by_pks<T: Table>(
+    conn: &dyn Executor,
+    table: &T,
+    keys: Vec<Value>,
+) -> std::result::Result<usize, Error> {
+    let primary_key = table.get_primary_key_column().ok_or_else(|| {
+        Error::InvalidQuery(format!("table `{}` has no primary key", table.get_name()))
+    })?;
+    let primary_key = Dialect::Sqlite.quote_identifier(primary_key);
+    let table_name =
+        remove_quotes_and_backslashes(&table.get_prefixed_name(&conn.config().table_prefix));
+
+    let statement = match table.get_soft_delete_column() {
+        Some(column) => {
+            let column = Dialect::Sqlite.quote_identifier(column);
+            format!(
+                "UPDATE {} SET {} = CURRENT_TIMESTAMP WHERE {} IS NULL AND",
+                table_name, column, column
+            )
+        }
+        None => format!("DELETE FROM {} WHERE", table_name),
+    };
+
+    let keys = keys
+        .iter()
+        .map(to_sqlite_value)
+        .collect::<std::result::Result<Vec<SqliteValue>, Error>>()?;
+
+    conn.execute_batch("SAVEPOINT njord_delete_by_pks")?;
+
+    let mut deleted = 0;
+    for chunk in keys.chunks(MAX_KEYS_PER_STATEMENT) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        let query = format!("{} {} IN ({})", statement, primary_key, placeholders);
+
+        debug!("{}", query);
+
+        match conn.execute(&query, chunk) {
+            Ok(changed) => deleted += changed,
+            Err(e) => {
+                conn.execute_batch("ROLLBACK TO njord_delete_by_pks; RELEASE njord_delete_by_pks")?;
+                return Err(e);
+            }
+        }
+    }
+
+    conn.execute_batch("RELEASE njord_delete_by_pks")?;
+
+    Ok(deleted)
+}
+
 /// A builder for constructing DELETE queries.
 pub struct DeleteQueryBuilder<'a, T: Table + Default> {
     table: Option<T>,
@@ -92,8 +168,8 @@ impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
         self
     }
 
@@ -109,6 +185,9 @@ impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
 
     /// Sets the LIMIT clause for the query.
     ///
+    /// Unless SQLite is compiled with `SQLITE_ENABLE_UPDATE_DELETE_LIMIT`, the limit is
+    /// applied with a subquery on the primary key, or the rowid for tables without one.
+    ///
     /// # Arguments
     ///
     /// * `count` - The maximum number of rows to be deleted.
@@ -128,6 +207,10 @@ impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
     }
 
     /// Builds and executes the DELETE query.
+    ///
+    /// For tables with a soft delete column, the matching rows that are not deleted yet
+    /// are updated to set the column to the current time instead. The table name is
+    /// prefixed with the table prefix of the connection.
     /// 
     /// # Arguments
     /// 
@@ -135,34 +218,69 @@ impl<'a, T: Table + Default> DeleteQueryBuilder<'a, T> {
     /// 
     /// # Returns
     ///
-    /// A `Result` indicating success or failure of the deletion operation.
-    pub fn build(self, conn: &Connection) -> Result<(), String> {
+    /// A `Result` containing the number of deleted rows if successful,
+    /// or an `Error` if an error occurs.
+    pub fn build(self, conn: &dyn Executor) -> std::result::Result<usize, Error> {
+        if let Some(condition) = &self.where_condition {
+            condition.check()?;
+        }
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(&conn.config().table_prefix))
             .unwrap_or("".to_string());
 
         // Sanitize table name from unwanted quotations or backslashes
         let table_name_str = remove_quotes_and_backslashes(&table_name);
-        let where_condition_str = generate_where_condition_str(self.where_condition);
-        let order_by_str = generate_order_by_str(&self.order_by);
-        let limit_str = generate_limit_str(self.limit);
-        let offset_str = generate_offset_str(self.offset);
+        let soft_delete_column = self
+            .table
+            .as_ref()
+            .and_then(|t| t.get_soft_delete_column().map(str::to_string));
 
-        // Construct the query based on defined variables above
-        let query = format!(
-            "DELETE FROM {} {} {} {}",
-            table_name_str,
-            where_condition_str,
-            order_by_str,
-            format!("{} {}", limit_str, offset_str),
+        let where_condition = match &soft_delete_column {
+            Some(column) => {
+                let not_deleted = Condition::NullSafeEq(column.clone(), Value::Null);
+                Some(match self.where_condition {
+                    Some(condition) => condition.and(not_deleted),
+                    None => not_deleted,
+                })
+            }
+            None => self.where_condition,
+        };
+        let key_column = self
+            .table
+            .as_ref()
+            .and_then(|t| t.get_primary_key_column())
+            .unwrap_or("rowid");
+        let (where_condition_str, limit_str) = generate_limited_where_str(
+            supports_update_delete_limit(conn),
+            &table_name_str,
+            key_column,
+            where_condition,
+            &self.order_by,
+            self.limit,
+            self.offset,
         );
 
+        let statement = match &soft_delete_column {
+            Some(column) => format!(
+                "UPDATE {} SET {} = CURRENT_TIMESTAMP",
+                table_name_str, column
+            ),
+            None => format!("DELETE FROM {}", table_name_str),
+        };
+
+        // Construct the query based on defined variables above
+        let query = format!("{} {} {}", statement, where_condition_str, limit_str);
+
         debug!("{}", query);
 
-        let _ = conn.execute(&query.to_string(), []);
+        conn.execute(&query, &[])
+    }
+}
 
-        Ok(())
+impl<'a, T: Table + Default> Default for DeleteQueryBuilder<'a, T> {
+    fn default() -> Self {
+        Self::new()
     }
 }
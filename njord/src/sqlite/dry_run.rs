@@ -0,0 +1,153 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::cell::RefCell;
+
+use rusqlite::types::Value;
+use rusqlite::Row;
+
+use crate::config::ConnectionConfig;
+use crate::error::Error;
+
+use super::connection::Executor;
+
+/// A statement recorded by a `DryRunConnection`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedStatement {
+    /// The SQL of the statement, with the placeholders of its parameters.
+    pub sql: String,
+    /// The values bound to the placeholders, in order. The values of named placeholders
+    /// are in the order they were given.
+    pub params: Vec<Value>,
+}
+
+/// A connection that records the SQL statements of operations instead of executing them.
+///
+/// Every statement is recorded with its parameters, in the order the query builders
+/// execute them, which makes the statement stream of an operation easy to assert on in
+/// tests and tooling. Nothing is sent to a database: statements change no rows and
+/// queries return no rows, so operations that depend on the result of a previous
+/// statement, e.g. `update_by_pk` of a table with a version column, may fail or take a
+/// different path than on a real connection.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let conn = DryRunConnection::new();
+/// sqlite::insert(&conn, users)?;
+/// println!("{:?}", conn.statements());
+/// ```
+#[derive(Debug, Default)]
+pub struct DryRunConnection {
+    config: ConnectionConfig,
+    statements: RefCell<Vec<CapturedStatement>>,
+}
+
+impl DryRunConnection {
+    /// Creates a connection recording its statements, with the default settings.
+    pub fn new() -> Self {
+        DryRunConnection::default()
+    }
+
+    /// Sets the settings the statements are built with, e.g. the table prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The settings of the connection.
+    pub fn with_config(mut self, config: ConnectionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the recorded statements in execution order.
+    pub fn statements(&self) -> Vec<CapturedStatement> {
+        self.statements.borrow().clone()
+    }
+
+    /// Clears the recorded statements.
+    pub fn clear(&self) {
+        self.statements.borrow_mut().clear();
+    }
+
+    /// Records a statement.
+    fn capture(&self, sql: &str, params: Vec<Value>) {
+        self.statements.borrow_mut().push(CapturedStatement {
+            sql: sql.to_string(),
+            params,
+        });
+    }
+}
+
+impl Executor for DryRunConnection {
+    fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<usize, Error> {
+        self.capture(sql, params.to_vec());
+        Ok(0)
+    }
+
+    fn execute_batch(&self, sql: &str) -> Result<(), Error> {
+        self.capture(sql, Vec::new());
+        Ok(())
+    }
+
+    fn query(
+        &self,
+        sql: &str,
+        params: &[Value],
+        _f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.capture(sql, params.to_vec());
+        Ok(())
+    }
+
+    fn query_named(
+        &self,
+        sql: &str,
+        params: &[(String, Value)],
+        _f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.capture(sql, params.iter().map(|(_, value)| value.clone()).collect());
+        Ok(())
+    }
+
+    fn last_insert_rowid(&self) -> i64 {
+        0
+    }
+
+    fn changes(&self) -> u64 {
+        0
+    }
+
+    fn is_autocommit(&self) -> bool {
+        true
+    }
+}
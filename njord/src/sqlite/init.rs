@@ -0,0 +1,321 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::dialect::Dialect;
+use crate::error::Error;
+use crate::table::Table;
+
+use super::connection::Executor;
+use super::util::quote_identifiers;
+
+/// Creates the tables in a SQLite database.
+///
+/// A `CREATE TABLE IF NOT EXISTS` statement is generated for every table, with the columns,
+/// types and defaults derived from the `Table` implementation, followed by its indexes.
+/// Tables are created after the tables their foreign keys reference, see `sort_by_dependencies`.
+/// The names of the tables and their indexes are prefixed with the table prefix of the connection.
+///
+/// All statements are executed as a single batch. Outside of a transaction the batch runs in
+/// its own transaction, so either all tables are created or none of them. Inside a transaction
+/// the statements become part of it.
+///
+/// # Arguments
+///
+/// * `conn` - A connection to the SQLite database.
+/// * `tables` - The tables to create.
+///
+/// # Returns
+///
+/// A `Result` containing `()` if all tables were created,
+/// or an `Error` if a statement failed.
+pub fn init(conn: &dyn Executor, tables: Vec<Box<dyn Table>>) -> Result<(), Error> {
+    let statements = init_statements(&tables, &conn.config().table_prefix)?;
+
+    execute_atomically(conn, &statements)
+}
+
+/// Executes statements in a single transaction, so either all or none of them apply.
+///
+/// Inside an open transaction, the statements are executed as part of it.
+///
+/// # Arguments
+///
+/// * `conn` - A connection to the SQLite database.
+/// * `statements` - The statements to execute, in order.
+///
+/// # Returns
+///
+/// A `Result` containing `()` if all statements were executed, or the `Error` of the
+/// failing statement.
+pub(crate) fn execute_atomically(conn: &dyn Executor, statements: &[String]) -> Result<(), Error> {
+    if !conn.is_autocommit() {
+        conn.execute_batch(&statements.join("\n"))?;
+        return Ok(());
+    }
+
+    let batch = format!("BEGIN;\n{}\nCOMMIT;", statements.join("\n"));
+    if let Err(e) = conn.execute_batch(&batch) {
+        if !conn.is_autocommit() {
+            conn.execute_batch("ROLLBACK;")?;
+        }
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Generates the `CREATE TABLE` and `CREATE INDEX` statements executed by `init`,
+/// in the order they are executed.
+///
+/// # Arguments
+///
+/// * `tables` - The tables to create.
+///
+/// # Returns
+///
+/// A `Result` containing the statements, or an `Error::InvalidQuery` if the
+/// foreign keys form a cycle or a `WITHOUT ROWID` table has no primary key.
+pub fn generate_init_statements(tables: &[Box<dyn Table>]) -> Result<Vec<String>, Error> {
+    init_statements(tables, "")
+}
+
+/// Generates the statements of `generate_init_statements` with the table names and
+/// index names prefixed with `table_prefix`.
+pub(crate) fn init_statements(
+    tables: &[Box<dyn Table>],
+    table_prefix: &str,
+) -> Result<Vec<String>, Error> {
+    let mut statements = Vec::new();
+
+    for table in sort_by_dependencies(tables)? {
+        if table.is_without_rowid() && table.get_primary_key_column().is_none() {
+            return Err(Error::InvalidQuery(format!(
+                "WITHOUT ROWID table `{}` requires a primary key",
+                table.get_name()
+            )));
+        }
+        table.check_mapped_types()?;
+        statements.push(create_table_statement(table, table_prefix));
+        statements.extend(create_index_statements(table, table_prefix));
+    }
+
+    Ok(statements)
+}
+
+/// Sorts the tables so that every table comes after the tables its foreign keys reference.
+///
+/// Tables without dependencies between them keep the order they were given in.
+/// The referenced tables are given by `Table::dependencies`, references to tables that
+/// are not in the list are ignored.
+///
+/// # Arguments
+///
+/// * `tables` - The tables to sort.
+///
+/// # Returns
+///
+/// A `Result` containing the sorted tables, or an `Error::InvalidQuery` if the
+/// foreign keys form a cycle.
+pub fn sort_by_dependencies(tables: &[Box<dyn Table>]) -> Result<Vec<&dyn Table>, Error> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Unvisited,
+        Visiting,
+        Done,
+    }
+
+    fn visit<'t>(
+        index: usize,
+        tables: &'t [Box<dyn Table>],
+        states: &mut [State],
+        path: &mut Vec<String>,
+        sorted: &mut Vec<&'t dyn Table>,
+    ) -> Result<(), Error> {
+        let table = tables[index].as_ref();
+        match states[index] {
+            State::Done => return Ok(()),
+            State::Visiting => {
+                path.push(table.get_name().to_string());
+                return Err(Error::InvalidQuery(format!(
+                    "foreign key cycle between tables: {}",
+                    path.join(" -> ")
+                )));
+            }
+            State::Unvisited => {}
+        }
+
+        states[index] = State::Visiting;
+        path.push(table.get_name().to_string());
+
+        for referenced in table.dependencies() {
+            let dependency = tables
+                .iter()
+                .position(|t| t.get_name() == referenced || t.get_qualified_name() == referenced);
+            match dependency {
+                Some(dependency) if dependency != index => {
+                    visit(dependency, tables, states, path, sorted)?
+                }
+                _ => {}
+            }
+        }
+
+        path.pop();
+        states[index] = State::Done;
+        sorted.push(table);
+
+        Ok(())
+    }
+
+    let mut states = vec![State::Unvisited; tables.len()];
+    let mut sorted = Vec::with_capacity(tables.len());
+
+    for index in 0..tables.len() {
+        visit(index, tables, &mut states, &mut Vec::new(), &mut sorted)?;
+    }
+
+    Ok(sorted)
+}
+
+/// Generates the `CREATE TABLE` statement for a table.
+///
+/// Columns are emitted in field declaration order, with their generated column clauses,
+/// defaults and foreign keys,
+/// followed by the uniqueness and check constraints of the table. Tables with `is_without_rowid` are
+/// created `WITHOUT ROWID`.
+///
+/// # Arguments
+///
+/// * `table` - The table to generate the statement for.
+///
+/// # Returns
+///
+/// A `String` containing the `CREATE TABLE` statement.
+pub fn generate_create_table_statement(table: &dyn Table) -> String {
+    create_table_statement(table, "")
+}
+
+/// Generates the `CREATE TABLE` statement of `generate_create_table_statement` for the
+/// table name prefixed with `table_prefix`.
+fn create_table_statement(table: &dyn Table, table_prefix: &str) -> String {
+    let columns = table.get_columns();
+    let defaults = table.get_column_defaults();
+    let foreign_keys = table.get_foreign_keys();
+    let generated_columns = table.get_generated_columns();
+
+    let column_definitions = table
+        .get_column_fields()
+        .iter()
+        .map(|field| {
+            let column_type = columns.get(field).map(String::as_str).unwrap_or("");
+            let mut definition =
+                format!("{} {}", Dialect::Sqlite.quote_identifier(field), column_type);
+            if let Some(generated) = generated_columns.get(field) {
+                definition.push_str(&format!(" {}", generated));
+            }
+            if let Some(default_sql) = defaults.get(field) {
+                definition.push_str(&format!(" DEFAULT {}", default_sql));
+            }
+            if let Some(references) = foreign_keys.get(field) {
+                definition.push_str(&format!(" {}", references));
+            }
+            definition
+        })
+        .chain(
+            table
+                .get_unique_constraints()
+                .iter()
+                .map(|columns| format!("UNIQUE ({})", quote_identifiers(columns))),
+        )
+        .chain(
+            table
+                .get_checks()
+                .iter()
+                .map(|check| format!("CHECK ({})", check)),
+        )
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} ({}){};",
+        table.get_prefixed_name(table_prefix),
+        column_definitions,
+        if table.is_without_rowid() {
+            " WITHOUT ROWID"
+        } else {
+            ""
+        }
+    )
+}
+
+/// Generates the `CREATE INDEX` statements for the indexes of a table.
+///
+/// Indexes with a predicate are created as partial indexes with a WHERE clause.
+///
+/// # Arguments
+///
+/// * `table` - The table to generate the statements for.
+///
+/// # Returns
+///
+/// A `Vec<String>` containing a `CREATE INDEX` statement per index.
+pub fn generate_create_index_statements(table: &dyn Table) -> Vec<String> {
+    create_index_statements(table, "")
+}
+
+/// Generates the `CREATE INDEX` statements of `generate_create_index_statements` with the
+/// table name and the index names prefixed with `table_prefix`.
+fn create_index_statements(table: &dyn Table, table_prefix: &str) -> Vec<String> {
+    // SQLite qualifies the index with the schema, not the table
+    let table_name = format!("{}{}", table_prefix, table.get_name());
+    let schema_prefix = table
+        .get_schema()
+        .map(|schema| format!("{}.", schema))
+        .unwrap_or_default();
+
+    table
+        .get_indexes()
+        .iter()
+        .map(|index| {
+            let mut statement = format!(
+                "CREATE {}INDEX IF NOT EXISTS {}{}{} ON {} ({})",
+                if index.unique { "UNIQUE " } else { "" },
+                schema_prefix,
+                table_prefix,
+                index.name,
+                table_name,
+                quote_identifiers(&index.columns)
+            );
+            if let Some(predicate) = &index.predicate {
+                statement.push_str(&format!(" WHERE {}", predicate));
+            }
+            statement.push(';');
+            statement
+        })
+        .collect()
+}
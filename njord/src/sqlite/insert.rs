@@ -29,13 +29,15 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::{query::QueryBuilder, table::Table};
+use crate::{error::Error, query::QueryBuilder, table::Table};
 
-use rusqlite::Error as RusqliteError;
+use std::collections::HashMap;
+
+use super::connection::{query_map, Executor};
+use super::select::{is_blob_column, row_to_table, SelectQueryBuilder};
+use super::util::{check_integer_range, quote_identifiers, remove_quotes_and_backslashes};
 
 use log::{debug, info};
-use rusqlite::{Connection, Result};
-use std::fmt::Error;
 
 /// Inserts rows into a SQLite table.
 ///
@@ -46,32 +48,255 @@ use std::fmt::Error;
 ///
 /// # Arguments
 ///
-/// * `conn` - A `Connection` to the SQLite database.
+/// * `conn` - A connection to the SQLite database.
 /// * `table_rows` - A vector of objects implementing the `Table` trait representing
-///                  the rows to be inserted into the database.
+///   the rows to be inserted into the database.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the joined SQL statements
-/// if the insertion is successful, or a `RusqliteError` if an error occurs.
-pub fn insert<T: Table>(conn: &Connection, table_rows: Vec<T>) -> Result<String, RusqliteError> {
-    let mut statements: Vec<String> = Vec::new();
-    for (index, table_row) in table_rows.iter().enumerate() {
-        match generate_statement(table_row, index == 0) {
-            Ok(statement) => statements.push(statement),
-            Err(_) => return Err(RusqliteError::InvalidQuery),
+/// if the insertion is successful, or an `Error` if an error occurs.
+pub fn insert<T: Table>(conn: &dyn Executor, table_rows: Vec<T>) -> Result<String, Error> {
+    InsertQueryBuilder::new(table_rows).build(conn)
+}
+
+/// Inserts a row with the default values of all columns into a SQLite table, using
+/// `INSERT INTO t DEFAULT VALUES`.
+///
+/// # Arguments
+///
+/// * `conn` - A connection to the SQLite database.
+/// * `table` - An instance of the table to insert the row into, prefixed with the table
+///   prefix of the connection.
+///
+/// # Returns
+///
+/// A `Result` containing the rowid of the new row if the insertion is successful,
+/// or an `Error` if a column without default is NOT NULL.
+pub fn insert_default<T: Table>(conn: &dyn Executor, table: &T) -> Result<i64, Error> {
+    let statement = format!(
+        "INSERT INTO {} DEFAULT VALUES",
+        remove_quotes_and_backslashes(&table.get_prefixed_name(&conn.config().table_prefix))
+    );
+
+    debug!("{}", statement);
+
+    conn.execute(&statement, &[])?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// The conflict resolution of an INSERT statement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConflictResolution {
+    /// INSERT OR IGNORE - Skips rows violating a constraint.
+    Ignore,
+    /// INSERT OR REPLACE - Deletes the conflicting rows before inserting.
+    Replace,
+}
+
+/// A builder for constructing INSERT queries with a conflict resolution.
+///
+/// `OR IGNORE` and `OR REPLACE` are SQLite specific, the other backends only
+/// provide plain inserts.
+pub struct InsertQueryBuilder<T: Table> {
+    table_rows: Vec<T>,
+    conflict: Option<ConflictResolution>,
+}
+
+impl<T: Table> InsertQueryBuilder<T> {
+    /// Creates a new `InsertQueryBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_rows` - A vector of objects implementing the `Table` trait representing
+    ///   the rows to be inserted into the database.
+    pub fn new(table_rows: Vec<T>) -> Self {
+        InsertQueryBuilder {
+            table_rows,
+            conflict: None,
+        }
+    }
+
+    /// Skips rows that would violate a unique, primary key, NOT NULL or CHECK constraint
+    /// with `INSERT OR IGNORE`.
+    pub fn or_ignore(mut self) -> Self {
+        self.conflict = Some(ConflictResolution::Ignore);
+        self
+    }
+
+    /// Replaces existing rows that conflict on a unique or primary key constraint
+    /// with `INSERT OR REPLACE`.
+    pub fn or_replace(mut self) -> Self {
+        self.conflict = Some(ConflictResolution::Replace);
+        self
+    }
+
+    /// Builds and executes the INSERT query.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A connection to the SQLite database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `String` representing the joined SQL statements
+    /// if the insertion is successful, or an `Error` if an error occurs.
+    pub fn build(self, conn: &dyn Executor) -> Result<String, Error> {
+        let joined_statements = self
+            .build_statements(&conn.config().table_prefix)?
+            .join("; ");
+
+        // FIXME: Convert to transaction
+        conn.execute_batch(&joined_statements)?;
+
+        info!("Inserted into table, done.");
+
+        // FIXME: Return the number of rows affected
+        Ok(joined_statements)
+    }
+
+    /// Builds and executes the INSERT query with a `RETURNING` clause, decoding the
+    /// inserted rows into `U`, e.g. to get the generated primary keys.
+    ///
+    /// The returned columns are the columns of `U`, so `U` can be the inserted table
+    /// type itself or a struct with a subset of its columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A connection to the SQLite database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the inserted rows if the insertion is successful,
+    /// or an `Error` if an error occurs.
+    pub fn returning_into<U: Table + Default>(
+        self,
+        conn: &dyn Executor,
+    ) -> Result<Vec<U>, Error> {
+        let returning_columns = quote_identifiers(&U::default().get_column_fields());
+        let mut rows = Vec::new();
+
+        for statement in self.build_statements(&conn.config().table_prefix)? {
+            let query = format!("{} RETURNING {}", statement, returning_columns);
+
+            debug!("{}", query);
+
+            rows.extend(query_map(conn, &query, &[], |row| {
+                Ok(row_to_table::<U>(row)?)
+            })?);
         }
+
+        Ok(rows)
     }
 
-    let joined_statements = statements.join(", ");
+    /// Inserts the rows one by one, skipping the rows that fail instead of aborting the
+    /// whole insert, e.g. to import partial data.
+    ///
+    /// Every row is inserted within its own savepoint, so a failed row is rolled back
+    /// without affecting the other rows. All rows are inserted within an enclosing
+    /// savepoint, which is a transaction if the connection is not in one yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A connection to the SQLite database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of inserted rows and the index and error of
+    /// every failed row, or an `Error` if the savepoints could not be created or released,
+    /// in which case all rows are rolled back.
+    pub fn continue_on_error(
+        self,
+        conn: &dyn Executor,
+    ) -> Result<(usize, Vec<(usize, Error)>), Error> {
+        conn.execute_batch("SAVEPOINT njord_insert")?;
 
-    // FIXME: Convert to transaction
-    let _ = conn.execute_batch(&joined_statements)?;
+        // Don't leave the savepoint open if the rows can't be inserted or released
+        let result = self.insert_rows(conn).and_then(|result| {
+            conn.execute_batch("RELEASE njord_insert")?;
+            Ok(result)
+        });
+        let (inserted, failures) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                let _ = conn.execute_batch("ROLLBACK TO njord_insert; RELEASE njord_insert");
+                return Err(e);
+            }
+        };
 
-    info!("Inserted into table, done.");
+        info!(
+            "Inserted {} rows into table, {} failed.",
+            inserted,
+            failures.len()
+        );
 
-    // FIXME: Return the number of rows affected
-    Ok(joined_statements)
+        Ok((inserted, failures))
+    }
+
+    /// Inserts the rows one by one within their own savepoint, see `continue_on_error`.
+    fn insert_rows(&self, conn: &dyn Executor) -> Result<(usize, Vec<(usize, Error)>), Error> {
+        let mut inserted = 0;
+        let mut failures = Vec::new();
+
+        for (index, table_row) in self.table_rows.iter().enumerate() {
+            let statement = match generate_statement(
+                std::slice::from_ref(table_row),
+                self.conflict,
+                &conn.config().table_prefix,
+            ) {
+                Ok(statement) => statement,
+                Err(e) => {
+                    failures.push((index, e));
+                    continue;
+                }
+            };
+
+            debug!("{}", statement);
+
+            conn.execute_batch("SAVEPOINT njord_insert_row")?;
+            match conn.execute(&statement, &[]) {
+                Ok(changed) => inserted += changed,
+                Err(e) => {
+                    conn.execute_batch("ROLLBACK TO njord_insert_row")?;
+                    failures.push((index, e));
+                }
+            }
+            conn.execute_batch("RELEASE njord_insert_row")?;
+        }
+
+        Ok((inserted, failures))
+    }
+
+    /// Builds the INSERT statements of all rows, one statement for every run of rows
+    /// with the same inserted columns, see `insert_columns`.
+    ///
+    /// Rows leaving out a column get its default, which can't be expressed in the
+    /// VALUES of other rows in SQLite.
+    fn build_statements(&self, table_prefix: &str) -> Result<Vec<String>, Error> {
+        let mut statements = Vec::new();
+        let mut start = 0;
+
+        while start < self.table_rows.len() {
+            let columns = insert_columns(&self.table_rows[start]);
+            let mut end = start + 1;
+            while !columns.is_empty()
+                && end < self.table_rows.len()
+                && insert_columns(&self.table_rows[end]) == columns
+            {
+                end += 1;
+            }
+
+            statements.push(generate_statement(
+                &self.table_rows[start..end],
+                self.conflict,
+                table_prefix,
+            )?);
+            start = end;
+        }
+
+        Ok(statements)
+    }
 }
 
 /// Generates an SQL INSERT INTO statement for a given table row.
@@ -83,17 +308,17 @@ pub fn insert<T: Table>(conn: &Connection, table_rows: Vec<T>) -> Result<String,
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the generated SQL statement
-/// if the operation is successful, or a `RusqliteError` if an error occurs.
+/// if the operation is successful, or an `Error` if an error occurs.
 pub fn into<'a, T: Table + Default>(
-    conn: &'a Connection,
+    conn: &'a dyn Executor,
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
-) -> Result<String, RusqliteError> {
-    let statement = generate_insert_into_statement::<T>(columns, subquery);
-    let sql = statement.unwrap();
+) -> Result<String, Error> {
+    let sql =
+        generate_insert_into_statement::<T>(columns, subquery, &conn.config().table_prefix)?;
 
     // FIXME: Convert to transaction
-    let _ = conn.execute_batch(&sql);
+    conn.execute_batch(&sql)?;
 
     info!("Inserted into table, done.");
 
@@ -101,25 +326,78 @@ pub fn into<'a, T: Table + Default>(
     Ok(sql)
 }
 
+/// Inserts the rows of a SELECT query into a table with `INSERT INTO ... SELECT`.
+///
+/// # Arguments
+///
+/// * `conn` - A connection to the SQLite database.
+/// * `table` - The table to insert the rows into, prefixed with the table prefix of the
+///   connection.
+/// * `columns` - The columns to insert, in the order of the selected columns.
+/// * `select` - The SELECT query providing the rows.
+///
+/// # Returns
+///
+/// A `Result` containing the number of inserted rows if successful, or an `Error`
+/// if the number of columns does not match the SELECT query or the insert failed.
+pub fn insert_from<'a, T, U>(
+    conn: &dyn Executor,
+    table: &T,
+    columns: Vec<String>,
+    select: SelectQueryBuilder<'a, U>,
+) -> Result<usize, Error>
+where
+    T: Table,
+    U: Table + Default + Clone + 'a,
+{
+    let select = select.with_table_prefix(&conn.config().table_prefix);
+    select.validate()?;
+
+    if columns.len() != select.column_count() {
+        return Err(Error::InvalidQuery(format!(
+            "INSERT into {} columns from a SELECT of {} columns",
+            columns.len(),
+            select.column_count()
+        )));
+    }
+
+    let (select_sql, params) = select.sql_with_bound_params()?;
+    let sql = format!(
+        "INSERT INTO {} ({}) {}",
+        table.get_prefixed_name(&conn.config().table_prefix),
+        columns.join(", "),
+        select_sql
+    );
+
+    debug!("{}", sql);
+
+    conn.execute(&sql, &params)
+}
+
 /// Generates an SQL INSERT INTO statement for a given subquery.
 ///
 /// # Arguments
 ///
 /// * `columns` - A `Vec` of column names.
 /// * `subquery` - A `QueryBuilder` object representing the subquery.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the generated SQL statement
-/// if the operation is successful, or a `RusqliteError` if an error occurs.
+/// if the operation is successful, or an `Error` if an error occurs.
 fn generate_insert_into_statement<'a, T: Table + Default>(
     columns: Vec<String>,
     subquery: Box<dyn QueryBuilder<'a> + 'a>,
-) -> Result<String, RusqliteError> {
+    table_prefix: &str,
+) -> Result<String, Error> {
     let columns_str = columns.join(", ");
     let subquery_str = subquery.to_sql();
     let table_row = T::default();
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+    let table_name = table_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
 
     let sql = format!(
         "INSERT INTO {} ({}) {}",
@@ -129,66 +407,99 @@ fn generate_insert_into_statement<'a, T: Table + Default>(
     Ok(sql)
 }
 
-/// Generates an SQL INSERT statement for a given table row.
+/// Returns the columns of a row that are inserted, leaving out its NULL columns, see
+/// `Table::get_null_columns`, and the generated columns computed by the database.
+fn insert_columns<T: Table>(table_row: &T) -> Vec<String> {
+    let null_columns = table_row.get_null_columns();
+    let generated_columns = table_row.get_generated_columns();
+
+    table_row
+        .get_column_fields()
+        .into_iter()
+        .filter(|column| !null_columns.contains(column))
+        .filter(|column| !generated_columns.contains_key(column))
+        .collect()
+}
+
+/// Generates an SQL INSERT statement for table rows with the same inserted columns.
 ///
-/// This function takes an object implementing the `Table` trait, representing
-/// a single row of data to be inserted into the database. It generates an SQL
-/// INSERT statement based on the column names and values of the table row.
+/// This function takes objects implementing the `Table` trait, representing
+/// the rows of data to be inserted into the database. It generates an SQL
+/// INSERT statement based on the column names and values of the table rows.
+///
+/// The columns of the first row are inserted, see `insert_columns`. A row without
+/// inserted columns is inserted with `DEFAULT VALUES`, which inserts a single row.
 ///
 /// # Arguments
 ///
-/// * `table_row` - An object implementing the `Table` trait representing
-///                 a single row of data to be inserted.
-/// * `first_statement` - A boolean flag indicating whether this is the first
-///                       statement to be generated.
+/// * `table_rows` - Objects implementing the `Table` trait representing
+///   the rows of data to be inserted, all with the same inserted columns.
+/// * `conflict` - The conflict resolution of the statement, if any.
+/// * `table_prefix` - The prefix of the table name.
 ///
 /// # Returns
 ///
 /// A `Result` containing a `String` representing the generated SQL statement
 /// if successful, or a `Error` if an error occurs during the generation process.
-fn generate_statement<T: Table>(table_row: &T, first_statement: bool) -> Result<String, Error> {
-    // Generate strings for columns and values
-    let mut columns_str = String::new();
-    let mut values_str = String::new();
-
-    // Iterate over the fields to generate columns and values
-    let column_fields = table_row.get_column_fields();
-    let column_values = table_row.get_column_values();
-
-    for (column_name, value) in column_fields.iter().zip(column_values.iter()) {
-        // Check if the field is an AutoIncrementPrimaryKey
-        if table_row.is_auto_increment_primary_key(value) {
-            debug!("Skipping AutoIncrementPrimaryKey field in SQL statement generation.");
-            continue;
-        }
+fn generate_statement<T: Table>(
+    table_rows: &[T],
+    conflict: Option<ConflictResolution>,
+    table_prefix: &str,
+) -> Result<String, Error> {
+    let Some(first_row) = table_rows.first() else {
+        return Err(Error::InvalidQuery("no rows to insert".to_string()));
+    };
+    first_row.check_mapped_types()?;
 
-        // Escape single quotes in the value
-        let escaped_value = value.replace("'", "''");
+    // The columns are the same for all rows
+    let columns = insert_columns(first_row);
+    let column_types = first_row.get_columns();
 
-        columns_str.push_str(&format!("{}, ", column_name));
-        values_str.push_str(&format!("'{}', ", escaped_value)); // Surround values with single quotes
-    }
+    let mut rows = Vec::new();
+    for table_row in table_rows {
+        let values: HashMap<String, String> = table_row
+            .get_column_fields()
+            .into_iter()
+            .zip(table_row.get_column_values())
+            .collect();
 
-    // Sanitize table name from unwanted quotations or backslashes
-    let table_name = table_row.get_name().replace("\"", "").replace("\\", "");
+        let mut row = Vec::new();
+        for column_name in &columns {
+            let value = values.get(column_name).cloned().unwrap_or_default();
+            if is_blob_column(&column_types, column_name) {
+                // BLOB values are hex encoded, insert them as a BLOB literal
+                row.push(format!("X'{}'", value));
+            } else {
+                check_integer_range(&column_types, column_name, &value)?;
 
-    // Remove the trailing comma and space
-    if !columns_str.is_empty() {
-        columns_str.pop();
-        columns_str.pop();
-    }
-    if !values_str.is_empty() {
-        values_str.pop();
-        values_str.pop();
+                // Escape single quotes in the value
+                row.push(format!("'{}'", value.replace("'", "''")));
+            }
+        }
+        rows.push(format!("({})", row.join(", ")));
     }
 
-    let sql = if first_statement {
-        format!(
-            "INSERT INTO {} ({}) VALUES ({})",
-            table_name, columns_str, values_str
-        )
-    } else {
-        format!("({})", values_str)
+    // Sanitize table name from unwanted quotations or backslashes
+    let table_name = first_row
+        .get_prefixed_name(table_prefix)
+        .replace("\"", "")
+        .replace("\\", "");
+
+    let insert_str = match conflict {
+        Some(ConflictResolution::Ignore) => "INSERT OR IGNORE",
+        Some(ConflictResolution::Replace) => "INSERT OR REPLACE",
+        None => "INSERT",
+    };
+    let sql = match columns.is_empty() {
+        // Every column is filled by the database
+        true => format!("{} INTO {} DEFAULT VALUES", insert_str, table_name),
+        false => format!(
+            "{} INTO {} ({}) VALUES {}",
+            insert_str,
+            table_name,
+            quote_identifiers(&columns),
+            rows.join(", ")
+        ),
     };
 
     debug!("{}", sql);
@@ -29,24 +29,47 @@
 
 use std::path::Path;
 
-use rusqlite::{Connection, Error};
+use rusqlite::Connection;
 
+use crate::error::Error;
+
+pub mod alter;
+pub mod connection;
+pub mod count;
 pub mod delete;
+pub mod dry_run;
 pub mod error;
+pub mod exists;
+pub mod init;
 pub mod insert;
+pub mod raw;
+pub mod read_only;
+pub mod schema;
 pub mod select;
+pub mod transaction;
 pub mod update;
 mod util;
 
-pub use delete::delete;
+pub use alter::alter_table;
+pub use connection::Executor;
+pub use count::count;
+pub use delete::{delete, delete_by_pks};
+pub use dry_run::{CapturedStatement, DryRunConnection};
 pub use error::SqliteError;
-pub use insert::insert;
-pub use select::select;
-pub use update::update;
+pub use exists::exists;
+pub use init::init;
+pub use insert::{insert, insert_default, insert_from, InsertQueryBuilder};
+pub use raw::{execute, query_raw, query_raw_named, raw, RawStatement};
+pub use read_only::ReadOnlyConnection;
+pub use schema::{diff_schemas, list_tables, table_columns};
+pub use select::{select, select_all, JoinBuilder};
+pub use transaction::{transaction, TransactionBuilder};
+pub use update::{update, update_by_pk};
 
 /// Open a database connection.
 ///
 /// This function opens a connection to a SQLite database located at the specified path.
+/// The database file is created if it does not exist.
 ///
 /// # Arguments
 ///
@@ -58,13 +81,14 @@ pub use update::update;
 ///
 /// # Errors
 ///
-/// This function can return an error if:
+/// This function returns `Error::ConnectionFailed` if:
 ///
-/// * The specified database path does not exist.
+/// * The directory of the specified database path does not exist.
 /// * There are permission issues when trying to access the database.
 /// * The database is corrupted or not a valid SQLite database.
 pub fn open(db_path: &Path) -> Result<Connection, Error> {
-    let conn = Connection::open(db_path)?;
+    let conn = Connection::open(db_path).map_err(|e| Error::ConnectionFailed(Box::new(e)))?;
+    enable_foreign_keys(&conn)?;
 
     Ok(conn)
 }
@@ -83,13 +107,41 @@ pub fn open(db_path: &Path) -> Result<Connection, Error> {
 ///
 /// * There was an issue creating the in-memory database connection.
 pub fn open_in_memory() -> Result<Connection, Error> {
-    let conn = Connection::open_in_memory()?;
+    let conn = Connection::open_in_memory().map_err(|e| Error::ConnectionFailed(Box::new(e)))?;
+    enable_foreign_keys(&conn)?;
 
     Ok(conn)
 }
 
+/// Enables the enforcement of foreign key constraints, SQLite leaves it off by default.
+fn enable_foreign_keys(conn: &Connection) -> Result<(), Error> {
+    conn.execute_batch("PRAGMA foreign_keys = ON;")
+        .map_err(|e| Error::ConnectionFailed(Box::new(e)))
+}
+
+/// Checks that the JSON1 functions like `json_extract` and `json_each` are available.
+///
+/// The bundled SQLite always includes them, a system SQLite may have been built without.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing `()` if the functions are available, or `Error::Unsupported`
+/// if they are not.
+pub fn ensure_json1(conn: &dyn Executor) -> Result<(), Error> {
+    conn.query("SELECT json_array_length('[]')", &[], &mut |_| Ok(()))
+        .map_err(|_| Error::Unsupported("the SQLite JSON1 functions are not available".to_string()))
+}
+
 /// Executes a raw SQL query and returns a vector of table rows.
 ///
+/// Every statement of `sql` is executed, to only accept a single statement use `raw`.
+/// Unlike `raw` and `execute` this is not guarded against multiple statements, as it
+/// is meant for SQL scripts, e.g. to set up a schema.
+///
 /// # Arguments
 ///
 /// * `sql` - The SQL query to execute.
@@ -97,11 +149,8 @@ pub fn open_in_memory() -> Result<Connection, Error> {
 ///
 /// # Returns
 ///
-/// A `Result` containing a vector of table rows if successful,
-/// or a `rusqlite::Error` if an error occurs during the execution.
-pub fn raw_execute(conn: &Connection, sql: &str) -> Result<(), SqliteError> {
-    match conn.execute_batch(sql) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(e.into()),
-    }
+/// A `Result` containing `()` if successful,
+/// or an `Error` if an error occurs during the execution.
+pub fn raw_execute(conn: &dyn Executor, sql: &str) -> Result<(), Error> {
+    conn.execute_batch(sql)
 }
@@ -0,0 +1,250 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+
+use rusqlite::types::Value as SqliteValue;
+
+use crate::condition::Value;
+use crate::error::Error;
+use crate::table::Table;
+use crate::util::statement_count;
+
+use super::connection::{query_map, Executor};
+use super::select::row_to_table;
+
+/// Executes a raw SQL statement with positional parameters and returns the number of affected rows.
+///
+/// Unlike `query_raw`, no rows are returned, which makes this suitable for statements like
+/// `UPDATE`, `DELETE` or DDL. Parameters are bound to the `?` placeholders in order.
+/// SQL containing more than one statement is rejected, see `RawStatement::allow_multiple`.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `sql` - The SQL statement to execute.
+/// * `params` - The values to bind to the placeholders.
+///
+/// # Returns
+///
+/// A `Result` containing the number of affected rows if successful,
+/// or an `Error` if an error occurs during the execution.
+pub fn execute(conn: &dyn Executor, sql: &str, params: Vec<Value>) -> Result<usize, Error> {
+    raw(sql).params(params).execute(conn)
+}
+
+/// Creates a raw SQL statement to execute.
+///
+/// # Arguments
+///
+/// * `sql` - The SQL statement to execute.
+///
+/// # Example
+///
+/// ```rust
+/// use njord::sqlite;
+///
+/// let conn = sqlite::open_in_memory().unwrap();
+///
+/// // more than one statement is only executed with `allow_multiple`
+/// assert!(sqlite::raw("CREATE TABLE a (id INTEGER); CREATE TABLE b (id INTEGER);")
+///     .execute(&conn)
+///     .is_err());
+/// ```
+pub fn raw<'a>(sql: &str) -> RawStatement<'a> {
+    RawStatement {
+        sql: sql.to_string(),
+        params: Vec::new(),
+        allow_multiple: false,
+    }
+}
+
+/// A raw SQL statement, created with `raw`.
+///
+/// Passing several statements by accident would run all of them, so SQL containing more
+/// than one top-level statement is rejected unless `allow_multiple` is set.
+pub struct RawStatement<'a> {
+    sql: String,
+    params: Vec<Value<'a>>,
+    allow_multiple: bool,
+}
+
+impl<'a> RawStatement<'a> {
+    /// Sets the values bound to the `?` placeholders in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The values to bind to the placeholders.
+    pub fn params(mut self, params: Vec<Value<'a>>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Allows the SQL to contain more than one statement, e.g. a schema script. All
+    /// statements are executed in order, they can't have parameters.
+    pub fn allow_multiple(mut self) -> Self {
+        self.allow_multiple = true;
+        self
+    }
+
+    /// Executes the statement.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the number of affected rows, for multiple statements those
+    /// of the last one, or an `Error` if the SQL contains more than one statement without
+    /// `allow_multiple` or an error occurs during the execution.
+    pub fn execute(self, conn: &dyn Executor) -> Result<usize, Error> {
+        let statements = statement_count(&self.sql);
+        if statements > 1 {
+            if !self.allow_multiple {
+                return Err(Error::InvalidQuery(format!(
+                    "raw SQL contains {} statements, use `allow_multiple` to execute all of them",
+                    statements
+                )));
+            }
+            if !self.params.is_empty() {
+                return Err(Error::InvalidQuery(
+                    "parameters can't be bound to multiple statements".to_string(),
+                ));
+            }
+
+            conn.execute_batch(&self.sql)?;
+            return Ok(conn.changes() as usize);
+        }
+
+        let params = self
+            .params
+            .iter()
+            .map(to_sqlite_value)
+            .collect::<Result<Vec<SqliteValue>, Error>>()?;
+
+        conn.execute(&self.sql, &params)
+    }
+}
+
+/// Executes a raw SQL query with positional parameters and returns a vector of table rows.
+///
+/// Parameters are bound to the `?` placeholders of the query in order. Numeric literals
+/// are bound as numbers, other literals as text.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `sql` - The SQL query to execute.
+/// * `params` - The values to bind to the placeholders.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of table rows if successful,
+/// or an `Error` if an error occurs during the execution.
+pub fn query_raw<T: Table + Default>(
+    conn: &dyn Executor,
+    sql: &str,
+    params: Vec<Value>,
+) -> Result<Vec<T>, Error> {
+    let params = params
+        .iter()
+        .map(to_sqlite_value)
+        .collect::<Result<Vec<SqliteValue>, Error>>()?;
+
+    query_map(conn, sql, &params, |row| Ok(row_to_table::<T>(row)?))
+}
+
+/// Executes a raw SQL query with named parameters and returns a vector of table rows.
+///
+/// Parameters are bound to the `:name` placeholders of the query by name. The keys of
+/// `params` may be given with or without the leading `:`.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `sql` - The SQL query to execute.
+/// * `params` - The values to bind to the placeholders, keyed by name.
+///
+/// # Returns
+///
+/// A `Result` containing a vector of table rows if successful, or an `Error` if
+/// a placeholder has no corresponding entry or an error occurs during the execution.
+pub fn query_raw_named<T: Table + Default>(
+    conn: &dyn Executor,
+    sql: &str,
+    params: HashMap<String, Value>,
+) -> Result<Vec<T>, Error> {
+    let params = params
+        .iter()
+        .map(|(name, value)| Ok((name.clone(), to_sqlite_value(value)?)))
+        .collect::<Result<Vec<(String, SqliteValue)>, Error>>()?;
+
+    let mut rows = Vec::new();
+    conn.query_named(sql, &params, &mut |row| {
+        rows.push(row_to_table::<T>(row)?);
+        Ok(())
+    })?;
+
+    Ok(rows)
+}
+
+/// Converts a `Value` into a SQLite value that can be bound as a parameter.
+///
+/// A literal is bound as a number only if it is exactly the text of the number, e.g.
+/// `42` or `1.5`, so text like `0123` or `1e3` keeps its leading zeros and notation.
+/// SQLite converts the value to the affinity of the column it is compared with or
+/// stored in.
+///
+/// # Arguments
+///
+/// * `value` - The value to convert.
+///
+/// # Returns
+///
+/// A `Result` containing the SQLite value, or an `Error` if the value
+/// cannot be bound as a single parameter.
+pub(super) fn to_sqlite_value(value: &Value) -> Result<SqliteValue, Error> {
+    let value = match value.to_param()? {
+        Some(literal) => {
+            match (literal.parse::<i64>(), literal.parse::<f64>()) {
+                (Ok(integer), _) if integer.to_string() == literal => {
+                    SqliteValue::Integer(integer)
+                }
+                (_, Ok(real)) if real.is_finite() && real.to_string() == literal => {
+                    SqliteValue::Real(real)
+                }
+                _ => SqliteValue::Text(literal),
+            }
+        }
+        None => SqliteValue::Null,
+    };
+
+    Ok(value)
+}
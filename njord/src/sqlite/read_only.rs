@@ -0,0 +1,146 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::path::Path;
+
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags, Row};
+
+use crate::config::ConnectionConfig;
+use crate::error::Error;
+
+use super::connection::{Database, Executor};
+
+/// A connection that rejects every statement that writes to the database.
+///
+/// The database file is opened with SQLite's read-only flag, and every statement is
+/// checked before it is executed, so inserts, updates, deletes and DDL statements fail
+/// with `Error::ReadOnly`, including those of temporary tables. Selects work as on any
+/// other connection. The wrapper is an `Executor`, so it is passed to the query functions
+/// like a connection, e.g. to connect to a replica without accidentally writing to it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let conn = ReadOnlyConnection::open(path)?;
+/// let users = sqlite::select_all::<User>().from(User::default()).build(&conn)?;
+/// ```
+pub struct ReadOnlyConnection {
+    conn: Connection,
+    config: ConnectionConfig,
+}
+
+impl ReadOnlyConnection {
+    /// Opens a database file in read-only mode.
+    ///
+    /// SQLite opens the file with its read-only flag, so the connection can't be made
+    /// writable again.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - A reference to the path where the SQLite database is located.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the `ReadOnlyConnection`, or `Error::ConnectionFailed` if
+    /// the database does not exist or could not be opened.
+    pub fn open(db_path: &Path) -> Result<Self, Error> {
+        let conn = Connection::open_with_flags(
+            db_path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY
+                | OpenFlags::SQLITE_OPEN_URI
+                | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .map_err(|e| Error::ConnectionFailed(Box::new(e)))?;
+
+        Ok(ReadOnlyConnection {
+            conn,
+            config: ConnectionConfig::default(),
+        })
+    }
+
+    /// Sets the settings of the connection, e.g. its table prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The settings of the connection.
+    pub fn with_config(mut self, config: ConnectionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the database connection.
+    fn database(&self) -> Database<'_> {
+        Database::new(&self.conn, &self.config).read_only()
+    }
+}
+
+impl Executor for ReadOnlyConnection {
+    fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<usize, Error> {
+        self.database().execute(sql, params)
+    }
+
+    fn execute_batch(&self, sql: &str) -> Result<(), Error> {
+        self.database().execute_batch(sql)
+    }
+
+    fn query(
+        &self,
+        sql: &str,
+        params: &[Value],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.database().query(sql, params, f)
+    }
+
+    fn query_named(
+        &self,
+        sql: &str,
+        params: &[(String, Value)],
+        f: &mut dyn FnMut(&Row<'_>) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        self.database().query_named(sql, params, f)
+    }
+
+    fn last_insert_rowid(&self) -> i64 {
+        self.conn.last_insert_rowid()
+    }
+
+    fn changes(&self) -> u64 {
+        self.conn.changes()
+    }
+
+    fn is_autocommit(&self) -> bool {
+        self.conn.is_autocommit()
+    }
+}
@@ -0,0 +1,166 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{dialect::Dialect, error::Error, schema::ColumnDef};
+
+use super::alter::alter_table;
+use super::connection::{query_map, query_row, Executor};
+
+use log::debug;
+use rusqlite::types::Value;
+
+/// Lists the tables of a SQLite database.
+///
+/// The internal `sqlite_` tables, like `sqlite_sequence`, are left out.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+///
+/// # Returns
+///
+/// A `Result` containing the table names in alphabetical order, or an `Error` if the
+/// schema could not be read.
+pub fn list_tables(conn: &dyn Executor) -> Result<Vec<String>, Error> {
+    let query = "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name";
+    debug!("{}", query);
+
+    query_map(conn, query, &[], |row| Ok(row.get(0)?))
+}
+
+/// Lists the columns of a table in a SQLite database, backed by `PRAGMA table_info`.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+/// * `name` - The name of the table.
+///
+/// # Returns
+///
+/// A `Result` containing the columns in their declared order, or an `Error` if the
+/// schema could not be read. A table that does not exist has no columns.
+pub fn table_columns(conn: &dyn Executor, name: &str) -> Result<Vec<ColumnDef>, Error> {
+    let query =
+        "SELECT name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1) ORDER BY cid";
+    debug!("{}", query);
+
+    query_map(conn, query, &[Value::Text(name.to_string())], |row| {
+        Ok(ColumnDef {
+            name: row.get(0)?,
+            data_type: row.get(1)?,
+            nullable: !row.get::<_, bool>(2)?,
+            default_value: row.get(3)?,
+            primary_key: row.get::<_, i64>(4)? > 0,
+        })
+    })
+}
+
+/// Builds the statements that change the schema of a database to match another one.
+///
+/// Tables missing in `to` are created with the `CREATE TABLE` statement of `from`, while
+/// tables missing in `from` are dropped. For the other tables, missing columns are added,
+/// extra columns are dropped and columns of a different type, nullability, default or
+/// primary key are changed, see `alter_table` for the statements. Types are compared
+/// case insensitively.
+///
+/// # Arguments
+///
+/// * `from` - A reference to the connection of the database with the wanted schema.
+/// * `to` - A reference to the connection of the database to change.
+///
+/// # Returns
+///
+/// A `Result` containing the statements in table order, empty if the schemas match, or
+/// an `Error` if a schema could not be read.
+pub fn diff_schemas(from: &dyn Executor, to: &dyn Executor) -> Result<Vec<String>, Error> {
+    let from_tables = list_tables(from)?;
+    let to_tables = list_tables(to)?;
+
+    let mut statements = Vec::new();
+    for table in &from_tables {
+        if !to_tables.contains(table) {
+            statements.push(format!("{};", create_table_statement(from, table)?));
+            continue;
+        }
+
+        let from_columns = table_columns(from, table)?;
+        let to_columns = table_columns(to, table)?;
+
+        let mut builder = alter_table(to, table);
+        for column in &from_columns {
+            match to_columns.iter().find(|c| c.name == column.name) {
+                None => builder = builder.add_column(column.clone()),
+                Some(existing) if !same_definition(existing, column) => {
+                    builder = builder.change_column(column.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        for column in &to_columns {
+            if !from_columns.iter().any(|c| c.name == column.name) {
+                builder = builder.drop_column(&column.name);
+            }
+        }
+
+        if !builder.operations().is_empty() {
+            statements.extend(builder.build_statements()?);
+        }
+    }
+
+    for table in &to_tables {
+        if !from_tables.contains(table) {
+            statements.push(format!(
+                "DROP TABLE {};",
+                Dialect::Sqlite.quote_identifier(table)
+            ));
+        }
+    }
+
+    Ok(statements)
+}
+
+/// Returns the `CREATE TABLE` statement a table was created with.
+fn create_table_statement(conn: &dyn Executor, name: &str) -> Result<String, Error> {
+    let query = "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?1";
+    debug!("{}", query);
+
+    let statement = query_row(conn, query, &[Value::Text(name.to_string())], |row| {
+        Ok(row.get(0)?)
+    })?;
+
+    Ok(statement.ok_or(rusqlite::Error::QueryReturnedNoRows)?)
+}
+
+/// Checks if two columns of the same name have the same definition.
+fn same_definition(a: &ColumnDef, b: &ColumnDef) -> bool {
+    a.data_type.eq_ignore_ascii_case(&b.data_type)
+        && a.nullable == b.nullable
+        && a.default_value == b.default_value
+        && a.primary_key == b.primary_key
+}
@@ -29,20 +29,31 @@
 
 use crate::{
     column::Column,
-    condition::Condition,
-    query::QueryBuilder,
+    condition::{Condition, ParamStyle, Params},
+    dialect::{Dialect, IdentifierCase},
+    query::{merge_order_by, QueryBuilder, SelectStatement},
+    sqlite::connection::{query_map, Executor},
+    sqlite::raw::to_sqlite_value,
     sqlite::util::{
-        generate_group_by_str, generate_having_str, generate_limit_str, generate_offset_str,
-        generate_order_by_str, generate_where_condition_str,
+        generate_group_by_str, generate_limit_str, generate_offset_str, generate_order_by_str,
+        quote_identifiers_with, to_condition_value,
     },
 };
-use rusqlite::{Connection, Result};
+use crate::error::Error;
+use rusqlite::Row;
 use std::{collections::HashMap, sync::Arc};
 
-use rusqlite::types::Value;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use rusqlite::types::{FromSql, Value, ValueRef};
 
 use crate::table::Table;
-use crate::util::{Join, JoinType};
+use crate::util::{
+    match_column_field, table_reference, ungrouped_column, Join, JoinConstraint, JoinType,
+    LockMode, Page,
+};
+
+/// The maximum number of rows pre-allocated based on the LIMIT of a query.
+const MAX_LIMIT_CAPACITY: usize = 1024;
 
 /// Constructs a new SELECT query builder.
 ///
@@ -57,21 +68,53 @@ pub fn select<'a, T: Table + Default>(columns: Vec<Column<'a>>) -> SelectQueryBu
     SelectQueryBuilder::new(columns)
 }
 
+/// Constructs a new SELECT query builder selecting every column of the table.
+///
+/// The columns are listed in field declaration order, the same order used by
+/// `init` and `insert`.
+///
+/// # Returns
+///
+/// A `SelectQueryBuilder` instance.
+pub fn select_all<'a, T: Table + Default>() -> SelectQueryBuilder<'a, T> {
+    let columns = T::default()
+        .get_column_fields()
+        .into_iter()
+        .map(Column::Text)
+        .collect();
+
+    SelectQueryBuilder::new(columns)
+}
+
 /// A builder for constructing SELECT queries.
 #[derive(Clone)]
 pub struct SelectQueryBuilder<'a, T: Table + Default> {
     table: Option<T>,
+    table_alias: Option<String>,
     columns: Vec<Column<'a>>,
     where_condition: Option<Condition<'a>>,
     distinct: bool,
+    distinct_by: Option<Vec<String>>,
     group_by: Option<Vec<String>>,
     order_by: Option<HashMap<Vec<String>, String>>,
+    order_by_expressions: Vec<(Condition<'a>, String)>,
     limit: Option<usize>,
     offset: Option<usize>,
     having_condition: Option<Condition<'a>>,
     except_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     union_clauses: Option<Vec<SelectQueryBuilder<'a, T>>>,
     joins: Option<Vec<Join<'a>>>,
+    lock_mode: Option<LockMode>,
+    subquery_error: Option<String>,
+    capacity: Option<usize>,
+    strict_types: bool,
+    ties_limit: Option<usize>,
+    dialect: Dialect,
+    with_deleted: bool,
+    strict_group_by: bool,
+    keyset: Option<Vec<(String, crate::condition::Value<'a>)>>,
+    identifier_case: IdentifierCase,
+    table_prefix: String,
 }
 
 impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
@@ -83,17 +126,31 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     pub fn new(columns: Vec<Column<'a>>) -> Self {
         SelectQueryBuilder {
             table: None,
+            table_alias: None,
             columns,
             where_condition: None,
             distinct: false,
+            distinct_by: None,
             group_by: None,
             order_by: None,
+            order_by_expressions: Vec::new(),
             limit: None,
             offset: None,
             having_condition: None,
             except_clauses: None,
             union_clauses: None,
             joins: None,
+            lock_mode: None,
+            subquery_error: None,
+            capacity: None,
+            strict_types: false,
+            ties_limit: None,
+            dialect: Dialect::Sqlite,
+            with_deleted: false,
+            strict_group_by: false,
+            keyset: None,
+            identifier_case: IdentifierCase::Preserve,
+            table_prefix: String::new(),
         }
     }
 
@@ -108,11 +165,29 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     }
 
     /// Sets the DISTINCT keyword for the query.
+    ///
+    /// Databases other than SQLite and MariaDB require the ORDER BY columns to be
+    /// selected, `check` reports the unselected ones for the dialect of the query.
     pub fn distinct(mut self) -> Self {
         self.distinct = true;
         self
     }
 
+    /// Keeps only the first row for every distinct combination of the given columns.
+    ///
+    /// Unlike `distinct`, the selected columns may include columns that are not part of
+    /// the deduplication. Since SQLite has no `DISTINCT ON`, the query is wrapped around a
+    /// `ROW_NUMBER() OVER (PARTITION BY ...)` subquery, the first row of every partition is
+    /// determined by the ORDER BY clause of the query, whose columns must be selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The columns to deduplicate on.
+    pub fn distinct_by(mut self, columns: Vec<String>) -> Self {
+        self.distinct_by = Some(columns);
+        self
+    }
+
     /// Sets the table from which to select data.
     ///
     /// # Arguments
@@ -123,13 +198,109 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         self
     }
 
+    /// Sets the table from which to select data, referenced by an alias throughout the
+    /// query, e.g. `a.name` in the columns and conditions of a self-join.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table from which to select data.
+    /// * `alias` - The alias of the table, e.g. `a` for `users AS a`.
+    pub fn from_as(mut self, table: T, alias: &str) -> Self {
+        self.table = Some(table);
+        self.table_alias = Some(alias.to_string());
+        self
+    }
+
     /// Sets the WHERE clause condition.
     ///
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
+        self
+    }
+
+    /// Adds an optional condition to the WHERE clause.
+    ///
+    /// `None` leaves the WHERE clause unchanged, `Some` combines the condition with an
+    /// existing WHERE condition using AND.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The optional condition to be applied in the WHERE clause.
+    pub fn where_opt(mut self, condition: Option<Condition<'a>>) -> Self {
+        if let Some(condition) = condition {
+            self.where_condition = match self.where_condition {
+                Some(existing) => Some(existing.and(condition)),
+                None => Some(condition),
+            };
+        }
+        self
+    }
+
+    /// Adds a raw SQL condition to the WHERE clause, e.g. `length(username) > ?`.
+    ///
+    /// The condition is combined with an existing WHERE condition using AND. Its values
+    /// are bound in textual order together with the values of the other conditions, see
+    /// `to_sql_with_params`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sql` - The SQL of the condition, with a `?` placeholder for each value.
+    /// * `values` - The values of the placeholders.
+    pub fn raw_where(self, sql: &str, values: Vec<crate::condition::Value<'a>>) -> Self {
+        self.where_opt(Some(Condition::raw(sql, values)))
+    }
+
+    /// Adds a `column IN (subquery)` condition to the WHERE clause.
+    ///
+    /// The condition is combined with an existing WHERE condition using AND.
+    /// The subquery must select exactly one column, otherwise `build` returns an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The column to match against the subquery result set.
+    /// * `subquery` - The subquery selecting the values to match.
+    pub fn where_in<U>(mut self, column: &str, subquery: SelectQueryBuilder<'a, U>) -> Self
+    where
+        U: Table + Default + Clone + 'a,
+    {
+        if subquery.columns.len() != 1 {
+            self.subquery_error = Some(format!(
+                "subquery for `{}` must select exactly one column, got {}",
+                column,
+                subquery.columns.len()
+            ));
+        }
+
+        let condition = Condition::In(
+            column.to_string(),
+            vec![crate::condition::Value::Subquery(Box::new(subquery))],
+        );
+
+        self.where_condition = match self.where_condition {
+            Some(existing) => Some(Condition::And(Box::new(existing), Box::new(condition))),
+            None => Some(condition),
+        };
+        self
+    }
+
+    /// Locks the selected rows with `FOR UPDATE`.
+    pub fn for_update(mut self) -> Self {
+        self.lock_mode = Some(LockMode::Wait);
+        self
+    }
+
+    /// Locks the selected rows with `FOR UPDATE NOWAIT`, failing if a row is already locked.
+    pub fn nowait(mut self) -> Self {
+        self.lock_mode = Some(LockMode::NoWait);
+        self
+    }
+
+    /// Locks the selected rows with `FOR UPDATE SKIP LOCKED`, leaving out rows that are already locked.
+    pub fn skip_locked(mut self) -> Self {
+        self.lock_mode = Some(LockMode::SkipLocked);
         self
     }
 
@@ -153,6 +324,29 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         self
     }
 
+    /// Adds an expression to the ORDER BY clause, e.g. for relevance ordering with
+    /// `CASE WHEN name LIKE ? THEN 0 ELSE 1 END`.
+    ///
+    /// The expression is inserted as is with a `?` placeholder for each of its values,
+    /// like `raw_where`. Expressions are ordered by in the order they are added, before
+    /// the columns of `order_by`.
+    ///
+    /// # Arguments
+    ///
+    /// * `expression` - The SQL expression to order by.
+    /// * `values` - The values of the placeholders, in order.
+    /// * `order` - The order direction, `ASC` or `DESC`.
+    pub fn order_by_expr(
+        mut self,
+        expression: &str,
+        values: Vec<crate::condition::Value<'a>>,
+        order: &str,
+    ) -> Self {
+        self.order_by_expressions
+            .push((Condition::raw(expression, values), order.to_string()));
+        self
+    }
+
     /// Sets the LIMIT clause for the query.
     ///
     /// # Arguments
@@ -173,13 +367,67 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         self
     }
 
+    /// Selects the rows after a key for keyset pagination, generating
+    /// `WHERE column > value ORDER BY column`, which replaces the ORDER BY clause.
+    ///
+    /// Unlike `offset`, the database seeks to the key instead of skipping rows, which is
+    /// fast on large tables if the column is indexed. The column should be unique, like
+    /// the primary key, so no rows are skipped between pages. `Value::Null` selects the
+    /// first page. Use `build_page` to get the page token of the next page.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - The keyset column to order and paginate by.
+    /// * `value` - The key of the last row of the previous page, e.g. from
+    ///   `PageToken::to_value`.
+    pub fn after(self, column: &str, value: crate::condition::Value<'a>) -> Self {
+        self.after_keys(vec![(column, value)])
+    }
+
+    /// Selects the rows after a composite key for keyset pagination, e.g. by
+    /// `(created_at, id)`, where the later columns break the ties of the earlier ones.
+    ///
+    /// Generates `WHERE (created_at, id) > (?, ?) ORDER BY created_at, id`, or the
+    /// expanded form of `Condition::gt_tuple` for databases without row value
+    /// comparisons. Works like `after` otherwise, the first page has NULL keys and
+    /// `PageToken::to_values` returns the keys of the next page.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keyset columns with the keys of the last row of the previous page.
+    pub fn after_keys(mut self, keys: Vec<(&str, crate::condition::Value<'a>)>) -> Self {
+        let columns = keys
+            .iter()
+            .map(|(column, _)| column.to_string())
+            .collect::<Vec<String>>();
+        self.order_by = Some(HashMap::from([(columns, "ASC".to_string())]));
+        self.keyset = Some(
+            keys.into_iter()
+                .map(|(column, value)| (column.to_string(), value))
+                .collect(),
+        );
+        self
+    }
+
+    /// Pre-allocates the result vector for the given number of rows.
+    ///
+    /// Without a hint, the LIMIT of the query is used if present, up to 1024 rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The expected number of rows.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
     /// Sets the HAVING clause condition.
     ///
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the HAVING clause.
-    pub fn having(mut self, condition: Condition<'a>) -> Self {
-        self.having_condition = Some(condition);
+    pub fn having(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.having_condition = Some(condition.into());
         self
     }
 
@@ -245,36 +493,263 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Returns
     ///
     /// Returns the modified `SelectQueryBuilder` instance with the new JOIN clause added.
-    pub fn join(
-        mut self,
-        join_type: JoinType,
-        table: Arc<dyn Table>,
-        on_condition: Condition<'a>,
-    ) -> Self {
+    pub fn join(self, join_type: JoinType, table: Arc<dyn Table>, on_condition: Condition<'a>) -> Self {
+        self.push_join(Join::new(join_type, table, on_condition))
+    }
+
+    /// Limits the result to the first `count` rows, including the rows that tie with
+    /// the last row according to the ORDER BY clause.
+    ///
+    /// SQLite has no `WITH TIES`, so building the query fails with `Error::Unsupported`.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of rows to return, not counting ties.
+    pub fn limit_with_ties(mut self, count: usize) -> Self {
+        self.ties_limit = Some(count);
+        self
+    }
+
+    /// Rejects decoding a REAL value into an INTEGER field, or an INTEGER value into a
+    /// REAL field, with an `Error::TypeMismatch` instead of coercing the value.
+    ///
+    /// By default the values are coerced like SQLite does for the column affinity.
+    pub fn strict_types(mut self) -> Self {
+        self.strict_types = true;
+        self
+    }
+
+    /// Renders the query for another SQL dialect instead of SQLite.
+    ///
+    /// The dialect decides the quoting of identifiers, the syntax of dialect specific
+    /// conditions and the placeholders of `to_sql_with_params`. A query rendered for
+    /// another dialect can not be executed on SQLite, building it fails with
+    /// `Error::Unsupported`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect to render the query for.
+    pub fn dialect(mut self, dialect: Dialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Sets how the table and the selected columns are rendered for the dialect, e.g.
+    /// `IdentifierCase::Fold` renders them in uppercase for Oracle and in lowercase for
+    /// PostgreSQL, so the same struct can be used for both.
+    ///
+    /// # Arguments
+    ///
+    /// * `case` - How to render the identifiers.
+    pub fn identifier_case(mut self, case: IdentifierCase) -> Self {
+        self.identifier_case = case;
+        self
+    }
+
+    /// Sets the prefix that is prepended to the name of the table and the joined tables,
+    /// including those of the UNION and EXCEPT queries, e.g. `app_` selects from
+    /// `app_users` instead of `users`.
+    ///
+    /// The prefix is the table prefix of the connection the query is built with.
+    pub(crate) fn with_table_prefix(mut self, prefix: &str) -> Self {
+        self.table_prefix = prefix.to_string();
+        for clauses in [&mut self.except_clauses, &mut self.union_clauses] {
+            if let Some(queries) = clauses.take() {
+                *clauses = Some(
+                    queries
+                        .into_iter()
+                        .map(|query| query.with_table_prefix(prefix))
+                        .collect(),
+                );
+            }
+        }
+        self
+    }
+
+    /// Returns the dialect the query is rendered for.
+    pub fn get_dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// Rejects selected columns that are neither aggregated nor part of the GROUP BY
+    /// clause with an `Error::InvalidGroupBy` when building the query.
+    ///
+    /// SQLite accepts these columns and returns the value of an arbitrary row of the group,
+    /// so the check is off by default. It catches queries that fail on stricter databases.
+    pub fn strict_group_by(mut self) -> Self {
+        self.strict_group_by = true;
+        self
+    }
+
+    /// Includes the soft deleted rows of a table with the `#[soft_delete]` attribute,
+    /// which are left out by default.
+    pub fn with_deleted(mut self) -> Self {
+        self.with_deleted = true;
+        self
+    }
+
+    /// Returns the WHERE condition, extended with a condition leaving out the soft
+    /// deleted rows unless `with_deleted` is set.
+    fn effective_where_condition(&self) -> Option<Condition<'a>> {
+        // The first page has NULL keys
+        let where_condition = match &self.keyset {
+            Some(keys)
+                if !keys
+                    .iter()
+                    .all(|(_, value)| matches!(value, crate::condition::Value::Null)) =>
+            {
+                let after = match keys.as_slice() {
+                    [(column, value)] => Condition::Gt(column.clone(), value.clone()),
+                    _ => Condition::GtTuple(
+                        keys.iter().map(|(column, _)| column.clone()).collect(),
+                        keys.iter().map(|(_, value)| value.clone()).collect(),
+                    ),
+                };
+                Some(match &self.where_condition {
+                    Some(condition) => condition.clone().and(after),
+                    None => after,
+                })
+            }
+            _ => self.where_condition.clone(),
+        };
+
+        let soft_delete_column = match &self.table {
+            Some(table) if !self.with_deleted => table.get_soft_delete_column(),
+            _ => None,
+        };
+
+        let Some(column) = soft_delete_column else {
+            return where_condition;
+        };
+
+        let column = match (&self.table, &self.table_alias, &self.joins) {
+            (_, Some(alias), Some(_)) => format!("{}.{}", alias, column),
+            (Some(table), None, Some(_)) => {
+                format!("{}{}.{}", self.table_prefix, table.get_name(), column)
+            }
+            _ => column.to_string(),
+        };
+        let not_deleted = Condition::NullSafeEq(column, crate::condition::Value::Null);
+
+        Some(match where_condition {
+            Some(condition) => condition.and(not_deleted),
+            None => not_deleted,
+        })
+    }
+
+    /// Starts a JOIN clause, the join condition is given with `on` or `using`.
+    ///
+    /// # Arguments
+    ///
+    /// * `join_type` - The type of join to perform (e.g., INNER, LEFT, RIGHT, FULL).
+    /// * `table` - The table to join with the current table.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `JoinBuilder` completing the JOIN clause.
+    pub fn join_table(self, join_type: JoinType, table: Arc<dyn Table>) -> JoinBuilder<'a, T> {
+        JoinBuilder {
+            query: self,
+            join_type,
+            table,
+            alias: None,
+        }
+    }
+
+    /// Adds a complete JOIN clause to the query.
+    fn push_join(mut self, join: Join<'a>) -> Self {
         match self.joins {
-            Some(ref mut joins) => joins.push(Join::new(join_type, table, on_condition)),
-            None => self.joins = Some(vec![Join::new(join_type, table, on_condition)]),
+            Some(ref mut joins) => joins.push(join),
+            None => self.joins = Some(vec![join]),
         }
         self
     }
 
     /// Builds the query string, this function should be used internally.
     pub fn build_query(&self) -> String {
-        let columns_str = self
-            .columns
-            .iter()
-            .map(|c| c.build())
-            .collect::<Vec<String>>()
-            .join(", ");
+        self.render(None)
+    }
+
+    /// Builds the query string with placeholders instead of the values of the
+    /// WHERE, HAVING and JOIN conditions.
+    ///
+    /// # Returns
+    ///
+    /// The query string in the dialect of the query together with the values to bind,
+    /// in the order of their placeholders.
+    pub fn to_sql_with_params(&self) -> (String, Vec<crate::condition::Value<'a>>) {
+        let mut params = Params::new(ParamStyle::Positional);
+        let query = self.render(Some(&mut params));
+        (query, params.into_values())
+    }
+
+    /// Builds the query string with named placeholders `:p0`, `:p1`, ... instead of the
+    /// values of the WHERE, HAVING and JOIN conditions, which are supported by SQLite
+    /// and Oracle.
+    ///
+    /// # Returns
+    ///
+    /// The query string together with the values to bind by placeholder name, without
+    /// the leading colon.
+    pub fn to_sql_with_named_params(
+        &self,
+    ) -> (String, HashMap<String, crate::condition::Value<'a>>) {
+        let mut params = Params::new(ParamStyle::Named);
+        let query = self.render(Some(&mut params));
+        (query, params.into_named())
+    }
+
+    /// Renders a condition, with placeholders if `params` is given.
+    fn render_condition(
+        &self,
+        condition: &Condition<'a>,
+        params: &mut Option<&mut Params<'a>>,
+    ) -> String {
+        match params {
+            Some(params) => condition.build_with_params(self.dialect, params),
+            None => condition.build_with(self.dialect),
+        }
+    }
+
+    /// Renders the query, with placeholders if `params` is given.
+    fn render(&self, mut params: Option<&mut Params<'a>>) -> String {
+        let dialect = self.dialect;
+        let identifier_case = self.identifier_case;
+        // The values of the columns are bound first, they come first in the query
+        let mut columns = Vec::new();
+        for c in &self.expanded_columns() {
+            columns.push(match (c, &mut params) {
+                (Column::Text(column), _) => dialect.normalize_identifier(column, identifier_case),
+                (Column::SubQuery(sub_query, alias), Some(params)) => {
+                    format!("({}) AS {}", sub_query.to_sql_with_params(params), alias)
+                }
+                (Column::Expr(expression, alias), Some(params)) => format!(
+                    "{} AS {}",
+                    expression.build_with_params(dialect, params),
+                    alias
+                ),
+                (Column::Expr(expression, alias), None) => {
+                    format!("{} AS {}", expression.build_with(dialect), alias)
+                }
+                _ => c.build(),
+            });
+        }
+        let columns_str = columns.join(", ");
 
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(&self.table_prefix))
+            .map(|name| match identifier_case {
+                IdentifierCase::Preserve => name,
+                _ => dialect.normalize_identifier(&name, identifier_case),
+            })
+            .map(|name| table_reference(&name, self.table_alias.as_deref()))
             .unwrap_or("".to_string());
 
-        // Generate JOIN clauses, if any
-        let join_clauses: Vec<String> = match &self.joins {
+        // Generate JOIN clauses, if any. They bind their parameters when they are
+        // rendered, so they are rendered in the order of the query text.
+        let render_joins = |params: &mut Option<&mut Params<'a>>| match &self.joins {
             Some(joins) => joins
                 .iter()
                 .map(|join| {
@@ -284,51 +759,109 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
                         JoinType::Right => "RIGHT JOIN",
                         JoinType::Full => "FULL OUTER JOIN",
                     };
+                    let constraint_str = match &join.constraint {
+                        JoinConstraint::On(condition) => {
+                            format!("ON {}", self.render_condition(condition, params))
+                        }
+                        JoinConstraint::Using(columns) => {
+                            format!("USING ({})", quote_identifiers_with(dialect, columns))
+                        }
+                    };
                     format!(
-                        "{} {} ON {}",
+                        "{} {} {}",
                         join_type_str,
-                        join.table.get_name(),
-                        generate_where_condition_str(Some(join.on_condition.clone()))
-                            .replace("WHERE", "")
+                        table_reference(
+                            &join.table.get_prefixed_name(&self.table_prefix),
+                            join.alias.as_deref()
+                        ),
+                        constraint_str
                     )
                 })
-                .collect(),
-            None => Vec::new(),
+                .collect::<Vec<String>>()
+                .join(" "),
+            None => String::new(),
         };
 
-        let distinct_str = if self.distinct { "DISTINCT " } else { "" };
-        let where_condition_str = generate_where_condition_str(self.where_condition.clone());
+        let where_condition = self.effective_where_condition();
         let group_by_str = generate_group_by_str(&self.group_by);
         let order_by_str = generate_order_by_str(&self.order_by);
-        let limit_str = generate_limit_str(self.limit);
-        let offset_str = generate_offset_str(self.offset);
-        let having_str =
-            generate_having_str(self.group_by.is_some(), self.having_condition.as_ref());
-
-        // Create the JOIN clause or an empty string
-        let join_clause = if !join_clauses.is_empty() {
-            join_clauses.join(" ")
-        } else {
-            String::new()
-        };
 
-        let mut query = format!(
-            "SELECT {}{} FROM {} {} {} {} {} {} {}",
-            distinct_str,
-            columns_str,
-            table_name,
-            join_clause,
-            where_condition_str,
-            group_by_str,
-            having_str,
-            order_by_str,
-            format!("{} {}", limit_str, offset_str),
-        );
+        let mut query = match &self.distinct_by {
+            Some(partition_columns) => {
+                // The window is ordered before the WHERE condition and the outer query after
+                let render_order_by = |params: &mut Option<&mut Params<'a>>| {
+                    let expressions: Vec<String> = self
+                        .order_by_expressions
+                        .iter()
+                        .map(|(expression, order)| {
+                            format!("{} {}", self.render_condition(expression, params), order)
+                        })
+                        .collect();
+                    merge_order_by(&order_by_str, &expressions)
+                };
+                let window_order_by_str = render_order_by(&mut params);
+                let join_clause = render_joins(&mut params);
+                let where_condition_str = match &where_condition {
+                    Some(condition) => {
+                        format!("WHERE {}", self.render_condition(condition, &mut params))
+                    }
+                    None => String::new(),
+                };
+                let having_str = match &self.having_condition {
+                    Some(condition) if self.group_by.is_some() => {
+                        format!("HAVING {}", self.render_condition(condition, &mut params))
+                    }
+                    _ => String::new(),
+                };
+                let outer_order_by_str = render_order_by(&mut params);
+
+                // Only the selected columns are returned, not the row number
+                let outer_columns_str = self
+                    .columns
+                    .iter()
+                    .map(|c| {
+                        dialect.normalize_identifier(&output_column_name(&c.build()), identifier_case)
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                format!(
+                    "SELECT {} FROM (SELECT {}, ROW_NUMBER() OVER (PARTITION BY {} {}) AS njord_row_number \
+                     FROM {} {} {} {} {}) WHERE njord_row_number = 1 {} {} {}",
+                    outer_columns_str,
+                    columns_str,
+                    quote_identifiers_with(dialect, partition_columns),
+                    window_order_by_str,
+                    table_name,
+                    join_clause,
+                    where_condition_str,
+                    group_by_str,
+                    having_str,
+                    outer_order_by_str,
+                    generate_limit_str(self.limit),
+                    generate_offset_str(self.offset),
+                )
+            }
+            None => SelectStatement {
+                distinct: self.distinct,
+                columns: columns_str,
+                from: table_name,
+                joins: render_joins(&mut params),
+                where_condition: where_condition.as_ref(),
+                group_by: group_by_str,
+                having_condition: self.having_condition.as_ref(),
+                order_by: order_by_str,
+                order_by_expressions: &self.order_by_expressions,
+                limit: self.limit,
+                offset: self.offset,
+            }
+            .render(dialect, params.as_deref_mut()),
+        };
 
         // Handle EXCEPT clauses
         if let Some(except_clauses) = &self.except_clauses {
             for except_query in except_clauses {
-                let except_sql = except_query.build_query();
+                let except_sql = except_query.render(params.as_deref_mut());
                 query = format!("{} EXCEPT {}", query, except_sql);
             }
         }
@@ -336,7 +869,7 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         // Handle UNION clauses
         if let Some(union_clauses) = &self.union_clauses {
             for union_query in union_clauses {
-                let union_sql = union_query.build_query();
+                let union_sql = union_query.render(params.as_deref_mut());
                 query = format!("{} UNION {}", query, union_sql);
             }
         }
@@ -344,6 +877,282 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
         query
     }
 
+    /// Checks that the query can be executed on SQLite.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        if self.dialect != Dialect::Sqlite {
+            return Err(Error::Unsupported(format!(
+                "the query is rendered for {:?} and can not be executed on SQLite",
+                self.dialect
+            )));
+        }
+
+        if let Some(lock_mode) = &self.lock_mode {
+            return Err(Error::Unsupported(format!(
+                "SQLite does not support row locking with `{}`",
+                lock_mode.to_sql()
+            )));
+        }
+
+        if self.ties_limit.is_some() {
+            return Err(Error::Unsupported(
+                "SQLite does not support `WITH TIES`, use `limit` instead".to_string(),
+            ));
+        }
+
+        self.check()
+    }
+
+    /// Checks that the values of the conditions, expressions and subqueries of the query can
+    /// be bound to their placeholders, see `Condition::check`.
+    fn check_conditions(&self) -> Result<(), Error> {
+        let joins = self
+            .joins
+            .iter()
+            .flatten()
+            .filter_map(|join| match &join.constraint {
+                JoinConstraint::On(condition) => Some(condition),
+                JoinConstraint::Using(_) => None,
+            });
+        let expressions = self
+            .order_by_expressions
+            .iter()
+            .map(|(expression, _)| expression);
+        self.where_condition
+            .iter()
+            .chain(&self.having_condition)
+            .chain(joins)
+            .chain(expressions)
+            .try_for_each(Condition::check)?;
+
+        for column in &self.columns {
+            match column {
+                Column::SubQuery(subquery, _) => subquery.check()?,
+                Column::Expr(expression, _) => expression.check()?,
+                Column::Text(_) => {}
+            }
+        }
+
+        self.except_clauses
+            .iter()
+            .chain(&self.union_clauses)
+            .flatten()
+            .try_for_each(SelectQueryBuilder::check)
+    }
+
+    /// Checks the query for errors the database of its dialect would report, without
+    /// executing it, e.g. before running the SQL of a query rendered for another dialect.
+    ///
+    /// Building the query runs the same checks for SQLite.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()` if the query is valid, or an `Error` like
+    /// `Error::InvalidDistinctOrder` if the ORDER BY column of a SELECT DISTINCT query is
+    /// not selected, which SQLite accepts and stricter databases reject.
+    pub fn check(&self) -> Result<(), Error> {
+        if let Some(message) = &self.subquery_error {
+            return Err(Error::InvalidQuery(message.clone()));
+        }
+
+        self.check_conditions()?;
+
+        if let (true, Some(group_by)) = (self.strict_group_by, &self.group_by) {
+            if let Some(column) = ungrouped_column(&self.expanded_columns(), group_by) {
+                return Err(Error::InvalidGroupBy(column));
+            }
+        }
+
+        if let Some(column) = self.ambiguous_column() {
+            return Err(Error::AmbiguousColumn(column));
+        }
+
+        if let Some(column) = self.unknown_order_by_column() {
+            return Err(Error::InvalidQuery(format!(
+                "ORDER BY column `{}` is neither grouped nor an alias of the selected columns",
+                column
+            )));
+        }
+
+        if let Some(column) = self.unselected_distinct_order_by_column() {
+            return Err(Error::InvalidDistinctOrder(column));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the first ORDER BY column of a SELECT DISTINCT query that is not selected,
+    /// if the dialect requires the ORDER BY columns to be selected.
+    fn unselected_distinct_order_by_column(&self) -> Option<String> {
+        if !self.distinct || !self.dialect.requires_selected_distinct_order() {
+            return None;
+        }
+        let order_by = self.order_by.as_ref()?;
+
+        let unqualified = |column: &str| column.rsplit('.').next().unwrap_or(column).to_string();
+        let mut selected: Vec<String> = Vec::new();
+        for column in &self.expanded_columns() {
+            match column {
+                Column::Text(column) => {
+                    selected.push(column.clone());
+                    selected.push(output_column_name(column));
+                }
+                Column::SubQuery(_, alias) | Column::Expr(_, alias) => selected.push(alias.clone()),
+            }
+        }
+        if selected.iter().any(|column| column == "*") {
+            return None;
+        }
+
+        order_by
+            .keys()
+            .flatten()
+            .find(|column| {
+                !selected.contains(column) && !selected.contains(&unqualified(column))
+            })
+            .cloned()
+    }
+
+    /// Returns the first ORDER BY column of a grouped query that is a plain name but
+    /// neither a GROUP BY column nor the name or alias of a selected column, e.g. the
+    /// misspelled alias of an aggregate. Expressions are not checked.
+    fn unknown_order_by_column(&self) -> Option<String> {
+        let group_by = self.group_by.as_ref()?;
+        let order_by = self.order_by.as_ref()?;
+
+        let unqualified = |column: &str| column.rsplit('.').next().unwrap_or(column).to_string();
+        let mut known: Vec<String> = group_by.iter().map(|g| unqualified(g)).collect();
+        for column in &self.expanded_columns() {
+            match column {
+                Column::Text(column) => known.push(output_column_name(column)),
+                Column::SubQuery(_, alias) | Column::Expr(_, alias) => known.push(alias.clone()),
+            }
+        }
+        if known.iter().any(|column| column == "*") {
+            return None;
+        }
+
+        order_by.keys().flatten().find_map(|column| {
+            let is_plain = column
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+            if is_plain && !known.contains(&unqualified(column)) {
+                Some(column.clone())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns the first selected column of a query with joins that is either an
+    /// unqualified column of more than one of the joined tables, or that has the same
+    /// name in the result as another selected column, e.g. `users.id` and `orders.id`.
+    fn ambiguous_column(&self) -> Option<String> {
+        let joins = self.joins.as_ref()?;
+
+        let mut tables_fields: Vec<Vec<String>> = joins
+            .iter()
+            .map(|join| join.table.get_column_fields())
+            .collect();
+        if let Some(table) = &self.table {
+            tables_fields.push(table.get_column_fields());
+        }
+
+        // The columns of a USING constraint are merged into a single column
+        let using_columns: Vec<&String> = joins
+            .iter()
+            .filter_map(|join| match &join.constraint {
+                JoinConstraint::Using(columns) => Some(columns),
+                JoinConstraint::On(_) => None,
+            })
+            .flatten()
+            .collect();
+
+        let mut output_names: Vec<String> = Vec::new();
+        for column in &self.expanded_columns() {
+            let column = match column {
+                Column::Text(column) => column,
+                Column::SubQuery(..) | Column::Expr(..) => continue,
+            };
+
+            let is_unqualified = !column.contains('.') && !column.contains(' ');
+            if is_unqualified && !using_columns.contains(&column) {
+                let table_count = tables_fields
+                    .iter()
+                    .filter(|fields| fields.contains(column))
+                    .count();
+                if table_count > 1 {
+                    return Some(column.clone());
+                }
+            }
+
+            let output_name = output_column_name(column);
+            if output_names.contains(&output_name) {
+                return Some(output_name);
+            }
+            output_names.push(output_name);
+        }
+
+        None
+    }
+
+    /// Returns the selected columns, with every `Column::table_all` expanded into the
+    /// qualified columns of the table it names, e.g. `orders.*` into `orders.id, orders.total`.
+    ///
+    /// A wildcard that doesn't name the table or a joined table by name or alias is kept.
+    fn expanded_columns(&self) -> Vec<Column<'a>> {
+        let mut tables: Vec<(String, Vec<String>)> = Vec::new();
+        if let Some(table) = &self.table {
+            let name = match &self.table_alias {
+                Some(alias) => alias.clone(),
+                None => table.get_prefixed_name(&self.table_prefix),
+            };
+            tables.push((name, table.get_column_fields()));
+        }
+        for join in self.joins.iter().flatten() {
+            let name = match &join.alias {
+                Some(alias) => alias.clone(),
+                None => join.table.get_prefixed_name(&self.table_prefix),
+            };
+            tables.push((name, join.table.get_column_fields()));
+        }
+
+        self.columns
+            .iter()
+            .flat_map(|column| {
+                let fields = match column {
+                    Column::Text(text) => text
+                        .strip_suffix(".*")
+                        .and_then(|table| tables.iter().find(|(name, _)| name == table)),
+                    Column::SubQuery(..) | Column::Expr(..) => None,
+                };
+                match fields {
+                    Some((name, fields)) => fields
+                        .iter()
+                        .map(|field| Column::Text(format!("{}.{}", name, field)))
+                        .collect(),
+                    None => vec![column.clone()],
+                }
+            })
+            .collect()
+    }
+
+    /// Renders the query with placeholders and returns it with the values to bind, so the
+    /// values of conditions and raw expressions are never spliced into the SQL.
+    pub(crate) fn sql_with_bound_params(&self) -> Result<(String, Vec<Value>), Error> {
+        let (sql, params) = self.to_sql_with_params();
+        let params = params
+            .iter()
+            .map(to_sqlite_value)
+            .collect::<Result<Vec<Value>, Error>>()?;
+
+        Ok((sql, params))
+    }
+
+    /// Returns the number of selected columns.
+    pub(crate) fn column_count(&self) -> usize {
+        self.expanded_columns().len()
+    }
+
     /// Builds and executes the SELECT query.
     ///
     /// # Arguments
@@ -353,11 +1162,310 @@ impl<'a, T: Table + Default> SelectQueryBuilder<'a, T> {
     /// # Returns
     ///
     /// A `Result` containing a vector of selected table rows if successful,
-    /// or a `rusqlite::Error` if an error occurs during the execution.
-    pub fn build(self, conn: &Connection) -> Result<Vec<T>> {
-        let final_query = self.build_query();
+    /// or an `Error` if an error occurs during the execution.
+    pub fn build(self, conn: &dyn Executor) -> Result<Vec<T>, Error> {
+        self.build_into::<T>(conn)
+    }
+
+    /// Builds and executes the SELECT query, decoding each result row into `U` instead
+    /// of the table type.
+    ///
+    /// This is useful for grouped queries, where `U` has fields named after the group
+    /// keys and the aliased aggregates, e.g. `SELECT description, SUM(amount) AS total
+    /// ... GROUP BY description` decoded into `Summary { description, total }`.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a vector of decoded rows if successful,
+    /// or an `Error` if an error occurs during the execution.
+    pub fn build_into<U: Table + Default>(self, conn: &dyn Executor) -> Result<Vec<U>, Error> {
+        let query = self.with_table_prefix(&conn.config().table_prefix);
+        query.validate()?;
+
+        let (final_query, params) = query.sql_with_bound_params()?;
+        // A large LIMIT is only an upper bound, so don't pre-allocate for all of it
+        let capacity = query
+            .capacity
+            .or(query.limit.map(|limit| limit.min(MAX_LIMIT_CAPACITY)))
+            .unwrap_or(0);
+
+        let mut rows = Vec::with_capacity(capacity);
+        conn.query(&final_query, &params, &mut |row| {
+            if query.strict_types {
+                check_column_types::<U>(row)?;
+            }
+            rows.push(row_to_table::<U>(row)?);
+            Ok(())
+        })?;
+
+        Ok(rows)
+    }
+
+    /// Builds and executes a keyset paginated SELECT query, see `after`.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the page of rows, with the page token of the next page if the
+    /// page has as many rows as the LIMIT, or an `Error` if `after` is not set or an error
+    /// occurs during the execution.
+    pub fn build_page(self, conn: &dyn Executor) -> Result<Page<T>, Error> {
+        let Some(keys) = &self.keyset else {
+            return Err(Error::InvalidQuery(
+                "a page requires a keyset column set with `after`".to_string(),
+            ));
+        };
+        let columns = keys
+            .iter()
+            .map(|(column, _)| column.clone())
+            .collect::<Vec<String>>();
+        let limit = self.limit;
+
+        let rows = self.build(conn)?;
+
+        let columns = columns.iter().map(String::as_str).collect::<Vec<&str>>();
+        Ok(Page::with_columns(rows, &columns, limit))
+    }
+
+    /// Builds and executes a grouped SELECT query, returning the aggregate row of every
+    /// group by its key.
+    ///
+    /// The rows are decoded into `U` like with `build_into`, e.g. `SELECT description,
+    /// SUM(amount) AS total ... GROUP BY description` keyed by `description`. The keys are
+    /// the values of the key column as literals, the group of NULL is keyed by `Value::Null`.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    /// * `key_column` - The selected GROUP BY column to key the groups by.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the rows by group key if successful, or an `Error` if the
+    /// key column is not a selected GROUP BY column, a key is not unique or an error
+    /// occurs during the execution.
+    pub fn build_grouped<U: Table + Default>(
+        self,
+        conn: &dyn Executor,
+        key_column: &str,
+    ) -> Result<HashMap<crate::condition::Value<'static>, U>, Error> {
+        let query = self.with_table_prefix(&conn.config().table_prefix);
+        query.validate()?;
+
+        let is_grouped = query.group_by.as_ref().is_some_and(|group_by| {
+            group_by
+                .iter()
+                .any(|column| output_column_name(column) == key_column)
+        });
+        if !is_grouped {
+            return Err(Error::InvalidGroupBy(key_column.to_string()));
+        }
+
+        let (final_query, params) = query.sql_with_bound_params()?;
+
+        let mut groups = HashMap::new();
+        conn.query(&final_query, &params, &mut |row| {
+            if query.strict_types {
+                check_column_types::<U>(row)?;
+            }
+
+            let key_index = row.as_ref().column_index(key_column)?;
+            let key = to_condition_value(row.get::<usize, Value>(key_index)?);
+            if groups.contains_key(&key) {
+                return Err(Error::InvalidQuery(format!(
+                    "group key `{}` of column `{}` is not unique",
+                    key, key_column
+                )));
+            }
+            groups.insert(key, row_to_table::<U>(row)?);
+            Ok(())
+        })?;
+
+        Ok(groups)
+    }
+
+    /// Builds and executes the SELECT query, returning the rows as `rusqlite` values
+    /// in the order of the selected columns.
+    ///
+    /// This skips decoding into the table struct, which saves the conversion for large
+    /// results. However, the values are SQLite specific, so code using `build_raw` does
+    /// not carry over to the other backends, use `build` for portable code.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the values of each row if successful,
+    /// or an `Error` if an error occurs during the execution.
+    pub fn build_raw(self, conn: &dyn Executor) -> Result<Vec<Vec<Value>>, Error> {
+        let query = self.with_table_prefix(&conn.config().table_prefix);
+        query.validate()?;
+
+        let (final_query, params) = query.sql_with_bound_params()?;
+
+        query_map(conn, &final_query, &params, |row| {
+            Ok((0..row.as_ref().column_count())
+                .map(|index| row.get::<usize, Value>(index))
+                .collect::<rusqlite::Result<Vec<Value>>>()?)
+        })
+    }
+
+    /// Builds and executes the SELECT query, returning the rows as a JSON array of objects
+    /// keyed by column name.
+    ///
+    /// Integers and reals map to JSON numbers, text to strings and NULL to `null`. Blobs
+    /// are base64 encoded strings, and reals that aren't finite, like NaN, are `null`.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the JSON array of rows if successful,
+    /// or an `Error` if an error occurs during the execution.
+    pub fn build_json(self, conn: &dyn Executor) -> Result<serde_json::Value, Error> {
+        let query = self.with_table_prefix(&conn.config().table_prefix);
+        query.validate()?;
+
+        let (final_query, params) = query.sql_with_bound_params()?;
+
+        let rows = query_map(conn, &final_query, &params, |row| {
+            let column_names = row.as_ref().column_names();
+            let mut object = serde_json::Map::with_capacity(column_names.len());
+            for (index, name) in column_names.iter().enumerate() {
+                object.insert(name.to_string(), value_to_json(row.get::<usize, Value>(index)?));
+            }
+            Ok(serde_json::Value::Object(object))
+        })?;
+
+        Ok(serde_json::Value::Array(rows))
+    }
+
+    /// Builds and executes the SELECT query, transforming each row with `f` as it is
+    /// read from the cursor.
+    ///
+    /// This is useful for projecting rows into types the `Table` derive doesn't cover,
+    /// e.g. tuples. The columns are read by index in the order they are selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    /// * `f` - The closure mapping a result row.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the mapped rows if successful,
+    /// or an `Error` if an error occurs during the execution or in `f`.
+    pub fn map_rows<U, F>(self, conn: &dyn Executor, mut f: F) -> Result<Vec<U>, Error>
+    where
+        F: FnMut(&Row) -> rusqlite::Result<U>,
+    {
+        let query = self.with_table_prefix(&conn.config().table_prefix);
+        query.validate()?;
+
+        let (final_query, params) = query.sql_with_bound_params()?;
 
-        raw_execute(&final_query, conn)
+        query_map(conn, &final_query, &params, |row| Ok(f(row)?))
+    }
+
+    /// Builds and executes the SELECT query for a single column, decoding the value of
+    /// each row into `U`, e.g. `pluck::<u32>(&conn, "amount")` for a `Vec<u32>`.
+    ///
+    /// The column replaces the selected columns, the other parts of the query like the
+    /// WHERE condition and the ordering are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    /// * `column` - The column to select.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the values of the column if successful, or an `Error` if
+    /// an error occurs during the execution or a value can't be decoded into `U`.
+    pub fn pluck<U: FromSql>(self, conn: &dyn Executor, column: &str) -> Result<Vec<U>, Error> {
+        self.select(vec![Column::Text(column.to_string())])
+            .map_rows(conn, |row| row.get::<usize, U>(0))
+    }
+
+    /// Returns the query plan of the SELECT query using `EXPLAIN QUERY PLAN`.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the plan steps as text, e.g.
+    /// `SEARCH users USING INDEX idx_users_email (email=?)`,
+    /// or an `Error` if the query is invalid.
+    pub fn explain(self, conn: &dyn Executor) -> Result<Vec<String>, Error> {
+        let query = self.with_table_prefix(&conn.config().table_prefix);
+        query.validate()?;
+
+        let (final_query, params) = query.sql_with_bound_params()?;
+        let final_query = format!("EXPLAIN QUERY PLAN {}", final_query);
+
+        query_map(conn, &final_query, &params, |row| {
+            Ok(row.get::<&str, String>("detail")?)
+        })
+    }
+}
+
+/// A builder for the join condition of a JOIN clause, created by `SelectQueryBuilder::join_table`.
+pub struct JoinBuilder<'a, T: Table + Default> {
+    query: SelectQueryBuilder<'a, T>,
+    join_type: JoinType,
+    table: Arc<dyn Table>,
+    alias: Option<String>,
+}
+
+impl<'a, T: Table + Default> JoinBuilder<'a, T> {
+    /// References the joined table by an alias, e.g. `b` for a self-join.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The alias of the joined table.
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.alias = Some(alias.to_string());
+        self
+    }
+
+    /// Joins the rows for which the condition holds, e.g. `users.id = orders.user_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `condition` - The condition that specifies how the tables are related.
+    pub fn on(self, condition: impl Into<Condition<'a>>) -> SelectQueryBuilder<'a, T> {
+        let mut join = Join::new(self.join_type, self.table, condition.into());
+        join.alias = self.alias;
+        self.query.push_join(join)
+    }
+
+    /// Joins the rows with equal values in the given columns, which both tables must have.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - The shared columns, e.g. `["user_id"]`.
+    pub fn using<I, S>(self, columns: I) -> SelectQueryBuilder<'a, T>
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        let columns = columns.into_iter().map(|c| c.to_string()).collect();
+        let mut join = Join::using(self.join_type, self.table, columns);
+        join.alias = self.alias;
+        self.query.push_join(join)
     }
 }
 
@@ -371,6 +1479,18 @@ where
     fn to_sql(&self) -> String {
         self.build_query()
     }
+
+    fn selected_columns(&self) -> Option<usize> {
+        Some(self.expanded_columns().len())
+    }
+
+    fn to_sql_with_params(&self, params: &mut Params<'a>) -> String {
+        self.render(Some(params))
+    }
+
+    fn check(&self) -> Result<(), Error> {
+        SelectQueryBuilder::check(self)
+    }
 }
 
 /// Executes a raw SQL query and returns a vector of table rows.
@@ -383,33 +1503,134 @@ where
 /// # Returns
 ///
 /// A `Result` containing a vector of table rows if successful,
-/// or a `rusqlite::Error` if an error occurs during the execution.
-pub fn raw_execute<T: Table + Default>(sql: &str, conn: &Connection) -> Result<Vec<T>> {
-    let mut binding = conn.prepare(sql)?;
-    let iter = binding.query_map((), |row| {
-        let mut instance = T::default();
-        let columns = instance.get_column_fields();
-
-        for (index, column) in columns.iter().enumerate() {
-            let value = row.get::<usize, Value>(index)?;
-
-            let string_value = match value {
-                Value::Integer(val) => val.to_string(),
-                Value::Null => String::new(),
-                Value::Real(val) => val.to_string(),
-                Value::Text(val) => val.to_string(),
-                Value::Blob(val) => String::from_utf8_lossy(&val).to_string(),
-            };
+/// or an `Error` if an error occurs during the execution.
+pub fn raw_execute<T: Table + Default>(sql: &str, conn: &dyn Executor) -> Result<Vec<T>, Error> {
+    query_map(conn, sql, &[], |row| Ok(row_to_table::<T>(row)?))
+}
 
-            instance.set_column_value(column, &string_value);
-        }
+/// Returns the name of a selected column in the result set, i.e. the alias if the
+/// column has one, otherwise the column name without the table qualifier.
+fn output_column_name(column: &str) -> String {
+    match column.rsplit_once(" AS ") {
+        Some((_, alias)) if !alias.contains(')') => alias.trim().to_string(),
+        _ => column.rsplit('.').next().unwrap_or(column).trim().to_string(),
+    }
+}
+
+/// Checks if the column of the table is a BLOB column.
+pub(crate) fn is_blob_column(column_types: &HashMap<String, String>, column: &str) -> bool {
+    column_types
+        .get(column)
+        .is_some_and(|column_type| column_type.starts_with("BLOB"))
+}
+
+/// Converts a result row into a table row.
+///
+/// If every column of the row is named after a distinct field of the table, the
+/// columns are mapped by name, so the order of the stored columns does not matter
+/// (e.g. for `SELECT *`). The names are matched case insensitively if there is no
+/// exact match, see `match_column_field`. Otherwise the columns are mapped to the fields
+/// in field declaration order.
+///
+/// # Arguments
+///
+/// * `row` - The row returned by the database.
+///
+/// # Returns
+///
+/// A `Result` containing the table row if successful,
+/// or a `rusqlite::Error` if a column could not be read.
+pub(crate) fn row_to_table<T: Table + Default>(row: &Row) -> rusqlite::Result<T> {
+    let mut instance = T::default();
+    let columns = decoded_columns(&instance, row);
+    let column_types = instance.get_columns();
+
+    for (index, column) in columns.iter().enumerate() {
+        let value = row.get::<usize, Value>(index)?;
+        let data_type = value.data_type();
+
+        let string_value = match value {
+            Value::Integer(val) => val.to_string(),
+            Value::Null => String::new(),
+            Value::Real(val) => val.to_string(),
+            Value::Text(val) => val.to_string(),
+            // BLOB fields (`Vec<u8>`) are decoded from hex, other fields get the text
+            Value::Blob(val) if is_blob_column(&column_types, column) => {
+                val.iter().map(|byte| format!("{:02x}", byte)).collect()
+            }
+            Value::Blob(val) => String::from_utf8_lossy(&val).to_string(),
+        };
+
+        instance
+            .try_set_column_value(column, &string_value)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, data_type, e.into()))?;
+    }
 
-        Ok(instance)
-    })?;
+    Ok(instance)
+}
+
+/// Converts a SQLite value to its JSON value, see `SelectQueryBuilder::build_json`.
+fn value_to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Integer(val) => serde_json::Value::from(val),
+        Value::Real(val) => serde_json::Number::from_f64(val)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::Text(val) => serde_json::Value::String(val),
+        Value::Blob(val) => serde_json::Value::String(BASE64_STANDARD.encode(val)),
+    }
+}
 
-    let result: Result<Vec<T>> = iter
-        .map(|row_result| row_result.and_then(|row| Ok(row)))
-        .collect::<Result<Vec<T>>>();
+/// Returns the fields the columns of a result row are decoded into, see `row_to_table`.
+fn decoded_columns<T: Table>(instance: &T, row: &Row) -> Vec<String> {
+    let fields = instance.get_column_fields();
+    let columns: Vec<Option<&str>> = row
+        .as_ref()
+        .column_names()
+        .iter()
+        .map(|name| match_column_field(&fields, name))
+        .collect();
+
+    let mut by_name = columns.iter().all(Option::is_some);
+    for (index, column) in columns.iter().enumerate() {
+        by_name &= !columns[..index].contains(column);
+    }
+
+    if by_name {
+        columns.iter().flatten().map(|column| column.to_string()).collect()
+    } else {
+        fields
+    }
+}
+
+/// Checks that no REAL value of a result row is decoded into an INTEGER field and
+/// no INTEGER value into a REAL field.
+fn check_column_types<T: Table + Default>(row: &Row) -> Result<(), Error> {
+    let instance = T::default();
+    let column_types = instance.get_columns();
+
+    for (index, column) in decoded_columns(&instance, row).iter().enumerate() {
+        let column_type = match column_types.get(column) {
+            Some(column_type) => column_type,
+            None => continue,
+        };
+
+        let mismatch = match row.get_ref(index)? {
+            ValueRef::Real(_) => column_type.starts_with("INTEGER"),
+            ValueRef::Integer(_) => column_type.starts_with("REAL"),
+            _ => false,
+        };
+
+        if mismatch {
+            return Err(Error::TypeMismatch(format!(
+                "column `{}` of type {} got a {} value",
+                column,
+                column_type,
+                row.get_ref(index)?.data_type()
+            )));
+        }
+    }
 
-    result.map_err(|err| err.into())
+    Ok(())
 }
@@ -0,0 +1,127 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::error::Error;
+use crate::sqlite::Executor;
+use crate::util::IsolationLevel;
+
+use log::{debug, warn};
+
+/// Constructs a new transaction builder.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+///
+/// # Returns
+///
+/// A `TransactionBuilder` instance.
+pub fn transaction(conn: &dyn Executor) -> TransactionBuilder<'_> {
+    TransactionBuilder::new(conn)
+}
+
+/// A builder for running statements in a transaction.
+pub struct TransactionBuilder<'a> {
+    conn: &'a dyn Executor,
+    isolation: Option<IsolationLevel>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    /// Creates a new `TransactionBuilder` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the database connection.
+    pub fn new(conn: &'a dyn Executor) -> Self {
+        TransactionBuilder {
+            conn,
+            isolation: None,
+        }
+    }
+
+    /// Sets the isolation level of the transaction.
+    ///
+    /// SQLite transactions are always serializable, the isolation level instead selects
+    /// when the database is locked: `ReadCommitted` begins a `DEFERRED` transaction that
+    /// locks on the first statement, `RepeatableRead` an `IMMEDIATE` transaction that
+    /// takes the write lock right away and `Serializable` an `EXCLUSIVE` transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `isolation` - The isolation level of the transaction.
+    pub fn isolation(mut self, isolation: IsolationLevel) -> Self {
+        self.isolation = Some(isolation);
+        self
+    }
+
+    /// Builds the statement beginning the transaction.
+    pub fn build_query(&self) -> String {
+        let behavior = match self.isolation {
+            None | Some(IsolationLevel::ReadCommitted) => "DEFERRED",
+            Some(IsolationLevel::RepeatableRead) => "IMMEDIATE",
+            Some(IsolationLevel::Serializable) => "EXCLUSIVE",
+        };
+
+        format!("BEGIN {} TRANSACTION", behavior)
+    }
+
+    /// Runs the operation in the transaction.
+    ///
+    /// The transaction is committed if the operation succeeds and rolled back if it fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - The operation to run, receiving the connection of the transaction.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the result of the operation, or the `Error` of the operation
+    /// or of beginning or committing the transaction.
+    pub fn run<R, F>(self, op: F) -> Result<R, Error>
+    where
+        F: FnOnce(&dyn Executor) -> Result<R, Error>,
+    {
+        let begin = self.build_query();
+        debug!("{}", begin);
+        self.conn.execute_batch(&begin)?;
+
+        let result = op(self.conn).and_then(|result| {
+            self.conn.execute_batch("COMMIT")?;
+            Ok(result)
+        });
+
+        if result.is_err() && !self.conn.is_autocommit() {
+            if let Err(e) = self.conn.execute_batch("ROLLBACK") {
+                warn!("Failed to roll back transaction: {}", e);
+            }
+        }
+
+        result
+    }
+}
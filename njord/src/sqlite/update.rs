@@ -31,19 +31,22 @@ use std::collections::HashMap;
 
 use crate::{
     condition::Condition,
+    dialect::Dialect,
+    error::Error,
+    sqlite::connection::{query_map, Executor},
     sqlite::util::{
-        generate_limit_str, generate_offset_str, generate_order_by_str,
-        generate_where_condition_str, remove_quotes_and_backslashes,
+        check_integer_range, generate_limited_where_str, quote_identifiers,
+        remove_quotes_and_backslashes, supports_update_delete_limit,
     },
 };
 
-use rusqlite::{Connection, Result};
+use rusqlite::types::Value;
 
-use log::{debug, info};
+use log::debug;
 
 use crate::table::Table;
 
-use super::select::SelectQueryBuilder;
+use super::select::{is_blob_column, row_to_table, SelectQueryBuilder};
 
 /// Constructs a new UPDATE query builder.
 ///
@@ -113,8 +116,8 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     /// # Arguments
     ///
     /// * `condition` - The condition to be applied in the WHERE clause.
-    pub fn where_clause(mut self, condition: Condition<'a>) -> Self {
-        self.where_condition = Some(condition);
+    pub fn where_clause(mut self, condition: impl Into<Condition<'a>>) -> Self {
+        self.where_condition = Some(condition.into());
         self
     }
 
@@ -130,6 +133,9 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
 
     /// Sets the LIMIT clause for the query.
     ///
+    /// Unless SQLite is compiled with `SQLITE_ENABLE_UPDATE_DELETE_LIMIT`, the limit is
+    /// applied with a subquery on the primary key, or the rowid for tables without one.
+    ///
     /// # Arguments
     ///
     /// * `count` - The maximum number of rows to be updated.
@@ -156,12 +162,62 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
     ///
     /// # Returns
     ///
-    /// A `Result` indicating success or failure of the update operation.
-    pub fn build(self, conn: &Connection) -> Result<(), String> {
+    /// A `Result` containing the number of updated rows if successful,
+    /// or an `Error` if an error occurs.
+    pub fn build(self, conn: &dyn Executor) -> std::result::Result<usize, Error> {
+        if let Some(table) = &self.table {
+            table.check_mapped_types()?;
+        }
+        if let Some(condition) = &self.where_condition {
+            condition.check()?;
+        }
+        let query = self.build_query(conn, None);
+
+        debug!("{}", query);
+
+        conn.execute(&query, &[])
+    }
+
+    /// Builds and executes the UPDATE query with a `RETURNING` clause, decoding the
+    /// updated rows into `U`.
+    ///
+    /// The returned columns are the columns of `U`, so `U` can be the updated table
+    /// type itself or a struct with a subset of its columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A `rusqlite::Connection` to the SQLite database.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the updated rows if the update is successful,
+    /// or an `Error` if an error occurs.
+    pub fn returning_into<U: Table + Default>(
+        self,
+        conn: &dyn Executor,
+    ) -> std::result::Result<Vec<U>, Error> {
+        if let Some(table) = &self.table {
+            table.check_mapped_types()?;
+        }
+        if let Some(condition) = &self.where_condition {
+            condition.check()?;
+        }
+        let returning_columns = quote_identifiers(&U::default().get_column_fields());
+        let query = self.build_query(conn, Some(&returning_columns));
+
+        debug!("{}", query);
+
+        query_map(conn, &query, &[], |row| Ok(row_to_table::<U>(row)?))
+    }
+
+    /// Builds the UPDATE statement, with a `RETURNING` clause of the given columns, if any.
+    ///
+    /// The table name is prefixed with the table prefix of the connection.
+    fn build_query(&self, conn: &dyn Executor, returning: Option<&str>) -> String {
         let table_name = self
             .table
             .as_ref()
-            .map(|t| t.get_name().to_string())
+            .map(|t| t.get_prefixed_name(&conn.config().table_prefix))
             .unwrap_or("".to_string());
 
         // Sanitize table name from unwanted quotations or backslashes
@@ -172,19 +228,32 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
             let mut set_fields = Vec::new();
             let fields = table.get_column_fields();
             let values = table.get_column_values();
+            let column_types = table.get_columns();
 
             for column in &self.columns {
                 // Check if column exists in the table's fields
                 if let Some(index) = fields.iter().position(|c| column == c) {
                     let value = values.get(index).cloned().unwrap_or_default();
-                    let formatted_value = if value.is_empty() {
+                    let is_null = value.is_empty()
+                        || (value == "NULL"
+                            && column_types
+                                .get(column)
+                                .is_some_and(|column_type| column_type.ends_with(" NULL")));
+                    let formatted_value = if is_null {
                         "NULL".to_string()
+                    } else if is_blob_column(&column_types, column) {
+                        // BLOB values are hex encoded, set them as a BLOB literal
+                        format!("X'{}'", value)
                     } else if value.parse::<f64>().is_ok() {
                         value
                     } else {
                         format!("'{}'", value)
                     };
-                    set_fields.push(format!("{} = {}", column, formatted_value));
+                    set_fields.push(format!(
+                        "{} = {}",
+                        Dialect::Sqlite.quote_identifier(column),
+                        formatted_value
+                    ));
                 } else {
                     eprintln!("Column '{}' does not exist in the table", column);
                 }
@@ -201,28 +270,166 @@ impl<'a, T: Table + Default> UpdateQueryBuilder<'a, T> {
             String::new()
         };
 
-        let where_condition_str = generate_where_condition_str(self.where_condition);
-        let order_by_str = generate_order_by_str(&self.order_by);
-        let limit_str = generate_limit_str(self.limit);
-        let offset_str = generate_offset_str(self.offset);
+        let key_column = self
+            .table
+            .as_ref()
+            .and_then(|t| t.get_primary_key_column())
+            .unwrap_or("rowid");
+        let (where_condition_str, limit_str) = generate_limited_where_str(
+            supports_update_delete_limit(conn),
+            &table_name_str,
+            key_column,
+            self.where_condition.clone(),
+            &self.order_by,
+            self.limit,
+            self.offset,
+        );
+        let returning_str = returning
+            .map(|columns| format!("RETURNING {}", columns))
+            .unwrap_or_default();
 
         // Construct the query based on defined variables above
-        let query = format!(
+        format!(
             "UPDATE {} SET {} {} {} {}",
-            table_name_str,
-            set,
-            where_condition_str,
-            order_by_str,
-            format!("{} {}", limit_str, offset_str),
-        );
+            table_name_str, set, where_condition_str, returning_str, limit_str,
+        )
+    }
+}
 
-        debug!("{}", query);
+/// Updates a row by its primary key, setting every other column to the value of the
+/// table row.
+///
+/// If the table has a version column, the row is only updated if its version still
+/// matches the version of the table row, and the version is incremented both in the
+/// database and in the table row. This prevents overwriting the changes of another
+/// update made since the row was read.
+///
+/// # Arguments
+///
+/// * `conn` - A `rusqlite::Connection` to the SQLite database.
+/// * `table` - The table row to update, its version is incremented on success.
+///
+/// # Returns
+///
+/// A `Result` containing `()` if the row was updated, an `Error::StaleData` if the
+/// version of the row has changed, or an `Error` if the update failed.
+pub fn update_by_pk<T: Table>(conn: &dyn Executor, table: &mut T) -> std::result::Result<(), Error> {
+    let primary_key = table
+        .get_primary_key_column()
+        .map(str::to_string)
+        .ok_or_else(|| {
+            Error::InvalidQuery(format!("table `{}` has no primary key", table.get_name()))
+        })?;
+    table.check_mapped_types()?;
+    let version = table.get_version_column().map(str::to_string);
 
-        match conn.prepare(query.as_str()) {
-            Ok(_) => println!("Success!"),
-            Err(_) => eprintln!("Could not execute..."),
-        };
+    let column_types = table.get_columns();
+    let values: HashMap<String, String> = table
+        .get_column_fields()
+        .into_iter()
+        .zip(table.get_column_values())
+        .collect();
+    for (column, value) in &values {
+        check_integer_range(&column_types, column, value)?;
+    }
+    let param = |column: &str| {
+        to_column_value(
+            column_types.get(column).map(String::as_str).unwrap_or(""),
+            values.get(column).map(String::as_str).unwrap_or(""),
+        )
+    };
 
-        Ok(())
+    let generated_columns = table.get_generated_columns();
+
+    let mut set_fields = Vec::new();
+    let mut params = Vec::new();
+    for column in table.get_column_fields() {
+        if column == primary_key
+            || Some(&column) == version.as_ref()
+            || generated_columns.contains_key(&column)
+        {
+            continue;
+        }
+        params.push(param(&column));
+        set_fields.push(format!(
+            "{} = ?{}",
+            Dialect::Sqlite.quote_identifier(&column),
+            params.len()
+        ));
+    }
+
+    params.push(param(&primary_key));
+    let mut where_str = format!(
+        "{} = ?{}",
+        Dialect::Sqlite.quote_identifier(&primary_key),
+        params.len()
+    );
+
+    if let Some(version) = &version {
+        let version_column = Dialect::Sqlite.quote_identifier(version);
+        set_fields.push(format!("{} = {} + 1", version_column, version_column));
+        params.push(param(version));
+        where_str.push_str(&format!(" AND {} = ?{}", version_column, params.len()));
     }
+
+    let query = format!(
+        "UPDATE {} SET {} WHERE {}",
+        remove_quotes_and_backslashes(&table.get_prefixed_name(&conn.config().table_prefix)),
+        set_fields.join(", "),
+        where_str
+    );
+
+    debug!("{}", query);
+
+    let changed = conn.execute(&query, &params)?;
+
+    if let Some(version) = &version {
+        if changed == 0 {
+            return Err(Error::StaleData(format!(
+                "row of `{}` with {} = {} was changed or deleted since it was read",
+                table.get_name(),
+                primary_key,
+                values.get(&primary_key).cloned().unwrap_or_default()
+            )));
+        }
+
+        let current = values
+            .get(version)
+            .and_then(|v| v.parse::<i64>().ok())
+            .unwrap_or(0);
+        table.set_column_value(version, &(current + 1).to_string());
+    }
+
+    Ok(())
+}
+
+/// Converts the string value of a column into a SQLite value of its column type.
+fn to_column_value(column_type: &str, value: &str) -> Value {
+    if column_type.ends_with(" NULL") && value == "NULL" {
+        return Value::Null;
+    }
+
+    if column_type.starts_with("BLOB") {
+        let bytes = (0..value.len())
+            .step_by(2)
+            .filter_map(|i| {
+                value
+                    .get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+            })
+            .collect();
+        return Value::Blob(bytes);
+    }
+
+    if column_type.starts_with("INTEGER") {
+        if let Ok(integer) = value.parse::<i64>() {
+            return Value::Integer(integer);
+        }
+    } else if column_type.starts_with("REAL") {
+        if let Ok(real) = value.parse::<f64>() {
+            return Value::Real(real);
+        }
+    }
+
+    Value::Text(value.to_string())
 }
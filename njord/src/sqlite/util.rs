@@ -29,7 +29,13 @@
 
 use std::collections::HashMap;
 
-use crate::condition::Condition;
+use crate::condition::{Condition, Value};
+use crate::dialect::Dialect;
+use crate::error::Error;
+
+use rusqlite::types::Value as SqliteValue;
+
+use super::connection::{query_map, Executor};
 
 /// Generates an SQL WHERE clause string based on the provided condition.
 ///
@@ -45,7 +51,7 @@ use crate::condition::Condition;
 /// A String representing the generated SQL WHERE clause.
 pub fn generate_where_condition_str(condition: Option<Condition>) -> String {
     if let Some(condition) = condition {
-        format!("WHERE {}", condition.build())
+        format!("WHERE {}", condition.build_with(Dialect::Sqlite))
     } else {
         String::new()
     }
@@ -65,7 +71,7 @@ pub fn generate_where_condition_str(condition: Option<Condition>) -> String {
 /// A String representing the generated SQL GROUP BY clause.
 pub fn generate_group_by_str(columns: &Option<Vec<String>>) -> String {
     match columns {
-        Some(columns) => format!("GROUP BY {}", columns.join(", ")),
+        Some(columns) => format!("GROUP BY {}", quote_identifiers(columns)),
         None => String::new(),
     }
 }
@@ -89,7 +95,7 @@ pub fn generate_order_by_str(order_by: &Option<HashMap<Vec<String>, String>>) ->
     let order_by_str = if let Some(order_by) = order_by.as_ref() {
         let order_by_str: Vec<String> = order_by
             .iter()
-            .map(|(columns, order)| format!("{} {}", columns.join(", "), order))
+            .map(|(columns, order)| format!("{} {}", quote_identifiers(columns), order))
             .collect();
         if !order_by_str.is_empty() {
             format!("ORDER BY {}", order_by_str.join(", "))
@@ -135,26 +141,74 @@ pub fn generate_offset_str(offset: Option<usize>) -> String {
     offset.map_or(String::new(), |offset| format!("OFFSET {}", offset))
 }
 
-/// Generates an SQL HAVING clause string based on the provided group by flag and condition.
+/// Returns `true` if SQLite is compiled with `SQLITE_ENABLE_UPDATE_DELETE_LIMIT`, which
+/// adds ORDER BY and LIMIT clauses to UPDATE and DELETE statements.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the database connection.
+pub fn supports_update_delete_limit(conn: &dyn Executor) -> bool {
+    let options = query_map(conn, "PRAGMA compile_options", &[], |row| {
+        Ok(row.get::<usize, String>(0)?)
+    });
+
+    options.is_ok_and(|options| options.iter().any(|o| o == "ENABLE_UPDATE_DELETE_LIMIT"))
+}
+
+/// Generates the WHERE clause and the ORDER BY, LIMIT and OFFSET clauses of an UPDATE
+/// or DELETE statement.
 ///
-/// If `group_by` is true and `having_condition` is Some, it constructs an SQL HAVING clause string
-/// with the specified condition.
-/// If either `group_by` is false or `having_condition` is None, an empty string is returned.
+/// If SQLite doesn't support limits on these statements, see `supports_update_delete_limit`,
+/// the rows are limited with a subquery on the key column instead, e.g.
+/// `WHERE id IN (SELECT id FROM users WHERE ... ORDER BY ... LIMIT 2)`, and the second
+/// clause is empty.
 ///
 /// # Arguments
 ///
-/// * `group_by` - An Option indicating whether GROUP BY is present.
-/// * `having_condition` - An Option containing the condition for the HAVING clause.
+/// * `native_limit` - Whether SQLite supports limits on UPDATE and DELETE statements.
+/// * `table_name` - The name of the updated or deleted table.
+/// * `key_column` - The primary key column, or `rowid` for tables without one.
+/// * `condition` - The condition of the WHERE clause.
+/// * `order_by` - The columns and order directions of the ORDER BY clause.
+/// * `limit` - The maximum number of rows to update or delete.
+/// * `offset` - The number of matching rows to skip.
 ///
 /// # Returns
 ///
-/// A String representing the generated SQL HAVING clause.
-pub fn generate_having_str(group_by: bool, having_condition: Option<&Condition>) -> String {
-    if group_by && having_condition.is_some() {
-        format!("HAVING {}", having_condition.unwrap().build())
-    } else {
-        String::new()
+/// A tuple of the WHERE clause and the clauses following it.
+pub fn generate_limited_where_str(
+    native_limit: bool,
+    table_name: &str,
+    key_column: &str,
+    condition: Option<Condition>,
+    order_by: &Option<HashMap<Vec<String>, String>>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> (String, String) {
+    let where_condition_str = generate_where_condition_str(condition);
+    let order_by_str = generate_order_by_str(order_by);
+    // OFFSET requires a LIMIT, where -1 stands for no limit
+    let limit_str = match (limit, offset) {
+        (None, Some(_)) => "LIMIT -1".to_string(),
+        _ => generate_limit_str(limit),
+    };
+    let offset_str = generate_offset_str(offset);
+
+    if native_limit || (limit.is_none() && offset.is_none()) {
+        let clauses = [order_by_str, limit_str, offset_str];
+        return (where_condition_str, clauses.join(" ").trim().to_string());
     }
+
+    let key_column = Dialect::Sqlite.quote_identifier(key_column);
+    let subquery = format!(
+        "SELECT {} FROM {} {} {} {} {}",
+        key_column, table_name, where_condition_str, order_by_str, limit_str, offset_str
+    );
+
+    (
+        format!("WHERE {} IN ({})", key_column, subquery),
+        String::new(),
+    )
 }
 
 /// Removes double quotes and backslashes from a given string.
@@ -170,10 +224,72 @@ pub fn remove_quotes_and_backslashes(input: &str) -> String {
     input.replace("\"", "").replace("\\", "")
 }
 
+/// Quotes the columns and joins them with commas.
+pub fn quote_identifiers(columns: &[String]) -> String {
+    quote_identifiers_with(Dialect::Sqlite, columns)
+}
+
+/// Quotes the columns for `dialect` and joins them with commas.
+pub fn quote_identifiers_with(dialect: Dialect, columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| dialect.quote_identifier(column))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Checks that the value of an INTEGER column fits in a SQLite integer.
+///
+/// SQLite integers are 64-bit signed, larger values like `u64::MAX` would be stored as
+/// an inexact REAL value. Fields that need the full range can use `i128` or `u128`,
+/// which are stored as TEXT.
+///
+/// # Arguments
+///
+/// * `column_types` - The column types of the table.
+/// * `column` - The name of the column.
+/// * `value` - The value of the column.
+///
+/// # Returns
+///
+/// A `Result` containing `()` if the value fits, or an `Error::IntegerOverflow` if not.
+pub fn check_integer_range(
+    column_types: &HashMap<String, String>,
+    column: &str,
+    value: &str,
+) -> Result<(), Error> {
+    let is_integer_column = column_types
+        .get(column)
+        .is_some_and(|column_type| column_type.starts_with("INTEGER"));
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let is_integer_literal = !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit());
+
+    if is_integer_column && is_integer_literal && value.parse::<i64>().is_err() {
+        return Err(Error::IntegerOverflow(format!(
+            "value {} of column `{}` does not fit in a 64-bit signed INTEGER",
+            value, column
+        )));
+    }
+
+    Ok(())
+}
+
+/// Converts a value read from SQLite into a condition value, e.g. to key rows by it.
+///
+/// Numbers and blobs are converted to their text, NULL is `Value::Null`.
+pub fn to_condition_value(value: SqliteValue) -> Value<'static> {
+    match value {
+        SqliteValue::Integer(val) => Value::Literal(val.to_string()),
+        SqliteValue::Real(val) => Value::Literal(val.to_string()),
+        SqliteValue::Text(val) => Value::Literal(val),
+        SqliteValue::Blob(val) => Value::Literal(String::from_utf8_lossy(&val).to_string()),
+        SqliteValue::Null => Value::Null,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::condition::{Condition, Value};
 
     #[test]
     fn test_generate_where_condition_str() {
@@ -187,12 +303,23 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_generate_where_condition_str_null_safe_eq() {
+        let condition = Condition::NullSafeEq("email".to_string(), Value::Null);
+        let result = generate_where_condition_str(Some(condition));
+        assert_eq!(result, "WHERE \"email\" IS NULL");
+
+        let condition = Condition::NullSafeEq("age".to_string(), Value::Literal("30".to_string()));
+        let result = generate_where_condition_str(Some(condition));
+        assert_eq!(result, "WHERE \"age\" IS 30");
+    }
+
     #[test]
     fn test_generate_group_by_str() {
         // Test when columns is Some
         let columns = Some(vec!["name".to_string(), "age".to_string()]);
         let result = generate_group_by_str(&columns);
-        assert_eq!(result, "GROUP BY name, age");
+        assert_eq!(result, "GROUP BY \"name\", \"age\"");
 
         // Test when columns is None
         let result = generate_group_by_str(&None);
@@ -235,26 +362,6 @@ mod tests {
         assert_eq!(result, "");
     }
 
-    #[test]
-    fn test_generate_having_str() {
-        // Test when group_by is true and having_condition is Some
-        let condition = Condition::Gt("COUNT(age)".to_string(), Value::Literal("5".to_string()));
-        let result = generate_having_str(true, Some(&condition));
-        assert_eq!(result, format!("HAVING {}", condition.build()));
-
-        // Test when group_by is false
-        let result = generate_having_str(false, Some(&condition));
-        assert_eq!(result, "");
-
-        // Test when having_condition is None
-        let result = generate_having_str(true, None);
-        assert_eq!(result, "");
-
-        // Test when both group_by is false and having_condition is None
-        let result = generate_having_str(false, None);
-        assert_eq!(result, "");
-    }
-
     #[test]
     fn test_remove_quotes_and_backslashes() {
         let input = r#""table_name\"""#;
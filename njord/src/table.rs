@@ -29,9 +29,24 @@
 
 use std::collections::HashMap;
 
+use crate::error::Error;
+
 #[allow(unused_imports)]
 use njord_derive::Table;
 
+/// An index of a table, set with the `#[index(...)]` struct attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Index {
+    /// The name of the index.
+    pub name: String,
+    /// The indexed columns, in order.
+    pub columns: Vec<String>,
+    /// Whether the index is a UNIQUE index.
+    pub unique: bool,
+    /// The WHERE clause of a partial index, e.g. `active = 1`.
+    pub predicate: Option<String>,
+}
+
 /// The Table trait.
 ///
 /// It is used for structs that want need the behaviour of an SQL Table.
@@ -41,27 +56,179 @@ pub trait Table {
     /// Returns a reference to a string representing the name of the table.
     fn get_name(&self) -> &str;
 
+    /// Get the schema of the table.
+    ///
+    /// Returns the schema set with the `#[schema = "..."]` attribute, or `None`
+    /// if the table lives in the default schema.
+    fn get_schema(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the qualified name of the table.
+    ///
+    /// Returns the table name qualified by its schema, e.g. `reporting.users`.
+    /// This is the name used in generated SQL.
+    fn get_qualified_name(&self) -> String {
+        self.get_prefixed_name("")
+    }
+
+    /// Get the qualified name of the table with a table prefix.
+    ///
+    /// Returns the table name with `prefix` prepended and qualified by its schema,
+    /// e.g. `reporting.app_users` for the prefix `app_`.
+    fn get_prefixed_name(&self, prefix: &str) -> String {
+        let name = format!("{}{}", prefix, self.get_name());
+
+        match self.get_schema() {
+            Some(schema) => format!("{}.{}", schema, name),
+            None => name,
+        }
+    }
+
     /// Get the columns of the table.
     ///
     /// Returns a reference to a `HashMap` where the keys are column names,
     /// and the values are column types represented as strings.
     fn get_columns(&self) -> HashMap<String, String>;
 
+    /// Get the database-side default expressions of the columns.
+    ///
+    /// Returns the expressions set with the `#[default_sql = "..."]` field attribute,
+    /// keyed by column name.
+    fn get_column_defaults(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Get the foreign keys of the columns.
+    ///
+    /// Returns the `REFERENCES` clauses set with the `#[foreign_key(...)]` field attribute,
+    /// keyed by column name, e.g. `REFERENCES users(id) ON DELETE SET NULL`.
+    fn get_foreign_keys(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Get the tables the table depends on.
+    ///
+    /// Returns the tables referenced by the `#[foreign_key(...)]` field attributes, in field
+    /// order, which `init` creates before this table.
+    fn dependencies(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Get the generated columns of the table.
+    ///
+    /// Returns the `GENERATED ALWAYS AS (...)` clauses set with the `#[generated(...)]`
+    /// field attribute, keyed by column name. Generated columns are computed by the
+    /// database, so they are left out of inserts and updates.
+    fn get_generated_columns(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Get the check constraints of the table.
+    ///
+    /// Returns the expressions set with the `#[check("...")]` struct and field attributes.
+    fn get_checks(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Get the uniqueness constraints of the table.
+    ///
+    /// Returns the columns of every constraint set with the `#[unique]` field attribute or
+    /// the `#[unique(columns = "...")]` struct attribute.
+    fn get_unique_constraints(&self) -> Vec<Vec<String>> {
+        Vec::new()
+    }
+
+    /// Get the indexes of the table.
+    ///
+    /// Returns the indexes set with the `#[index(...)]` struct attribute, in declaration order.
+    fn get_indexes(&self) -> Vec<Index> {
+        Vec::new()
+    }
+
+    /// Get the soft delete column of the table.
+    ///
+    /// Returns `deleted_at` for tables with the `#[soft_delete]` attribute. Deleting rows
+    /// of these tables sets the column instead of removing the rows, and selects leave
+    /// out the rows where it is set.
+    fn get_soft_delete_column(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns `true` if the table is created as a SQLite `WITHOUT ROWID` table,
+    /// set with the `#[without_rowid]` attribute.
+    fn is_without_rowid(&self) -> bool {
+        false
+    }
+
+    /// Get the primary key column of the table.
+    ///
+    /// Returns the column of the `PrimaryKey` or `AutoIncrementPrimaryKey` field, if any.
+    fn get_primary_key_column(&self) -> Option<&str> {
+        None
+    }
+
+    /// Get the version column of the table.
+    ///
+    /// Returns the column of the field with the `#[version]` attribute, which is checked
+    /// and incremented by updates by primary key for optimistic locking.
+    fn get_version_column(&self) -> Option<&str> {
+        None
+    }
+
     /// Get the names of the columns.
     ///
-    /// Returns a `Vec<String>` containing the names of the columns in the same order
-    /// as they appear in the table.
+    /// Returns a `Vec<String>` containing the names of the columns in field declaration
+    /// order. This is the canonical column order used when creating, inserting into and
+    /// selecting all columns of the table.
     fn get_column_fields(&self) -> Vec<String>;
 
     /// Get the values of the columns.
     ///
     /// Returns a `Vec<String>` containing the values of the columns in the same order
-    /// as they appear in the table.
+    /// as `get_column_fields()`.
     fn get_column_values(&self) -> Vec<String>;
 
+    /// Get the columns whose value is NULL, i.e. the `None` fields and an unset
+    /// auto-increment primary key.
+    ///
+    /// Inserts leave these columns out, so the database fills them with their default.
+    /// The default implementation returns the columns whose value is an auto-increment
+    /// primary key according to `is_auto_increment_primary_key`.
+    fn get_null_columns(&self) -> Vec<String> {
+        self.get_column_fields()
+            .into_iter()
+            .zip(self.get_column_values())
+            .filter(|(_, value)| self.is_auto_increment_primary_key(value))
+            .map(|(column, _)| column)
+            .collect()
+    }
+
     /// Set the values of the columns.
     fn set_column_value(&mut self, column: &str, value: &str);
 
+    /// Set the value of a column, failing if the value cannot be converted to the type
+    /// of the field.
+    ///
+    /// The default implementation calls `set_column_value`, which ignores invalid values.
+    fn try_set_column_value(&mut self, column: &str, value: &str) -> Result<(), String> {
+        self.set_column_value(column, value);
+        Ok(())
+    }
+
+    /// Checks that a mapping is registered for the type of every `#[mapped]` field,
+    /// see `TypeRegistry`.
+    ///
+    /// The builders check the mappings before they create, insert into or update the table.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `()`, or an `Error::InvalidQuery` naming the first column
+    /// whose type has no registered mapping.
+    fn check_mapped_types(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Determines if the provided value represents an auto-incrementing primary key.
     ///
     /// # Arguments
@@ -193,4 +360,31 @@ mod tests {
         assert_eq!(table_b.get_name(), "table_b");
         assert_eq!(table_c.get_name(), "table_c");
     }
+
+    #[test]
+    fn qualified_table_names() {
+        #[derive(Table)]
+        #[table_name = "users"]
+        #[schema = "reporting"]
+        struct User {
+            name: String,
+        }
+
+        #[derive(Table)]
+        #[table_name = "orders"]
+        struct Order {
+            amount: u32,
+        }
+
+        assert_eq!(User::default().get_schema(), Some("reporting"));
+        assert_eq!(Order::default().get_schema(), None);
+        assert_eq!(User::default().get_qualified_name(), "reporting.users");
+        assert_eq!(Order::default().get_qualified_name(), "orders");
+
+        assert_eq!(
+            User::default().get_prefixed_name("app_"),
+            "reporting.app_users"
+        );
+        assert_eq!(Order::default().get_prefixed_name("app_"), "app_orders");
+    }
 }
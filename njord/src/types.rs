@@ -0,0 +1,137 @@
+//! BSD 3-Clause License
+//!
+//! Copyright (c) 2024, Marcus Cvjeticanin
+//!
+//! Redistribution and use in source and binary forms, with or without
+//! modification, are permitted provided that the following conditions are met:
+//!
+//! 1. Redistributions of source code must retain the above copyright notice, this
+//!    list of conditions and the following disclaimer.
+//!
+//! 2. Redistributions in binary form must reproduce the above copyright notice,
+//!    this list of conditions and the following disclaimer in the documentation
+//!    and/or other materials provided with the distribution.
+//!
+//! 3. Neither the name of the copyright holder nor the names of its
+//!    contributors may be used to endorse or promote products derived from
+//!    this software without specific prior written permission.
+//!
+//! THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+//! AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+//! IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//! DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+//! FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+//! DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//! SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+//! CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+//! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+//! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// The mappings of all registered types, keyed by the `TypeId` of the type.
+static MAPPINGS: OnceLock<RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>> = OnceLock::new();
+
+/// The mapping of a registered type.
+struct Mapping<T> {
+    sql_type: String,
+    to_sql: fn(&T) -> String,
+    from_sql: fn(&str) -> Result<T, String>,
+}
+
+/// A registry of the conversions of custom field types.
+///
+/// Fields with `#[mapped]` are converted to and from the string values of the `Table`
+/// trait with the mapping registered for their type, which also gives the SQL type of
+/// their column. This supports domain types like `Email` or `Money` without implementing
+/// `Display` and `FromStr`, including types of other crates.
+///
+/// Types must be registered before a table using them is created, inserted or selected,
+/// otherwise the builders return an `Error::InvalidQuery`, see `Table::check_mapped_types`.
+///
+/// # Example
+///
+/// ```rust
+/// use njord::types::TypeRegistry;
+///
+/// struct Money(i64);
+///
+/// TypeRegistry::register::<Money>("INTEGER", |m| m.0.to_string(), |value| {
+///     value.parse().map(Money).map_err(|e| format!("invalid amount: {}", e))
+/// });
+///
+/// assert_eq!(TypeRegistry::sql_type::<Money>().as_deref(), Some("INTEGER"));
+/// ```
+pub struct TypeRegistry;
+
+impl TypeRegistry {
+    /// Registers the mapping of a type, replacing a previous mapping of the type.
+    ///
+    /// # Arguments
+    ///
+    /// * `sql_type` - The SQL type of the columns, e.g. `TEXT`.
+    /// * `to_sql` - Converts a value into the string value of its column.
+    /// * `from_sql` - Converts the string value of a column back into a value, or returns
+    ///   an error message if the value is invalid.
+    pub fn register<T: 'static>(
+        sql_type: &str,
+        to_sql: fn(&T) -> String,
+        from_sql: fn(&str) -> Result<T, String>,
+    ) {
+        let mapping = Mapping {
+            sql_type: sql_type.to_string(),
+            to_sql,
+            from_sql,
+        };
+
+        let mut mappings = MAPPINGS.get_or_init(Default::default).write().unwrap();
+        mappings.insert(TypeId::of::<T>(), Box::new(mapping));
+    }
+
+    /// Returns `true` if a mapping is registered for the type.
+    pub fn is_registered<T: 'static>() -> bool {
+        TypeRegistry::with_mapping::<T, _>(|_| ()).is_some()
+    }
+
+    /// Returns the SQL type registered for the type.
+    pub fn sql_type<T: 'static>() -> Option<String> {
+        TypeRegistry::with_mapping::<T, _>(|mapping| mapping.sql_type.clone())
+    }
+
+    /// Converts a value with the mapping registered for its type.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the string value, or `None` if the type is not registered.
+    pub fn to_sql<T: 'static>(value: &T) -> Option<String> {
+        TypeRegistry::with_mapping::<T, _>(|mapping| (mapping.to_sql)(value))
+    }
+
+    /// Converts a string value with the mapping registered for the type.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the value, or an error message if the type is not registered
+    /// or the value is invalid.
+    pub fn from_sql<T: 'static>(value: &str) -> Result<T, String> {
+        TypeRegistry::with_mapping::<T, _>(|mapping| (mapping.from_sql)(value)).unwrap_or_else(
+            || {
+                Err(format!(
+                    "no mapping registered for type `{}`",
+                    std::any::type_name::<T>()
+                ))
+            },
+        )
+    }
+
+    /// Calls `f` with the mapping of the type, if it is registered.
+    fn with_mapping<T: 'static, R>(f: impl FnOnce(&Mapping<T>) -> R) -> Option<R> {
+        let mappings = MAPPINGS.get()?.read().unwrap();
+        mappings
+            .get(&TypeId::of::<T>())
+            .and_then(|mapping| mapping.downcast_ref::<Mapping<T>>())
+            .map(f)
+    }
+}
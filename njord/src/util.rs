@@ -27,11 +27,30 @@
 //! OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
 //! OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::io::{Read, Write};
 use std::sync::Arc;
 
-use crate::condition::Condition;
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::column::Column;
+use crate::condition::{Condition, Value};
+use crate::error::Error;
 use crate::table::Table;
 
+/// Applies a collation to a column, e.g. `title COLLATE NOCASE`.
+///
+/// The result can be used as an ORDER BY column. Postgres collation names are
+/// quoted identifiers, e.g. `collate("title", "\"C\"")`.
+///
+/// # Arguments
+///
+/// * `column` - The column to apply the collation to.
+/// * `collation` - The name of the collation.
+pub fn collate(column: &str, collation: &str) -> String {
+    format!("{} COLLATE {}", column, collation)
+}
+
 /// Represents the type of SQL join.
 #[derive(Clone, Debug)]
 pub enum JoinType {
@@ -45,6 +64,413 @@ pub enum JoinType {
     Full,
 }
 
+/// Represents the row locking mode of a `SELECT ... FOR UPDATE` query.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LockMode {
+    /// FOR UPDATE - Waits until locked rows are released.
+    Wait,
+    /// FOR UPDATE NOWAIT - Fails immediately if a selected row is locked.
+    NoWait,
+    /// FOR UPDATE SKIP LOCKED - Leaves out rows that are locked.
+    SkipLocked,
+}
+
+impl LockMode {
+    /// Returns the SQL locking clause for the lock mode.
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            LockMode::Wait => "FOR UPDATE",
+            LockMode::NoWait => "FOR UPDATE NOWAIT",
+            LockMode::SkipLocked => "FOR UPDATE SKIP LOCKED",
+        }
+    }
+}
+
+/// Represents the isolation level of a transaction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IsolationLevel {
+    /// READ COMMITTED - Sees only data committed before each statement.
+    ReadCommitted,
+    /// REPEATABLE READ - Sees the same data for every read of the transaction.
+    RepeatableRead,
+    /// SERIALIZABLE - Behaves as if the transactions ran one after another.
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// Returns the `SET TRANSACTION ISOLATION LEVEL` statement for the isolation level.
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadCommitted => "SET TRANSACTION ISOLATION LEVEL READ COMMITTED",
+            IsolationLevel::RepeatableRead => "SET TRANSACTION ISOLATION LEVEL REPEATABLE READ",
+            IsolationLevel::Serializable => "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+        }
+    }
+}
+
+/// An opaque cursor pointing after the last row of a page of a keyset paginated query.
+///
+/// The token is URL safe, so it can be handed to clients and parsed back with `FromStr`
+/// to select the next page with `after`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageToken(String);
+
+impl PageToken {
+    /// Creates a page token pointing after the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value of the keyset column of the last row of the page.
+    pub fn new(key: &str) -> Self {
+        PageToken(BASE64_URL_SAFE_NO_PAD.encode(key))
+    }
+
+    /// Creates a page token pointing after the given keys of a composite keyset, see
+    /// `after_keys`.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The values of the keyset columns of the last row of the page, in order.
+    pub fn from_keys(keys: &[String]) -> Self {
+        let keys = keys
+            .iter()
+            .map(|key| BASE64_URL_SAFE_NO_PAD.encode(key))
+            .collect::<Vec<String>>();
+        PageToken(keys.join("."))
+    }
+
+    /// Returns the key the page token points after, composite keys are an error.
+    pub fn key(&self) -> Result<String, Error> {
+        match self.keys()?.as_slice() {
+            [key] => Ok(key.clone()),
+            _ => Err(Error::InvalidQuery(format!(
+                "page token `{}` has a composite key",
+                self.0
+            ))),
+        }
+    }
+
+    /// Returns the keys the page token points after, one per keyset column.
+    pub fn keys(&self) -> Result<Vec<String>, Error> {
+        let invalid = || Error::InvalidQuery(format!("invalid page token `{}`", self.0));
+        self.0
+            .split('.')
+            .map(|key| {
+                let bytes = BASE64_URL_SAFE_NO_PAD.decode(key).map_err(|_| invalid())?;
+                String::from_utf8(bytes).map_err(|_| invalid())
+            })
+            .collect()
+    }
+
+    /// Returns the key the page token points after as a value for `after`.
+    pub fn to_value(&self) -> Result<Value<'static>, Error> {
+        Ok(Value::Literal(self.key()?))
+    }
+
+    /// Returns the keys the page token points after as values for `after_keys`.
+    pub fn to_values(&self) -> Result<Vec<Value<'static>>, Error> {
+        Ok(self.keys()?.into_iter().map(Value::Literal).collect())
+    }
+}
+
+impl std::fmt::Display for PageToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for PageToken {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = PageToken(s.to_string());
+        token.keys()?;
+        Ok(token)
+    }
+}
+
+/// A page of rows of a keyset paginated query, see `after`.
+pub struct Page<T> {
+    /// The rows of the page.
+    pub rows: Vec<T>,
+    next: Option<PageToken>,
+}
+
+impl<T: Table> Page<T> {
+    /// Creates a page of rows, the page token points after the last row if the page is
+    /// full, i.e. has as many rows as the limit of the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The rows of the page.
+    /// * `column` - The keyset column the rows are ordered by.
+    /// * `limit` - The limit of the query, without a limit there is no next page.
+    pub fn new(rows: Vec<T>, column: &str, limit: Option<usize>) -> Self {
+        Page::with_columns(rows, &[column], limit)
+    }
+
+    /// Creates a page of rows of a query paginated by a composite keyset, see `new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The rows of the page.
+    /// * `columns` - The keyset columns the rows are ordered by, in order.
+    /// * `limit` - The limit of the query, without a limit there is no next page.
+    pub fn with_columns(rows: Vec<T>, columns: &[&str], limit: Option<usize>) -> Self {
+        let next = match (rows.last(), limit) {
+            (Some(last), Some(limit)) if rows.len() >= limit => {
+                let fields = last.get_column_fields();
+                let values = last.get_column_values();
+                columns
+                    .iter()
+                    .map(|column| {
+                        fields
+                            .iter()
+                            .position(|field| field == column)
+                            .and_then(|index| values.get(index).cloned())
+                    })
+                    .collect::<Option<Vec<String>>>()
+                    .map(|keys| PageToken::from_keys(&keys))
+            }
+            _ => None,
+        };
+
+        Page { rows, next }
+    }
+
+    /// Returns the page token of the next page, or `None` if this is the last page.
+    pub fn page_token(&self) -> Option<&PageToken> {
+        self.next.as_ref()
+    }
+}
+
+/// Returns the field of a table a column of a result row is decoded into.
+///
+/// The column name is compared exactly first and then case insensitively, since Oracle
+/// returns unquoted column names in uppercase and PostgreSQL in lowercase, e.g. `USERNAME`
+/// is decoded into the `username` field.
+///
+/// # Arguments
+///
+/// * `fields` - The column fields of the table.
+/// * `column` - The name of the result column.
+///
+/// # Returns
+///
+/// An `Option` containing the matching field, or `None` if no field matches.
+pub fn match_column_field<'f>(fields: &'f [String], column: &str) -> Option<&'f str> {
+    fields
+        .iter()
+        .find(|field| *field == column)
+        .or_else(|| fields.iter().find(|field| field.eq_ignore_ascii_case(column)))
+        .map(String::as_str)
+}
+
+/// Returns the number of top-level statements of raw SQL.
+///
+/// Statements are separated by semicolons outside of string literals, quoted
+/// identifiers and comments. The `BEGIN ... END` body of a `CREATE TRIGGER` is part
+/// of its statement, so its semicolons don't separate statements either. Empty
+/// statements, e.g. after a trailing semicolon, are not counted.
+///
+/// # Arguments
+///
+/// * `sql` - The raw SQL.
+pub fn statement_count(sql: &str) -> usize {
+    let mut count = 0;
+    let mut has_content = false;
+    let mut statement = StatementKeywords::default();
+    let mut word = String::new();
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            has_content = true;
+            continue;
+        }
+        statement.push(&word);
+        word.clear();
+
+        match c {
+            '\'' | '"' | '`' | '[' => {
+                let end = if c == '[' { ']' } else { c };
+                // a doubled quote is an escaped quote, which just continues the literal
+                for c in chars.by_ref() {
+                    if c == end {
+                        break;
+                    }
+                }
+                has_content = true;
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ';' if statement.in_trigger_body => {}
+            ';' => {
+                if has_content {
+                    count += 1;
+                }
+                has_content = false;
+                statement = StatementKeywords::default();
+            }
+            c if !c.is_whitespace() => has_content = true,
+            _ => {}
+        }
+    }
+
+    if has_content {
+        count += 1;
+    }
+
+    count
+}
+
+/// Returns the byte offsets of the `?` placeholders of raw SQL.
+///
+/// Like the semicolons of `statement_count`, question marks in string literals, quoted
+/// identifiers and comments are not placeholders.
+///
+/// # Arguments
+///
+/// * `sql` - The raw SQL.
+pub fn placeholder_offsets(sql: &str) -> Vec<usize> {
+    let mut offsets = Vec::new();
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' | '[' => {
+                let end = if c == '[' { ']' } else { c };
+                // a doubled quote is an escaped quote, which just continues the literal
+                for (_, c) in chars.by_ref() {
+                    if c == end {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek().map(|&(_, c)| c) == Some('-') => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek().map(|&(_, c)| c) == Some('*') => {
+                chars.next();
+                let mut previous = ' ';
+                for (_, c) in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '?' => offsets.push(offset),
+            _ => {}
+        }
+    }
+
+    offsets
+}
+
+/// The keywords of a statement that `statement_count` needs to find the end of a
+/// `CREATE TRIGGER` statement.
+#[derive(Default)]
+struct StatementKeywords {
+    words: usize,
+    is_create: bool,
+    is_trigger: bool,
+    in_trigger_body: bool,
+    case_depth: usize,
+}
+
+impl StatementKeywords {
+    /// Adds the next word of the statement outside of literals and comments.
+    fn push(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
+        let keyword = word.to_ascii_uppercase();
+        let index = self.words;
+        self.words += 1;
+
+        // CREATE [TEMP | TEMPORARY] TRIGGER
+        match index {
+            0 => self.is_create = keyword == "CREATE",
+            1 | 2 if self.is_create && keyword == "TRIGGER" => self.is_trigger = true,
+            _ => {}
+        }
+        if !self.is_trigger {
+            return;
+        }
+
+        // CASE expressions of the body end with END as well
+        match keyword.as_str() {
+            "BEGIN" if !self.in_trigger_body => self.in_trigger_body = true,
+            "CASE" if self.in_trigger_body => self.case_depth += 1,
+            "END" if self.in_trigger_body && self.case_depth > 0 => self.case_depth -= 1,
+            "END" if self.in_trigger_body => self.in_trigger_body = false,
+            _ => {}
+        }
+    }
+}
+
+/// Compresses the value of a `#[compress]` field with gzip.
+///
+/// # Arguments
+///
+/// * `value` - The bytes of the field value.
+pub fn compress(value: impl AsRef<[u8]>) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    // Writing to a `Vec` can't fail
+    encoder
+        .write_all(value.as_ref())
+        .and_then(|_| encoder.finish())
+        .unwrap_or_default()
+}
+
+/// Decompresses the stored value of a `#[compress]` field.
+///
+/// # Arguments
+///
+/// * `value` - The gzip compressed bytes.
+///
+/// # Returns
+///
+/// A `Result` containing the decompressed bytes, or an error message if the value is
+/// not gzip compressed.
+pub fn decompress(value: &[u8]) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    GzDecoder::new(value)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("failed to decompress value: {}", e))?;
+    Ok(bytes)
+}
+
+/// Represents how the rows of a joined table are matched.
+#[derive(Clone)]
+pub enum JoinConstraint<'a> {
+    /// ON condition - Matches rows for which the condition holds.
+    On(Condition<'a>),
+    /// USING (columns) - Matches rows with equal values in the columns shared by both tables.
+    Using(Vec<String>),
+}
+
 /// Represents a join operation in an SQL query.
 #[derive(Clone)]
 pub struct Join<'a> {
@@ -52,8 +478,10 @@ pub struct Join<'a> {
     pub join_type: JoinType,
     /// The table involved in the join.
     pub table: Arc<dyn Table>,
-    /// The condition specifying how the tables are joined.
-    pub on_condition: Condition<'a>,
+    /// Specifies how the tables are joined.
+    pub constraint: JoinConstraint<'a>,
+    /// The alias the joined table is referenced by, e.g. for self-joins.
+    pub alias: Option<String>,
 }
 
 impl<'a> Join<'a> {
@@ -74,6 +502,7 @@ impl<'a> Join<'a> {
     /// ```rust
     /// use std::sync::Arc;
     /// use njord::condition::{Condition, Value};
+    /// use njord::table::Table;
     /// use njord::util::{Join, JoinType};
     /// use njord_derive::Table;
     ///
@@ -106,7 +535,7 @@ impl<'a> Join<'a> {
     ///
     ///     let join_condition = Condition::Eq(
     ///         "categories.id".to_string(),
-    ///         Value::Literal("products.category_id".to_string()),
+    ///         Value::Column("products.category_id".to_string()),
     ///     );
     ///
     ///     let join = Join::new(
@@ -120,9 +549,58 @@ impl<'a> Join<'a> {
         Join {
             join_type,
             table,
-            on_condition,
+            constraint: JoinConstraint::On(on_condition),
+            alias: None,
+        }
+    }
+
+    /// Creates a new `Join` instance joining on the columns shared by both tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `join_type` - The type of join to perform, such as `Inner`, `Left`, `Right`, or `Full`.
+    /// * `table` - A reference to the table being joined, wrapped in an `Arc<dyn Table>` for shared ownership.
+    /// * `columns` - The columns that must be equal in both tables.
+    ///
+    /// # Returns
+    ///
+    /// A new `Join` instance representing the specified join operation.
+    pub fn using(join_type: JoinType, table: Arc<dyn Table>, columns: Vec<String>) -> Self {
+        Join {
+            join_type,
+            table,
+            constraint: JoinConstraint::Using(columns),
+            alias: None,
         }
     }
+
+    /// Sets the alias the joined table is referenced by in the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - The alias of the table, e.g. `b` for `users AS b`.
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.alias = Some(alias.to_string());
+        self
+    }
+
+    /// Returns the joined table as it appears in the JOIN clause, e.g. `users AS b`.
+    pub fn table_reference(&self) -> String {
+        table_reference(&self.table.get_qualified_name(), self.alias.as_deref())
+    }
+}
+
+/// Renders a table of a FROM or JOIN clause, with its alias if it has one.
+///
+/// # Arguments
+///
+/// * `table_name` - The qualified name of the table.
+/// * `alias` - The optional alias of the table.
+pub fn table_reference(table_name: &str, alias: Option<&str>) -> String {
+    match alias {
+        Some(alias) => format!("{} AS {}", table_name, alias),
+        None => table_name.to_string(),
+    }
 }
 
 /// Converts values for SQL INSERT
@@ -154,3 +632,60 @@ pub fn convert_insert_values(values: Vec<String>) -> Vec<String> {
 
     result
 }
+
+/// Returns the first selected column that is neither an aggregate nor listed in the
+/// GROUP BY columns, which strict databases reject.
+///
+/// Columns are matched with and without their table qualifier, so `users.name` is
+/// grouped by `name` and the other way around. Subqueries and literals are ignored.
+///
+/// # Arguments
+///
+/// * `columns` - The selected columns.
+/// * `group_by` - The GROUP BY columns.
+///
+/// # Returns
+///
+/// An `Option` containing the first ungrouped column, or `None` if the query is valid.
+pub fn ungrouped_column(columns: &[Column], group_by: &[String]) -> Option<String> {
+    const AGGREGATES: &[&str] = &[
+        "COUNT(",
+        "SUM(",
+        "AVG(",
+        "MIN(",
+        "MAX(",
+        "TOTAL(",
+        "GROUP_CONCAT(",
+    ];
+
+    let unqualified = |column: &str| column.rsplit('.').next().unwrap_or(column).to_string();
+    let is_grouped = |column: &str| {
+        group_by
+            .iter()
+            .any(|g| g == column || unqualified(g) == unqualified(column))
+    };
+
+    columns.iter().find_map(|column| {
+        let Column::Text(text) = column else {
+            return None;
+        };
+
+        // The alias of a computed column is not part of the grouped expression
+        let (expression, alias) = match text.rsplit_once(" AS ") {
+            Some((expression, alias)) => (expression.trim(), Some(alias.trim())),
+            None => (text.trim(), None),
+        };
+        let upper = expression.to_uppercase();
+
+        let is_aggregate = AGGREGATES
+            .iter()
+            .any(|aggregate| upper.starts_with(aggregate));
+        let is_literal = expression.parse::<f64>().is_ok() || expression.starts_with('\'');
+
+        if is_aggregate || is_literal || is_grouped(expression) || alias.is_some_and(is_grouped) {
+            None
+        } else {
+            Some(expression.to_string())
+        }
+    })
+}
@@ -77,7 +77,7 @@ fn select_inner_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "categories.id".to_string(),
-        Value::Literal("products.category_id".to_string()),
+        Value::Column("products.category_id".to_string()),
     );
     match conn {
         Ok(ref mut c) => {
@@ -142,7 +142,7 @@ fn select_left_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "categories.id".to_string(),
-        Value::Literal("products.category_id".to_string()),
+        Value::Column("products.category_id".to_string()),
     );
     match conn {
         Ok(ref mut c) => {
@@ -694,7 +694,7 @@ fn select_sub_queries() {
 
             match result {
                 Ok(r) => {
-                    assert!(r.len() > 0);
+                    assert!(!r.is_empty());
                     assert_eq!(r[0].additional_address, "SubQuery");
                 }
                 Err(e) => panic!("Failed to SELECT: {:?}", e),
@@ -679,7 +679,7 @@ async fn select_sub_queries() {
 
             match result {
                 Ok(r) => {
-                    assert!(r.len() > 0);
+                    assert!(!r.is_empty());
                     assert_eq!(r[0].additional_address, "SubQuery");
                 }
                 Err(e) => panic!("Failed to SELECT: {:?}", e),
@@ -0,0 +1,43 @@
+use njord::condition::Value;
+use njord::oracle;
+
+#[test]
+fn call_proc_returns_every_cursor() {
+    let connection_string = "//localhost:1521/FREEPDB1";
+    let conn = oracle::open("njord_user", "njord_password", connection_string).unwrap();
+
+    oracle::raw_execute(
+        &conn,
+        "CREATE OR REPLACE PROCEDURE njord_two_cursors(max_n IN NUMBER) AS
+            numbers SYS_REFCURSOR;
+            names SYS_REFCURSOR;
+        BEGIN
+            OPEN numbers FOR SELECT LEVEL AS n FROM dual CONNECT BY LEVEL <= max_n;
+            DBMS_SQL.RETURN_RESULT(numbers);
+            OPEN names FOR SELECT 'njord' AS name FROM dual;
+            DBMS_SQL.RETURN_RESULT(names);
+        END;",
+    )
+    .unwrap();
+
+    let result_sets = oracle::call_proc(
+        &conn,
+        "njord_two_cursors",
+        vec![Value::Literal("3".to_string())],
+    )
+    .unwrap();
+
+    assert_eq!(result_sets.len(), 2);
+
+    let numbers: Vec<i64> = result_sets[0]
+        .iter()
+        .map(|row| row.get(0).unwrap())
+        .collect();
+    assert_eq!(numbers, vec![1, 2, 3]);
+
+    let names: Vec<String> = result_sets[1]
+        .iter()
+        .map(|row| row.get(0).unwrap())
+        .collect();
+    assert_eq!(names, vec!["njord"]);
+}
@@ -1,3 +1,4 @@
+mod call_proc_test;
 mod delete_test;
 mod insert_test;
 mod open_test;
@@ -1,8 +1,31 @@
 use njord::oracle;
 
+use std::time::{Duration, Instant};
+
 #[test]
 fn open_db() {
     let connection_string = "//localhost:1521/FREEPDB1";
     let conn = oracle::open("njord_user", "njord_password", connection_string);
     assert!(conn.is_ok());
 }
+
+#[test]
+fn blocking_query_times_out() {
+    let connection_string = "//localhost:1521/FREEPDB1";
+    let conn = oracle::open_with_call_timeout(
+        "njord_user",
+        "njord_password",
+        connection_string,
+        Duration::from_secs(1),
+    )
+    .unwrap();
+
+    let started = Instant::now();
+    let result = oracle::raw_execute(&conn, "BEGIN DBMS_SESSION.SLEEP(5); END;");
+
+    match result {
+        Err(err) => assert!(matches!(njord::Error::from(err), njord::Error::Timeout(_))),
+        Ok(_) => panic!("Expected the blocking query to time out"),
+    }
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -653,7 +653,7 @@ fn select_sub_queries() {
 
             match result {
                 Ok(r) => {
-                    assert!(r.len() > 0);
+                    assert!(!r.is_empty());
                     assert_eq!(r[0].additional_address, "SubQuery");
                 }
                 Err(e) => panic!("Failed to SELECT: {:?}", e),
@@ -768,3 +768,23 @@ fn raw_execute() {
         Err(e) => panic!("Failed to SELECT: {:?}", e),
     };
 }
+
+#[test]
+fn select_limit_with_ties() {
+    let columns = vec![
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+    ];
+    let mut order_by = HashMap::new();
+    order_by.insert(vec!["email".to_string()], "ASC".to_string());
+
+    let query = oracle::select::<User>(columns)
+        .from(User::default())
+        .order_by(order_by)
+        .limit_with_ties(3);
+
+    assert!(query
+        .build_query()
+        .trim_end()
+        .ends_with("ORDER BY email ASC FETCH FIRST 3 ROWS WITH TIES"));
+}
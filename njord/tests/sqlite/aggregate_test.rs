@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::Connection;
+
+#[derive(Table)]
+#[table_name = "products"]
+pub struct DescriptionCount {
+    pub description_count: usize,
+}
+
+#[derive(Table)]
+#[table_name = "products"]
+pub struct CategoryDescriptionCount {
+    pub category_id: usize,
+    pub description_count: usize,
+}
+
+#[derive(Table)]
+#[table_name = "products"]
+pub struct CategoryProductCount {
+    pub category_id: usize,
+    pub product_count: usize,
+}
+
+fn setup() -> Connection {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE products (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            category_id INTEGER NOT NULL
+        );
+        INSERT INTO products (name, description, category_id) VALUES
+            ('Apple', 'Fruit', 1),
+            ('Banana', 'Fruit', 1),
+            ('Carrot', 'Vegetable', 1),
+            ('Hammer', 'Tool', 2),
+            ('Wrench', 'Tool', 2);",
+    )
+    .expect("Failed to create products");
+
+    conn
+}
+
+#[test]
+fn select_count_distinct() {
+    let conn = setup();
+
+    let query = sqlite::select(vec![Column::count_distinct("description")])
+        .from(DescriptionCount::default());
+    assert_eq!(
+        query
+            .build_query()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        "SELECT COUNT(DISTINCT description) FROM products"
+    );
+
+    match query.build(&conn) {
+        Ok(r) => assert_eq!(r[0].description_count, 3),
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_count_distinct_group_by() {
+    let conn = setup();
+
+    let result = sqlite::select(vec![
+        Column::Text("category_id".to_string()),
+        Column::count_distinct("description"),
+    ])
+    .from(CategoryDescriptionCount::default())
+    .group_by(vec!["category_id".to_string()])
+    .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 2);
+            assert_eq!(r[0].category_id, 1);
+            assert_eq!(r[0].description_count, 2);
+            assert_eq!(r[1].category_id, 2);
+            assert_eq!(r[1].description_count, 1);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_having_aggregate() {
+    let conn = setup();
+
+    let query = sqlite::select(vec![
+        Column::Text("category_id".to_string()),
+        Column::count("*"),
+    ])
+    .from(CategoryProductCount::default())
+    .group_by(vec!["category_id".to_string()])
+    .having(Condition::gt(
+        Column::count("*"),
+        Value::Literal("2".to_string()),
+    ));
+
+    assert!(query.build_query().contains("HAVING COUNT(*) > 2"));
+
+    match query.build(&conn) {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].category_id, 1);
+            assert_eq!(r[0].product_count, 3);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[derive(Table, Clone)]
+#[table_name = "payments"]
+pub struct Payment {
+    pub id: usize,
+    pub description: String,
+    pub amount: f64,
+}
+
+#[derive(Table)]
+#[table_name = "payments"]
+pub struct Summary {
+    pub description: String,
+    pub total: f64,
+}
+
+#[test]
+fn select_group_by_build_into() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE payments (
+            id INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            amount REAL NOT NULL
+        );
+        INSERT INTO payments (description, amount) VALUES
+            ('rent', 1000.0),
+            ('food', 25.5),
+            ('food', 14.5),
+            ('rent', 200.0);",
+    )
+    .expect("Failed to create payments");
+
+    let result = sqlite::select(vec![
+        Column::Text("description".to_string()),
+        Column::Text("SUM(amount) AS total".to_string()),
+    ])
+    .from(Payment::default())
+    .group_by(vec!["description".to_string()])
+    .order_by(HashMap::from([(
+        vec!["description".to_string()],
+        "ASC".to_string(),
+    )]))
+    .build_into::<Summary>(&conn);
+
+    match result {
+        Ok(summaries) => {
+            let totals: Vec<(&str, f64)> = summaries
+                .iter()
+                .map(|s| (s.description.as_str(), s.total))
+                .collect();
+            assert_eq!(totals, vec![("food", 40.0), ("rent", 1200.0)]);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[derive(Table, Clone)]
+#[table_name = "products"]
+pub struct CategoryDescription {
+    pub category_id: usize,
+    pub description: String,
+    pub product_count: usize,
+}
+
+#[test]
+fn strict_group_by_rejects_ungrouped_column() {
+    let conn = setup();
+
+    let query = sqlite::select(vec![
+        Column::Text("category_id".to_string()),
+        Column::Text("description".to_string()),
+        Column::count("*"),
+    ])
+    .from(CategoryDescription::default())
+    .group_by(vec!["category_id".to_string()]);
+
+    // SQLite itself accepts the ungrouped column
+    assert!(query.clone().build(&conn).is_ok());
+
+    match query.strict_group_by().build(&conn) {
+        Err(njord::Error::InvalidGroupBy(column)) => assert_eq!(column, "description"),
+        other => panic!("Expected InvalidGroupBy, got {:?}", other.map(|r| r.len())),
+    }
+}
+
+#[test]
+fn strict_group_by_accepts_grouped_and_aggregate_columns() {
+    let conn = setup();
+
+    let result = sqlite::select(vec![
+        Column::Text("products.category_id".to_string()),
+        Column::expr("COUNT(*)", vec![], "product_count"),
+    ])
+    .from(CategoryProductCount::default())
+    .group_by(vec!["category_id".to_string()])
+    .strict_group_by()
+    .build(&conn);
+
+    assert_eq!(result.unwrap().len(), 2);
+}
+
+#[test]
+fn order_by_aggregate_alias() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE payments (
+            id INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            amount REAL NOT NULL
+        );
+        INSERT INTO payments (description, amount) VALUES
+            ('food', 25.5),
+            ('rent', 1000.0),
+            ('fuel', 60.0),
+            ('food', 14.5);",
+    )
+    .expect("Failed to create payments");
+
+    let query = sqlite::select(vec![
+        Column::Text("description".to_string()),
+        Column::sum("amount").alias("total"),
+    ])
+    .from(Payment::default())
+    .group_by(vec!["description".to_string()])
+    .order_by(HashMap::from([(
+        vec!["total".to_string()],
+        "DESC".to_string(),
+    )]));
+
+    let sql = query.build_query();
+    assert!(sql.contains("SUM(amount) AS total"));
+    assert!(sql.contains("ORDER BY \"total\" DESC"));
+
+    match query.build_into::<Summary>(&conn) {
+        Ok(summaries) => {
+            let descriptions: Vec<&str> =
+                summaries.iter().map(|s| s.description.as_str()).collect();
+            assert_eq!(descriptions, vec!["rent", "fuel", "food"]);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn order_by_unknown_alias_is_rejected() {
+    let conn = setup();
+
+    let result = sqlite::select(vec![
+        Column::Text("category_id".to_string()),
+        Column::count("*").alias("product_count"),
+    ])
+    .from(CategoryProductCount::default())
+    .group_by(vec!["category_id".to_string()])
+    .order_by(HashMap::from([(
+        vec!["products_count".to_string()],
+        "DESC".to_string(),
+    )]))
+    .build(&conn);
+
+    match result {
+        Err(njord::Error::InvalidQuery(message)) => assert!(message.contains("products_count")),
+        other => panic!("Expected InvalidQuery, got {:?}", other.map(|r| r.len())),
+    }
+}
+
+#[test]
+fn build_grouped_returns_sums_by_group() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE payments (
+            id INTEGER PRIMARY KEY,
+            description TEXT NOT NULL,
+            amount REAL NOT NULL
+        );
+        INSERT INTO payments (description, amount) VALUES
+            ('rent', 1000.0),
+            ('food', 25.5),
+            ('food', 14.5),
+            ('rent', 200.0),
+            ('fuel', 60.0);",
+    )
+    .expect("Failed to create payments");
+
+    let query = sqlite::select(vec![
+        Column::Text("description".to_string()),
+        Column::sum("amount").alias("total"),
+    ])
+    .from(Payment::default())
+    .group_by(vec!["description".to_string()]);
+
+    let groups = query
+        .clone()
+        .build_grouped::<Summary>(&conn, "description")
+        .unwrap();
+
+    let mut totals: Vec<(String, f64)> = groups
+        .iter()
+        .map(|(key, summary)| (key.to_string(), summary.total))
+        .collect();
+    totals.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        totals,
+        vec![
+            ("food".to_string(), 40.0),
+            ("fuel".to_string(), 60.0),
+            ("rent".to_string(), 1200.0)
+        ]
+    );
+    assert_eq!(
+        groups[&Value::Literal("rent".to_string())].description,
+        "rent"
+    );
+
+    match query.build_grouped::<Summary>(&conn, "total") {
+        Err(njord::Error::InvalidGroupBy(column)) => assert_eq!(column, "total"),
+        other => panic!("Expected InvalidGroupBy, got {:?}", other.map(|g| g.len())),
+    }
+}
+
+#[derive(Table)]
+#[table_name = "payments"]
+pub struct OptionalSummary {
+    pub description: Option<String>,
+    pub total: f64,
+}
+
+#[test]
+fn build_grouped_keeps_null_apart_from_empty_string() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE payments (
+            id INTEGER PRIMARY KEY,
+            description TEXT,
+            amount REAL NOT NULL
+        );
+        INSERT INTO payments (description, amount) VALUES
+            (NULL, 10.0),
+            ('', 2.5),
+            (NULL, 5.0),
+            ('rent', 1000.0);",
+    )
+    .expect("Failed to create payments");
+
+    let groups = sqlite::select(vec![
+        Column::Text("description".to_string()),
+        Column::sum("amount").alias("total"),
+    ])
+    .from(Payment::default())
+    .group_by(vec!["description".to_string()])
+    .build_grouped::<OptionalSummary>(&conn, "description")
+    .unwrap();
+
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[&Value::Null].total, 15.0);
+    assert_eq!(groups[&Value::Null].description, None);
+    assert_eq!(groups[&Value::Literal(String::new())].total, 2.5);
+    assert_eq!(groups[&Value::Literal("rent".to_string())].total, 1000.0);
+}
@@ -0,0 +1,95 @@
+use njord::schema::ColumnDef;
+use njord::sqlite;
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE people (id INTEGER PRIMARY KEY, name TEXT NOT NULL, nick TEXT);
+        INSERT INTO people (id, name, nick) VALUES (1, 'Marcus', 'mjovanc'), (2, 'Anna', NULL);",
+    )
+    .unwrap();
+    conn
+}
+
+fn column_names(conn: &rusqlite::Connection) -> Vec<String> {
+    sqlite::table_columns(conn, "people")
+        .unwrap()
+        .into_iter()
+        .map(|column| column.name)
+        .collect()
+}
+
+#[test]
+fn add_column_appears_in_table_info() {
+    let conn = setup();
+
+    sqlite::alter_table(&conn, "people")
+        .add_column(ColumnDef {
+            name: "age".to_string(),
+            data_type: "INTEGER".to_string(),
+            nullable: false,
+            default_value: Some("0".to_string()),
+            primary_key: false,
+        })
+        .build()
+        .unwrap();
+
+    let columns = sqlite::table_columns(&conn, "people").unwrap();
+    let age = columns.iter().find(|column| column.name == "age").unwrap();
+    assert_eq!(age.data_type, "INTEGER");
+    assert!(!age.nullable);
+    assert_eq!(age.default_value.as_deref(), Some("0"));
+}
+
+#[test]
+fn rename_and_drop_columns() {
+    let conn = setup();
+
+    sqlite::alter_table(&conn, "people")
+        .rename_column("name", "full_name")
+        .drop_column("nick")
+        .build()
+        .unwrap();
+
+    assert_eq!(column_names(&conn), vec!["id", "full_name"]);
+}
+
+#[test]
+fn drop_primary_key_column_rebuilds_table() {
+    let conn = setup();
+
+    let statements = sqlite::alter_table(&conn, "people")
+        .drop_column("id")
+        .build_statements()
+        .unwrap();
+    assert!(statements[0].starts_with("CREATE TABLE \"njord_new_people\""));
+
+    sqlite::alter_table(&conn, "people")
+        .drop_column("id")
+        .build()
+        .unwrap();
+
+    assert_eq!(column_names(&conn), vec!["name", "nick"]);
+    let names: Vec<String> = conn
+        .prepare("SELECT name FROM people ORDER BY name")
+        .unwrap()
+        .query_map((), |row| row.get(0))
+        .unwrap()
+        .collect::<rusqlite::Result<Vec<String>>>()
+        .unwrap();
+    assert_eq!(names, vec!["Anna", "Marcus"]);
+}
+
+#[test]
+fn failing_change_applies_nothing() {
+    let conn = setup();
+
+    let result = sqlite::alter_table(&conn, "people")
+        .rename_column("name", "full_name")
+        .drop_column("missing")
+        .build();
+
+    assert!(result.is_err());
+    assert_eq!(column_names(&conn), vec!["id", "name", "nick"]);
+}
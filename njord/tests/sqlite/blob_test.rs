@@ -0,0 +1,116 @@
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "attachments"]
+pub struct Attachment {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+    pub data: Vec<u8>,
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+#[test]
+fn blob_columns_round_trip() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Attachment::default())]).expect("Failed to init tables");
+
+    // Every byte value, including invalid UTF-8 and NUL bytes
+    let data: Vec<u8> = (0..=255).collect();
+
+    let attachment = Attachment {
+        id: AutoIncrementPrimaryKey::default(),
+        name: "image.png".to_string(),
+        data: data.clone(),
+        thumbnail: Some(vec![0x89, 0x50, 0x4e, 0x47, 0x00, 0xff]),
+    };
+    sqlite::insert(&conn, vec![attachment]).expect("Failed to INSERT");
+
+    let result = sqlite::select_all::<Attachment>()
+        .from(Attachment::default())
+        .build(&conn)
+        .expect("Failed to SELECT");
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].data, data);
+    assert_eq!(
+        result[0].thumbnail,
+        Some(vec![0x89, 0x50, 0x4e, 0x47, 0x00, 0xff])
+    );
+}
+
+#[test]
+fn blob_column_type() {
+    let columns = Attachment::default().get_columns();
+
+    assert_eq!(columns.get("data").map(String::as_str), Some("BLOB"));
+    assert_eq!(
+        columns.get("thumbnail").map(String::as_str),
+        Some("BLOB NULL")
+    );
+}
+
+#[test]
+fn blob_columns_update_round_trip() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Attachment::default())]).expect("Failed to init tables");
+
+    let attachment = Attachment {
+        id: AutoIncrementPrimaryKey::default(),
+        name: "image.png".to_string(),
+        data: vec![0xff],
+        thumbnail: Some(vec![0xff]),
+    };
+    sqlite::insert(&conn, vec![attachment]).expect("Failed to INSERT");
+
+    // Hex that looks like a number, `0012` and `1e10`, must still be stored as a BLOB
+    let attachment = Attachment {
+        id: AutoIncrementPrimaryKey::new(Some(1)),
+        name: "image.png".to_string(),
+        data: vec![0x00, 0x12],
+        thumbnail: Some(vec![0x1e, 0x10]),
+    };
+    sqlite::update(attachment)
+        .set(vec!["data".to_string(), "thumbnail".to_string()])
+        .where_clause(Condition::eq("id", Value::Literal("1".to_string())))
+        .build(&conn)
+        .expect("Failed to UPDATE");
+
+    let types: (String, String) = conn
+        .query_row(
+            "SELECT typeof(data), typeof(thumbnail) FROM attachments",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(types, ("blob".to_string(), "blob".to_string()));
+
+    let result = sqlite::select_all::<Attachment>()
+        .from(Attachment::default())
+        .build(&conn)
+        .expect("Failed to SELECT");
+    assert_eq!(result[0].data, vec![0x00, 0x12]);
+    assert_eq!(result[0].thumbnail, Some(vec![0x1e, 0x10]));
+
+    // A NULL BLOB is set to NULL
+    let attachment = Attachment {
+        id: AutoIncrementPrimaryKey::new(Some(1)),
+        name: "image.png".to_string(),
+        data: vec![0x00, 0x12],
+        thumbnail: None,
+    };
+    sqlite::update(attachment)
+        .set(vec!["thumbnail".to_string()])
+        .where_clause(Condition::eq("id", Value::Literal("1".to_string())))
+        .build(&conn)
+        .expect("Failed to UPDATE");
+
+    let result = sqlite::select_all::<Attachment>()
+        .from(Attachment::default())
+        .build(&conn)
+        .expect("Failed to SELECT");
+    assert_eq!(result[0].thumbnail, None);
+}
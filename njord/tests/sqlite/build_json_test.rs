@@ -0,0 +1,77 @@
+use njord::column::Column;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use serde_json::json;
+
+#[derive(Table, Clone)]
+#[table_name = "attachments"]
+pub struct Attachment {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+    pub size: f64,
+    pub data: Vec<u8>,
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+#[test]
+fn build_json_returns_objects_by_column() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Attachment::default())]).expect("Failed to init tables");
+
+    sqlite::insert(
+        &conn,
+        vec![Attachment {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "image.png".to_string(),
+            size: 1.5,
+            data: vec![0x89, 0x50, 0x4e, 0x47],
+            thumbnail: Some(vec![0x00, 0xff]),
+        }],
+    )
+    .expect("Failed to INSERT");
+    sqlite::insert(
+        &conn,
+        vec![Attachment {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "notes.txt".to_string(),
+            size: 0.25,
+            data: b"hello".to_vec(),
+            thumbnail: None,
+        }],
+    )
+    .expect("Failed to INSERT");
+
+    let columns = vec![
+        Column::Text("id".to_string()),
+        Column::Text("name".to_string()),
+        Column::Text("size".to_string()),
+        Column::Text("data".to_string()),
+        Column::Text("thumbnail".to_string()),
+    ];
+    let rows = sqlite::select(columns)
+        .from(Attachment::default())
+        .build_json(&conn)
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        json!([
+            {
+                "id": 1,
+                "name": "image.png",
+                "size": 1.5,
+                "data": "iVBORw==",
+                "thumbnail": "AP8=",
+            },
+            {
+                "id": 2,
+                "name": "notes.txt",
+                "size": 0.25,
+                "data": "aGVsbG8=",
+                "thumbnail": null,
+            },
+        ])
+    );
+}
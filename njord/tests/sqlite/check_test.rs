@@ -0,0 +1,46 @@
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord::Error;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "payments"]
+#[check("amount <= limit_amount")]
+pub struct Payment {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[check("amount >= 0")]
+    pub amount: f64,
+    pub limit_amount: f64,
+}
+
+fn payment(amount: f64) -> Payment {
+    Payment {
+        id: AutoIncrementPrimaryKey::default(),
+        amount,
+        limit_amount: 100.0,
+    }
+}
+
+#[test]
+fn create_table_statement_with_checks() {
+    assert_eq!(
+        sqlite::init::generate_create_table_statement(&Payment::default()),
+        "CREATE TABLE IF NOT EXISTS payments (\"id\" INTEGER PRIMARY KEY AUTOINCREMENT, \"amount\" REAL, \
+         \"limit_amount\" REAL, CHECK (amount <= limit_amount), CHECK (amount >= 0));"
+    );
+}
+
+#[test]
+fn insert_violating_check_fails() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Payment::default())]).expect("Failed to init tables");
+
+    assert!(sqlite::insert(&conn, vec![payment(25.0)]).is_ok());
+
+    let negative = sqlite::insert(&conn, vec![payment(-1.0)]);
+    assert!(matches!(negative, Err(Error::Conflict(_))));
+
+    let over_limit = sqlite::insert(&conn, vec![payment(150.0)]);
+    assert!(matches!(over_limit, Err(Error::Conflict(_))));
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord::util::collate;
+use njord::Error;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "tags"]
+pub struct Tag {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[collation = "NOCASE"]
+    pub name: String,
+}
+
+fn tag(name: &str) -> Tag {
+    Tag {
+        id: AutoIncrementPrimaryKey::default(),
+        name: name.to_string(),
+    }
+}
+
+#[test]
+fn nocase_unique_column_rejects_case_differing_duplicate() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Tag::default())]).expect("Failed to init tables");
+    sqlite::raw_execute(&conn, "CREATE UNIQUE INDEX idx_tags_name ON tags (name);").unwrap();
+
+    assert_eq!(
+        Tag::default().get_columns().get("name").map(String::as_str),
+        Some("TEXT COLLATE NOCASE")
+    );
+
+    sqlite::insert(&conn, vec![tag("Rust")]).expect("Failed to INSERT");
+    let result = sqlite::insert(&conn, vec![tag("rust")]);
+
+    assert!(matches!(result, Err(Error::Conflict(_))));
+}
+
+#[test]
+fn order_by_collate() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE tags (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT);",
+    )
+    .unwrap();
+    sqlite::insert(&conn, vec![tag("b"), tag("C"), tag("a")]).expect("Failed to INSERT");
+
+    let mut order = HashMap::new();
+    order.insert(vec![collate("name", "NOCASE")], "ASC".to_string());
+
+    let result = sqlite::select_all::<Tag>()
+        .from(Tag::default())
+        .order_by(order)
+        .build(&conn)
+        .expect("Failed to SELECT");
+
+    let names: Vec<&str> = result.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["a", "b", "C"]);
+}
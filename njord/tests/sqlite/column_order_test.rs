@@ -0,0 +1,68 @@
+use njord::column::Column;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "expenses"]
+pub struct Expense {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    pub description: String,
+    pub amount: f64,
+}
+
+#[test]
+fn insert_and_select_all_map_columns_by_field() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    // The stored columns are in alphabetical order, not in field declaration order
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE expenses (
+            amount REAL NOT NULL,
+            description TEXT NOT NULL,
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            title TEXT NOT NULL
+        );",
+    )
+    .unwrap();
+
+    let expense = Expense {
+        id: AutoIncrementPrimaryKey::default(),
+        title: "Lunch".to_string(),
+        description: "Team lunch".to_string(),
+        amount: 42.5,
+    };
+
+    sqlite::insert(&conn, vec![expense]).unwrap();
+
+    let expected = vec!["1", "Lunch", "Team lunch", "42.5"];
+
+    let result = sqlite::select_all::<Expense>()
+        .from(Expense::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].get_column_values(), expected);
+
+    let result = sqlite::select::<Expense>(vec![Column::all()])
+        .from(Expense::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(result[0].get_column_values(), expected);
+}
+
+#[test]
+fn select_all_uses_field_declaration_order() {
+    let query = sqlite::select_all::<Expense>()
+        .from(Expense::default())
+        .build_query();
+
+    assert!(
+        query.starts_with("SELECT \"id\", \"title\", \"description\", \"amount\" FROM expenses")
+    );
+}
@@ -0,0 +1,140 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::types::Value as SqliteValue;
+use rusqlite::Connection;
+
+#[derive(Table)]
+#[table_name = "order_lines"]
+pub struct OrderLineSubtotal {
+    pub product: String,
+    pub subtotal: f64,
+}
+
+fn setup() -> Connection {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE order_lines (
+            id INTEGER PRIMARY KEY,
+            product TEXT NOT NULL,
+            price REAL NOT NULL,
+            quantity INTEGER NOT NULL
+        );
+        INSERT INTO order_lines (product, price, quantity) VALUES
+            ('Apple', 1.5, 4),
+            ('Banana', 0.25, 10);",
+    )
+    .expect("Failed to create order_lines");
+
+    conn
+}
+
+#[test]
+fn select_expression_column() {
+    let conn = setup();
+
+    let query = sqlite::select(vec![
+        Column::Text("product".to_string()),
+        Column::expr("price * quantity", vec![], "subtotal"),
+    ])
+    .from(OrderLineSubtotal::default());
+
+    assert!(query
+        .build_query()
+        .starts_with("SELECT \"product\", price * quantity AS subtotal FROM order_lines"));
+
+    match query.build(&conn) {
+        Ok(r) => {
+            assert_eq!(r.len(), 2);
+            assert_eq!(r[0].product, "Apple");
+            assert_eq!(r[0].subtotal, 6.0);
+            assert_eq!(r[1].product, "Banana");
+            assert_eq!(r[1].subtotal, 2.5);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_expression_column_with_bound_values() {
+    let conn = setup();
+
+    let query = sqlite::select(vec![
+        Column::Text("product".to_string()),
+        Column::expr(
+            "price * quantity * ?",
+            vec![Value::Literal("2".to_string())],
+            "subtotal",
+        ),
+    ])
+    .from(OrderLineSubtotal::default())
+    .where_clause(Condition::eq(
+        "product",
+        Value::Literal("Banana".to_string()),
+    ));
+
+    // The value of the column is bound before the value of the condition
+    let (sql, params) = query.to_sql_with_params();
+    assert!(
+        sql.starts_with("SELECT \"product\", price * quantity * ? AS subtotal FROM order_lines")
+    );
+    assert_eq!(
+        params.iter().map(|v| v.to_string()).collect::<Vec<String>>(),
+        vec!["2", "Banana"]
+    );
+
+    let r = query.build(&conn).unwrap();
+    assert_eq!(r.len(), 1);
+    assert_eq!(r[0].product, "Banana");
+    assert_eq!(r[0].subtotal, 5.0);
+}
+
+#[derive(Table, Clone)]
+#[table_name = "codes"]
+pub struct Code {
+    pub label: String,
+    pub number: i64,
+}
+
+#[test]
+fn select_cast_column() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE codes (label TEXT NOT NULL, code TEXT NOT NULL);
+        INSERT INTO codes (label, code) VALUES ('nine', '9'), ('ten', '10'), ('two', '2');",
+    )
+    .expect("Failed to create codes");
+
+    // compared as text, '10' sorts before '9'
+    let query = sqlite::select(vec![
+        Column::Text("label".to_string()),
+        Column::cast("code", "int").alias("number"),
+    ])
+    .from(Code::default())
+    .where_clause(Condition::gt(
+        Column::cast("code", "INTEGER"),
+        Value::Literal("5".to_string()),
+    ));
+
+    let sql = query.build_query();
+    assert!(sql.starts_with("SELECT \"label\", CAST(code AS INTEGER) AS number FROM codes"));
+    assert!(sql.contains("WHERE CAST(code AS INTEGER) > 5"));
+
+    let rows = query.clone().build_raw(&conn).expect("Failed to SELECT");
+    assert!(rows
+        .iter()
+        .all(|row| matches!(row[1], SqliteValue::Integer(_))));
+
+    match query.build(&conn) {
+        Ok(r) => {
+            let numbers: Vec<i64> = r.iter().map(|c| c.number).collect();
+            assert_eq!(numbers, vec![9, 10]);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
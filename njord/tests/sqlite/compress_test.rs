@@ -0,0 +1,101 @@
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "documents"]
+pub struct Document {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    #[compress]
+    pub body: String,
+    #[compress]
+    pub attachment: Vec<u8>,
+}
+
+fn stored_size(conn: &rusqlite::Connection, column: &str) -> usize {
+    conn.query_row(
+        &format!("SELECT length({}) FROM documents", column),
+        [],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+fn stored_type(conn: &rusqlite::Connection, column: &str) -> String {
+    conn.query_row(
+        &format!("SELECT typeof({}) FROM documents", column),
+        [],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn compressed_column_type() {
+    let columns = Document::default().get_columns();
+
+    assert_eq!(columns.get("body").map(String::as_str), Some("BLOB"));
+    assert_eq!(columns.get("attachment").map(String::as_str), Some("BLOB"));
+}
+
+#[test]
+fn compressed_fields_round_trip() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Document::default())]).unwrap();
+
+    let body = "All work and no play makes Jack a dull boy. ".repeat(1000);
+    let attachment = vec![0u8; 4096];
+    let document = Document {
+        id: AutoIncrementPrimaryKey::default(),
+        title: "notes".to_string(),
+        body: body.clone(),
+        attachment: attachment.clone(),
+    };
+    sqlite::insert(&conn, vec![document]).unwrap();
+
+    assert!(stored_size(&conn, "body") < body.len());
+    assert!(stored_size(&conn, "attachment") < attachment.len());
+    assert_eq!(stored_type(&conn, "body"), "blob");
+    assert_eq!(stored_type(&conn, "attachment"), "blob");
+
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].body, body);
+    assert_eq!(documents[0].attachment, attachment);
+}
+
+#[test]
+fn compressed_field_is_updated() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Document::default())]).unwrap();
+    sqlite::insert(&conn, vec![Document::default()]).unwrap();
+    assert_eq!(stored_type(&conn, "body"), "blob");
+
+    let body = "updated ".repeat(500);
+    let document = Document {
+        body: body.clone(),
+        ..Document::default()
+    };
+    sqlite::update(document)
+        .set(vec!["body".to_string()])
+        .where_clause(Condition::eq("id", Value::Literal("1".to_string())))
+        .build(&conn)
+        .unwrap();
+
+    assert!(stored_size(&conn, "body") < body.len());
+    assert_eq!(stored_type(&conn, "body"), "blob");
+
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(documents[0].body, body);
+}
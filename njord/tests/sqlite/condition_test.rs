@@ -0,0 +1,303 @@
+use njord::condition::{Condition, Value};
+use njord::error::Error;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "documents"]
+pub struct Document {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub tenant_id: usize,
+    pub title: String,
+}
+
+#[test]
+fn reuse_condition_across_builders() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER, title TEXT);
+        INSERT INTO documents (tenant_id, title) VALUES
+            (1, 'invoice'), (1, 'draft'), (2, 'invoice'), (2, 'draft');",
+    )
+    .unwrap();
+
+    let tenant = Condition::eq("tenant_id", Value::Literal("1".to_string()));
+
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(&tenant)
+        .build(&conn)
+        .unwrap();
+    assert_eq!(documents.len(), 2);
+
+    let drafts = tenant
+        .clone()
+        .and(Condition::eq("title", Value::Literal("draft".to_string())));
+    sqlite::delete()
+        .from(Document::default())
+        .where_clause(&drafts)
+        .build(&conn)
+        .unwrap();
+
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(&tenant)
+        .build(&conn)
+        .unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].title, "invoice");
+
+    // the other tenant is untouched
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .build(&conn)
+        .unwrap();
+    assert_eq!(documents.len(), 3);
+}
+
+#[test]
+fn filter_on_two_column_tuple() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER, title TEXT);
+        INSERT INTO documents (tenant_id, title) VALUES
+            (1, 'invoice'), (1, 'draft'), (2, 'invoice'), (2, 'draft');",
+    )
+    .unwrap();
+
+    let condition = Condition::in_tuple(
+        &["tenant_id", "title"],
+        vec![
+            vec![
+                Value::Literal("1".to_string()),
+                Value::Literal("draft".to_string()),
+            ],
+            vec![
+                Value::Literal("2".to_string()),
+                Value::Literal("invoice".to_string()),
+            ],
+        ],
+    )
+    .unwrap();
+
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(condition)
+        .build(&conn)
+        .unwrap();
+
+    let mut matched: Vec<(usize, String)> = documents
+        .into_iter()
+        .map(|document| (document.tenant_id, document.title))
+        .collect();
+    matched.sort();
+    assert_eq!(
+        matched,
+        vec![(1, "draft".to_string()), (2, "invoice".to_string())]
+    );
+}
+
+#[test]
+fn tuple_arity_must_match_columns() {
+    let result = Condition::in_tuple(
+        &["tenant_id", "title"],
+        vec![vec![Value::Literal("1".to_string())]],
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn mix_structured_and_raw_where() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER, title TEXT);
+        INSERT INTO documents (tenant_id, title) VALUES
+            (1, 'invoice'), (1, 'draft'), (2, 'invoice'), (2, 'draft');",
+    )
+    .unwrap();
+
+    let query = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(Condition::eq("tenant_id", Value::Literal("2".to_string())))
+        .raw_where("length(title) > ?", vec![Value::Literal("5".to_string())]);
+
+    let (sql, params) = query.to_sql_with_params();
+    assert!(sql.contains("WHERE (\"tenant_id\" = ?) AND (length(title) > ?)"));
+    assert_eq!(
+        params.iter().map(|v| v.to_string()).collect::<Vec<String>>(),
+        vec!["2", "5"]
+    );
+
+    let documents = query.build(&conn).unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].tenant_id, 2);
+    assert_eq!(documents[0].title, "invoice");
+}
+
+#[test]
+fn raw_where_binds_values_with_quotes() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER, title TEXT);
+        INSERT INTO documents (tenant_id, title) VALUES
+            (1, 'O''Brien'), (1, 'draft'), (2, 'invoice');",
+    )
+    .unwrap();
+
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .raw_where("title = ?", vec![Value::Literal("O'Brien".to_string())])
+        .build(&conn)
+        .unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].title, "O'Brien");
+
+    // The value is bound, so it can't change the condition
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .raw_where(
+            "title = ?",
+            vec![Value::Literal("x' OR '1'='1".to_string())],
+        )
+        .build(&conn)
+        .unwrap();
+    assert!(documents.is_empty());
+}
+
+#[test]
+fn contains_matches_wildcards_literally() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER, title TEXT);
+        INSERT INTO documents (tenant_id, title) VALUES
+            (1, '100% done'), (1, '100 done'), (1, 'file_name'), (1, 'filename'), (1, 'it''s 5%');",
+    )
+    .unwrap();
+
+    let titles = |condition: Condition<'static>| {
+        sqlite::select_all::<Document>()
+            .from(Document::default())
+            .where_clause(condition)
+            .build(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|document| document.title)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        titles(Condition::Contains("title".to_string(), "0%".to_string())),
+        vec!["100% done"]
+    );
+    assert_eq!(
+        titles(Condition::StartsWith("title".to_string(), "file_".to_string())),
+        vec!["file_name"]
+    );
+    assert_eq!(
+        titles(Condition::EndsWith("title".to_string(), "'s 5%".to_string())),
+        vec!["it's 5%"]
+    );
+
+    let query = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(Condition::Contains("title".to_string(), "0%".to_string()));
+
+    let (sql, params) = query.to_sql_with_params();
+    assert!(sql.contains("WHERE \"title\" LIKE ? ESCAPE '!'"));
+    assert_eq!(
+        params.iter().map(|v| v.to_string()).collect::<Vec<String>>(),
+        vec!["%0!%%"]
+    );
+}
+
+#[test]
+fn raw_where_placeholders_must_match_values() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER, title TEXT);
+        INSERT INTO documents (tenant_id, title) VALUES (1, 'invoice'), (2, 'draft');",
+    )
+    .unwrap();
+
+    let too_few = Condition::raw(
+        "tenant_id = ? AND title = ?",
+        vec![Value::Literal("1".to_string())],
+    );
+    let result = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(&too_few)
+        .build(&conn);
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+    let result = sqlite::count(&conn, vec![])
+        .from(&Document::default())
+        .where_clause(&too_few)
+        .build();
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+    let too_many = Condition::raw(
+        "tenant_id = ?",
+        vec![
+            Value::Literal("1".to_string()),
+            Value::Literal("2".to_string()),
+        ],
+    );
+    let result = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(&too_many)
+        .build(&conn);
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+    let result = sqlite::delete()
+        .from(Document::default())
+        .where_clause(&too_many)
+        .build(&conn);
+    assert!(result.is_err());
+
+    // Nothing was deleted
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .build(&conn)
+        .unwrap();
+    assert_eq!(documents.len(), 2);
+}
+
+#[test]
+fn raw_where_skips_question_marks_in_identifiers_and_comments() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE documents (id INTEGER PRIMARY KEY AUTOINCREMENT, tenant_id INTEGER, title TEXT, \"done?\" INTEGER);
+        INSERT INTO documents (tenant_id, title, \"done?\") VALUES (1, 'invoice', 1), (1, 'what?', 0);",
+    )
+    .unwrap();
+
+    let finished = Condition::raw(
+        "\"done?\" = ? /* finished? */ AND title <> 'what?' -- really?\n",
+        vec![Value::Literal("1".to_string())],
+    );
+
+    let documents = sqlite::select_all::<Document>()
+        .from(Document::default())
+        .where_clause(&finished)
+        .build(&conn)
+        .unwrap();
+    assert_eq!(documents.len(), 1);
+    assert_eq!(documents[0].title, "invoice");
+
+    let count = sqlite::count(&conn, vec![])
+        .from(&Document::default())
+        .where_clause(&finished)
+        .build()
+        .unwrap();
+    assert_eq!(count, 1);
+}
@@ -0,0 +1,114 @@
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table)]
+#[table_name = "visits"]
+pub struct Visit {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub page: String,
+    pub country: String,
+}
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Visit::default())]).unwrap();
+
+    let visits = [
+        ("home", "SE"),
+        ("home", "SE"),
+        ("home", "NO"),
+        ("about", "SE"),
+        ("about", "SE"),
+    ];
+    for (page, country) in visits {
+        sqlite::insert(
+            &conn,
+            vec![Visit {
+                id: AutoIncrementPrimaryKey::default(),
+                page: page.to_string(),
+                country: country.to_string(),
+            }],
+        )
+        .unwrap();
+    }
+
+    conn
+}
+
+#[test]
+fn count_all_rows() {
+    let conn = setup();
+
+    let count = sqlite::count(&conn, vec![])
+        .from(&Visit::default())
+        .build()
+        .unwrap();
+
+    assert_eq!(count, 5);
+}
+
+#[test]
+fn distinct_count_matches_unique_rows() {
+    let conn = setup();
+    let columns = vec!["page".to_string(), "country".to_string()];
+
+    let query = sqlite::count(&conn, columns.clone())
+        .from(&Visit::default())
+        .distinct();
+
+    assert_eq!(
+        query.build_query().trim(),
+        "SELECT COUNT(*) FROM (SELECT DISTINCT \"page\", \"country\" FROM visits ) njord_distinct"
+    );
+
+    let unique_rows: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM (SELECT page, country FROM visits GROUP BY page, country)",
+            (),
+            |row| row.get(0),
+        )
+        .unwrap();
+
+    assert_eq!(query.build().unwrap(), unique_rows as usize);
+    assert_eq!(unique_rows, 3);
+}
+
+#[test]
+fn distinct_count_with_where_clause() {
+    let conn = setup();
+
+    let count = sqlite::count(&conn, vec!["country".to_string()])
+        .from(&Visit::default())
+        .where_clause(Condition::eq("page", Value::Literal("home".to_string())))
+        .distinct()
+        .build()
+        .unwrap();
+
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn counts_by_column_value() {
+    let conn = setup();
+
+    let counts = sqlite::count(&conn, vec![])
+        .from(&Visit::default())
+        .counts_by("page")
+        .unwrap();
+
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[&Value::Literal("home".to_string())], 3);
+    assert_eq!(counts[&Value::Literal("about".to_string())], 2);
+
+    let counts = sqlite::count(&conn, vec![])
+        .from(&Visit::default())
+        .where_clause(Condition::eq("country", Value::Literal("SE".to_string())))
+        .counts_by("page")
+        .unwrap();
+
+    assert_eq!(counts[&Value::Literal("home".to_string())], 2);
+    assert_eq!(counts[&Value::Literal("about".to_string())], 2);
+}
@@ -0,0 +1,95 @@
+use njord::condition::Value;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table)]
+#[table_name = "jobs"]
+pub struct Job {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+}
+
+#[derive(Table)]
+#[table_name = "drafts"]
+#[soft_delete]
+pub struct Draft {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    pub deleted_at: Option<String>,
+}
+
+fn setup(rows: usize) -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Job::default())]).unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        &format!(
+            "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < {})
+            INSERT INTO jobs (id, name) SELECT i, 'job ' || i FROM n;",
+            rows
+        ),
+    )
+    .unwrap();
+
+    conn
+}
+
+fn ids(range: std::ops::RangeInclusive<usize>) -> Vec<Value<'static>> {
+    range.map(|id| Value::Literal(id.to_string())).collect()
+}
+
+fn remaining(conn: &rusqlite::Connection) -> usize {
+    sqlite::count(conn, vec![])
+        .from(&Job::default())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn delete_by_pks_deletes_the_given_rows() {
+    let conn = setup(600);
+
+    let deleted = sqlite::delete_by_pks(&conn, &Job::default(), ids(1..=500)).unwrap();
+
+    assert_eq!(deleted, 500);
+    assert_eq!(remaining(&conn), 100);
+}
+
+#[test]
+fn delete_by_pks_chunks_large_key_lists() {
+    let conn = setup(2500);
+
+    // Includes keys of rows that do not exist
+    let deleted = sqlite::delete_by_pks(&conn, &Job::default(), ids(1..=3000)).unwrap();
+
+    assert_eq!(deleted, 2500);
+    assert_eq!(remaining(&conn), 0);
+}
+
+#[test]
+fn delete_by_pks_soft_deletes_rows() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Draft::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO drafts (id, title) VALUES (1, 'a'), (2, 'b'), (3, 'c');",
+    )
+    .unwrap();
+
+    let deleted = sqlite::delete_by_pks(&conn, &Draft::default(), ids(1..=2)).unwrap();
+    assert_eq!(deleted, 2);
+
+    // Rows that are deleted already are not deleted again
+    let deleted = sqlite::delete_by_pks(&conn, &Draft::default(), ids(1..=3)).unwrap();
+    assert_eq!(deleted, 1);
+
+    let count = sqlite::count(&conn, vec![])
+        .from(&Draft::default())
+        .with_deleted()
+        .build()
+        .unwrap();
+    assert_eq!(count, 3);
+}
@@ -1,8 +1,12 @@
 use super::User;
 use njord::column::Column;
 use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
 use njord::sqlite;
 use njord::sqlite::select::SelectQueryBuilder;
+use njord::table::Table;
+use njord::Error;
+use njord_derive::Table;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -72,3 +76,94 @@ fn delete_with_subquery() {
         }
     }
 }
+
+#[derive(Table, Clone)]
+#[table_name = "tasks"]
+pub struct Task {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    pub done: bool,
+}
+
+fn setup_tasks() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE tasks (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT, done BOOLEAN);
+        INSERT INTO tasks (title, done) VALUES
+            ('a', 1), ('b', 1), ('c', 0), ('d', 1), ('e', 1);",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn task_titles(conn: &rusqlite::Connection) -> Vec<String> {
+    sqlite::select_all::<Task>()
+        .from(Task::default())
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|task| task.title)
+        .collect()
+}
+
+#[test]
+fn delete_with_limit() {
+    let conn = setup_tasks();
+
+    let mut order = HashMap::new();
+    order.insert(vec!["id".to_string()], "DESC".to_string());
+
+    let deleted = sqlite::delete()
+        .from(Task::default())
+        .where_clause(Condition::eq("done", Value::Literal("1".to_string())))
+        .order_by(order)
+        .limit(2)
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(deleted, 2);
+    assert_eq!(task_titles(&conn), vec!["a", "b", "c"]);
+}
+
+#[test]
+fn delete_from_nonexistent_table_fails() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let result = sqlite::delete().from(Task::default()).build(&conn);
+
+    match result {
+        Err(Error::QueryFailed(source)) => assert!(source.to_string().contains("no such table")),
+        Err(e) => panic!("Expected Error::QueryFailed, got: {:?}", e),
+        Ok(_) => panic!("Expected DELETE from a nonexistent table to fail"),
+    }
+}
+
+#[test]
+fn update_with_limit_and_offset() {
+    let conn = setup_tasks();
+
+    let task = Task {
+        id: AutoIncrementPrimaryKey::default(),
+        title: "archived".to_string(),
+        done: true,
+    };
+    let mut order = HashMap::new();
+    order.insert(vec!["id".to_string()], "ASC".to_string());
+
+    let updated = sqlite::update(task)
+        .set(vec!["title".to_string()])
+        .where_clause(Condition::eq("done", Value::Literal("1".to_string())))
+        .order_by(order)
+        .limit(2)
+        .offset(1)
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(updated, 2);
+    assert_eq!(
+        task_titles(&conn),
+        vec!["a", "archived", "c", "archived", "e"]
+    );
+}
@@ -0,0 +1,169 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::dialect::{Dialect, IdentifierCase};
+use njord::error::Error;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "members"]
+pub struct Member {
+    pub id: usize,
+    pub username: String,
+    pub order: usize,
+}
+
+fn members_query<'a>() -> sqlite::select::SelectQueryBuilder<'a, Member> {
+    sqlite::select(vec![
+        Column::Text("username".to_string()),
+        Column::Text("order".to_string()),
+    ])
+    .from(Member::default())
+    .where_clause(
+        Condition::eq("username", Value::Literal("mjovanc".to_string()))
+            .and(Condition::gt("id", Value::Literal("10".to_string()))),
+    )
+}
+
+#[test]
+fn placeholders_depend_on_dialect() {
+    let query = members_query();
+
+    let (sqlite_sql, sqlite_params) = query.to_sql_with_params();
+    assert!(sqlite_sql.contains("(\"username\" = ?) AND (\"id\" > ?)"));
+
+    let (postgres_sql, postgres_params) = query.dialect(Dialect::Postgres).to_sql_with_params();
+    assert!(postgres_sql.contains("(\"username\" = $1) AND (\"id\" > $2)"));
+
+    let values = |params: Vec<Value>| params.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+    assert_eq!(values(sqlite_params), vec!["mjovanc", "10"]);
+    assert_eq!(values(postgres_params), vec!["mjovanc", "10"]);
+}
+
+#[test]
+fn dialect_decides_identifier_quoting() {
+    let sql = members_query()
+        .dialect(Dialect::MySql)
+        .to_sql_with_params()
+        .0;
+
+    assert!(sql.starts_with("SELECT `username`, `order` FROM members"));
+}
+
+#[test]
+fn identifiers_are_folded_per_dialect() {
+    let oracle_sql = members_query()
+        .dialect(Dialect::Oracle)
+        .identifier_case(IdentifierCase::Fold)
+        .to_sql_with_params()
+        .0;
+    assert!(oracle_sql.starts_with("SELECT \"USERNAME\", \"ORDER\" FROM \"MEMBERS\""));
+
+    let postgres_sql = members_query()
+        .dialect(Dialect::Postgres)
+        .identifier_case(IdentifierCase::Fold)
+        .to_sql_with_params()
+        .0;
+    assert!(postgres_sql.starts_with("SELECT \"username\", \"order\" FROM \"members\""));
+
+    let quoted_sql = members_query()
+        .identifier_case(IdentifierCase::Quote)
+        .to_sql_with_params()
+        .0;
+    assert!(quoted_sql.starts_with("SELECT \"username\", \"order\" FROM \"members\""));
+}
+
+#[test]
+fn identifiers_can_opt_out_of_quoting() {
+    let sql = members_query()
+        .identifier_case(IdentifierCase::Unquoted)
+        .to_sql_with_params()
+        .0;
+
+    assert!(sql.starts_with("SELECT username, order FROM members"));
+}
+
+#[test]
+fn columns_are_decoded_case_insensitively() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Member::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO members (id, username, \"order\") VALUES (1, 'mjovanc', 2);",
+    )
+    .unwrap();
+
+    // Oracle returns unquoted identifiers in uppercase, PostgreSQL in lowercase
+    let oracle_members: Vec<Member> = sqlite::select::raw_execute(
+        "SELECT id AS ID, username AS USERNAME, \"order\" AS \"ORDER\" FROM members",
+        &conn,
+    )
+    .unwrap();
+    let postgres_members: Vec<Member> = sqlite::select::raw_execute(
+        "SELECT id AS id, username AS username, \"order\" AS \"order\" FROM members",
+        &conn,
+    )
+    .unwrap();
+
+    for members in [oracle_members, postgres_members] {
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].id, 1);
+        assert_eq!(members[0].username, "mjovanc");
+        assert_eq!(members[0].order, 2);
+    }
+}
+
+#[test]
+fn null_is_not_bound() {
+    let (sql, params) = sqlite::select::<Member>(vec![Column::Text("id".to_string())])
+        .from(Member::default())
+        .where_clause(Condition::NullSafeEq("username".to_string(), Value::Null))
+        .dialect(Dialect::Postgres)
+        .to_sql_with_params();
+
+    assert!(sql.contains("\"username\" IS NOT DISTINCT FROM NULL"));
+    assert!(params.is_empty());
+}
+
+#[test]
+fn other_dialect_can_not_be_executed() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Member::default())]).unwrap();
+
+    let result = members_query().dialect(Dialect::Postgres).build(&conn);
+
+    assert!(matches!(result, Err(Error::Unsupported(_))));
+}
+
+#[test]
+fn named_placeholders_match_params() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Member::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO members (id, username, \"order\") VALUES
+            (11, 'mjovanc', 1), (12, 'mjovanc', 2), (3, 'mjovanc', 3), (13, 'other', 4);",
+    )
+    .unwrap();
+
+    let (sql, params) = members_query()
+        .where_opt(Some(
+            Condition::lt("order", Value::Literal("3".to_string()))
+                .or(Condition::eq("id", Value::Literal("3".to_string()))),
+        ))
+        .to_sql_with_named_params();
+
+    assert!(sql.contains("(\"username\" = :p0) AND (\"id\" > :p1)"));
+    assert!(sql.contains("(\"order\" < :p2) OR (\"id\" = :p3)"));
+
+    let mut names: Vec<&String> = params.keys().collect();
+    names.sort();
+    assert_eq!(names, vec!["p0", "p1", "p2", "p3"]);
+    assert_eq!(params["p0"].to_string(), "mjovanc");
+    assert_eq!(params["p2"].to_string(), "3");
+
+    let members: Vec<Member> = sqlite::query_raw_named(&conn, &sql, params).unwrap();
+    let orders: Vec<usize> = members.iter().map(|m| m.order).collect();
+    assert_eq!(orders, vec![1, 2]);
+}
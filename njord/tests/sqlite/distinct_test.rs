@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use njord::column::Column;
+use njord::dialect::Dialect;
+use njord::error::Error;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "notes"]
+pub struct Note {
+    pub id: usize,
+    pub title: String,
+    pub description: String,
+}
+
+#[test]
+fn distinct_by_keeps_first_row_per_group() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE notes (
+            id INTEGER PRIMARY KEY,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL
+        );
+        INSERT INTO notes (id, title, description) VALUES
+            (1, 'groceries', 'milk'),
+            (2, 'groceries', 'bread'),
+            (3, 'work', 'deploy'),
+            (4, 'work', 'review'),
+            (5, 'travel', 'passport');",
+    )
+    .unwrap();
+
+    let columns = vec![
+        Column::Text("id".to_string()),
+        Column::Text("title".to_string()),
+        Column::Text("description".to_string()),
+    ];
+    let mut order = HashMap::new();
+    order.insert(vec!["id".to_string()], "ASC".to_string());
+
+    let result = sqlite::select::<Note>(columns)
+        .from(Note::default())
+        .distinct_by(vec!["title".to_string()])
+        .order_by(order)
+        .build(&conn);
+
+    match result {
+        Ok(notes) => {
+            let descriptions: Vec<&str> = notes.iter().map(|n| n.description.as_str()).collect();
+            assert_eq!(descriptions, vec!["milk", "deploy", "passport"]);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn distinct_order_by_unselected_column() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE notes (
+            id INTEGER PRIMARY KEY,
+            title TEXT NOT NULL,
+            description TEXT NOT NULL
+        );
+        INSERT INTO notes (id, title, description) VALUES
+            (1, 'work', 'deploy'),
+            (2, 'groceries', 'milk'),
+            (3, 'work', 'review');",
+    )
+    .unwrap();
+
+    let mut order = HashMap::new();
+    order.insert(vec!["id".to_string()], "ASC".to_string());
+
+    let query = sqlite::select::<Note>(vec![Column::Text("title".to_string())])
+        .from(Note::default())
+        .distinct()
+        .order_by(order);
+
+    // SQLite orders by any column of the table
+    assert!(query.check().is_ok());
+    assert_eq!(query.clone().build(&conn).unwrap().len(), 2);
+
+    let result = query.dialect(Dialect::Postgres).check();
+    assert!(matches!(result, Err(Error::InvalidDistinctOrder(column)) if column == "id"));
+
+    let mut order = HashMap::new();
+    order.insert(vec!["notes.title".to_string()], "DESC".to_string());
+
+    let query = sqlite::select::<Note>(vec![Column::Text("title".to_string())])
+        .from(Note::default())
+        .distinct()
+        .order_by(order)
+        .dialect(Dialect::Postgres);
+    assert!(query.check().is_ok());
+}
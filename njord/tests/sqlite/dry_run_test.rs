@@ -0,0 +1,112 @@
+use njord::condition::Value;
+use njord::config::ConnectionConfig;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite::{self, CapturedStatement, DryRunConnection};
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::types::Value as SqliteValue;
+
+#[derive(Table)]
+#[table_name = "tasks"]
+pub struct Task {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    pub done: bool,
+}
+
+#[test]
+fn dry_run_captures_insert_and_update() {
+    let conn = DryRunConnection::new();
+
+    sqlite::insert(
+        &conn,
+        vec![Task {
+            id: AutoIncrementPrimaryKey::default(),
+            title: "write tests".to_string(),
+            done: false,
+        }],
+    )
+    .unwrap();
+
+    let mut task = Task {
+        id: AutoIncrementPrimaryKey::new(Some(1)),
+        title: "write tests".to_string(),
+        done: true,
+    };
+    sqlite::update_by_pk(&conn, &mut task).unwrap();
+
+    assert_eq!(
+        conn.statements(),
+        [
+            CapturedStatement {
+                sql: "INSERT INTO tasks (\"title\", \"done\") VALUES ('write tests', 'false')"
+                    .to_string(),
+                params: vec![],
+            },
+            CapturedStatement {
+                sql: "UPDATE tasks SET \"title\" = ?1, \"done\" = ?2 WHERE \"id\" = ?3".to_string(),
+                params: vec![
+                    SqliteValue::Text("write tests".to_string()),
+                    SqliteValue::Text("true".to_string()),
+                    SqliteValue::Integer(1),
+                ],
+            },
+        ]
+    );
+}
+
+#[test]
+fn dry_run_records_parameters_without_executing() {
+    let conn = DryRunConnection::new();
+
+    let affected = sqlite::execute(
+        &conn,
+        "INSERT INTO tasks (\"title\", \"done\") VALUES (?, ?)",
+        vec![
+            Value::Literal("it's a '?' task".to_string()),
+            Value::Literal("1".to_string()),
+        ],
+    )
+    .unwrap();
+    assert_eq!(affected, 0);
+
+    let tasks = sqlite::select_all::<Task>()
+        .from(Task::default())
+        .build(&conn)
+        .unwrap();
+    assert!(tasks.is_empty());
+
+    let statements = conn.statements();
+    assert_eq!(statements.len(), 2);
+    assert_eq!(
+        statements[0],
+        CapturedStatement {
+            sql: "INSERT INTO tasks (\"title\", \"done\") VALUES (?, ?)".to_string(),
+            params: vec![
+                SqliteValue::Text("it's a '?' task".to_string()),
+                SqliteValue::Integer(1),
+            ],
+        }
+    );
+    assert!(statements[1].sql.starts_with("SELECT "));
+
+    conn.clear();
+    assert!(conn.statements().is_empty());
+}
+
+#[test]
+fn dry_run_builds_statements_with_the_connection_config() {
+    let conn = DryRunConnection::new().with_config(ConnectionConfig {
+        table_prefix: "app_".to_string(),
+        ..Default::default()
+    });
+
+    sqlite::delete().from(Task::default()).build(&conn).unwrap();
+
+    let delete = conn.statements().pop().unwrap();
+    assert!(
+        delete.sql.starts_with("DELETE FROM app_tasks"),
+        "{}",
+        delete.sql
+    );
+}
@@ -0,0 +1,108 @@
+use njord::column::Column;
+use njord::error::Error;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::{NjordEnum, Table};
+
+#[derive(NjordEnum, Debug, Default, Clone, Copy, PartialEq)]
+#[njord_enum(repr = "i32")]
+pub enum Status {
+    #[default]
+    Active = 1,
+    Suspended = 2,
+    Closed = 10,
+}
+
+#[derive(NjordEnum, Debug, Default, Clone, Copy, PartialEq)]
+pub enum Plan {
+    #[default]
+    Free,
+    Pro,
+}
+
+#[derive(Table)]
+#[table_name = "accounts"]
+pub struct Account {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub status: Status,
+    pub plan: Option<Plan>,
+}
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            status INTEGER NOT NULL,
+            plan TEXT
+        );",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn select_accounts(conn: &rusqlite::Connection) -> Result<Vec<Account>, Error> {
+    sqlite::select(vec![
+        Column::Text("id".to_string()),
+        Column::Text("status".to_string()),
+        Column::Text("plan".to_string()),
+    ])
+    .from(Account::default())
+    .build(conn)
+}
+
+#[test]
+fn integer_enum_round_trips() {
+    let conn = setup();
+
+    let expected = [(Status::Closed, Some(Plan::Pro)), (Status::Suspended, None)];
+    for (status, plan) in expected {
+        sqlite::insert(
+            &conn,
+            vec![Account {
+                id: AutoIncrementPrimaryKey::default(),
+                status,
+                plan,
+            }],
+        )
+        .unwrap();
+    }
+
+    let status: i64 = conn
+        .query_row("SELECT status FROM accounts WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .unwrap();
+    assert_eq!(status, 10);
+
+    let accounts = select_accounts(&conn).unwrap();
+    let values: Vec<(Status, Option<Plan>)> = accounts.iter().map(|a| (a.status, a.plan)).collect();
+    assert_eq!(values, expected);
+}
+
+#[test]
+fn unknown_discriminant_is_a_decode_error() {
+    let conn = setup();
+    sqlite::raw_execute(&conn, "INSERT INTO accounts (status) VALUES (3);").unwrap();
+
+    match select_accounts(&conn) {
+        Err(Error::Decode(e)) => assert!(e.to_string().contains("value `3` for column `status`")),
+        other => panic!("Expected a decode error, got {:?}", other.map(|r| r.len())),
+    }
+}
+
+#[test]
+fn unknown_variant_name_is_a_decode_error() {
+    let conn = setup();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO accounts (status, plan) VALUES (1, 'Enterprise');",
+    )
+    .unwrap();
+
+    assert!(matches!(select_accounts(&conn), Err(Error::Decode(_))));
+}
@@ -0,0 +1,55 @@
+use njord::condition::{Condition, Value};
+use njord::sqlite;
+
+use crate::User;
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1');",
+    )
+    .unwrap();
+
+    conn
+}
+
+#[test]
+fn exists_with_matching_row() {
+    let conn = setup();
+
+    let result = sqlite::exists(&conn)
+        .from(&User::default())
+        .where_clause(Condition::Eq(
+            "username".to_string(),
+            Value::Literal("mjovanc".to_string()),
+        ))
+        .build();
+
+    assert!(result.unwrap());
+}
+
+#[test]
+fn exists_without_matching_row() {
+    let conn = setup();
+
+    let query = sqlite::exists(&conn)
+        .from(&User::default())
+        .where_clause(Condition::Eq(
+            "username".to_string(),
+            Value::Literal("nobody".to_string()),
+        ));
+    assert!(query
+        .build_query()
+        .starts_with("SELECT EXISTS(SELECT 1 FROM users WHERE"));
+
+    assert!(!query.build().unwrap());
+}
@@ -0,0 +1,37 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::sqlite;
+
+use super::User;
+
+#[test]
+fn explain_uses_index() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        CREATE INDEX idx_users_email ON users (email);",
+    )
+    .unwrap();
+
+    let plan = sqlite::select::<User>(vec![Column::Text("username".to_string())])
+        .from(User::default())
+        .where_clause(Condition::Eq(
+            "email".to_string(),
+            Value::Literal("mjovanc@icloud.com".to_string()),
+        ))
+        .explain(&conn)
+        .expect("Failed to EXPLAIN");
+
+    assert!(
+        plan.iter().any(|step| step.contains("idx_users_email")),
+        "plan does not use the index: {:?}",
+        plan
+    );
+}
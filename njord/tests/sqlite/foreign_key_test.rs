@@ -0,0 +1,166 @@
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord::Error;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "authors"]
+pub struct Author {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+}
+
+#[derive(Table, Clone)]
+#[table_name = "articles"]
+pub struct Article {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    #[foreign_key(table = "authors", column = "id", on_delete = "set_null")]
+    pub author_id: Option<usize>,
+}
+
+#[test]
+fn create_table_statement_with_foreign_key() {
+    assert_eq!(
+        sqlite::init::generate_create_table_statement(&Article::default()),
+        "CREATE TABLE IF NOT EXISTS articles (\"id\" INTEGER PRIMARY KEY AUTOINCREMENT, \"title\" TEXT, \
+         \"author_id\" INTEGER NULL REFERENCES authors(id) ON DELETE SET NULL);"
+    );
+}
+
+#[test]
+fn delete_parent_sets_foreign_key_to_null() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(
+        &conn,
+        vec![Box::new(Author::default()), Box::new(Article::default())],
+    )
+    .expect("Failed to init tables");
+
+    let author = Author {
+        id: AutoIncrementPrimaryKey::default(),
+        name: "mjovanc".to_string(),
+    };
+    sqlite::insert(&conn, vec![author]).expect("Failed to INSERT author");
+
+    let article = Article {
+        id: AutoIncrementPrimaryKey::default(),
+        title: "Foreign keys in SQLite".to_string(),
+        author_id: Some(1),
+    };
+    sqlite::insert(&conn, vec![article]).expect("Failed to INSERT article");
+
+    sqlite::delete()
+        .from(Author::default())
+        .where_clause(Condition::Eq(
+            "id".to_string(),
+            Value::Literal("1".to_string()),
+        ))
+        .build(&conn)
+        .expect("Failed to DELETE author");
+
+    let articles = sqlite::select_all::<Article>()
+        .from(Article::default())
+        .build(&conn)
+        .expect("Failed to SELECT articles");
+
+    assert_eq!(articles.len(), 1);
+    assert_eq!(articles[0].author_id, None);
+}
+
+#[derive(Table, Clone)]
+#[table_name = "comments"]
+pub struct Comment {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[foreign_key(table = "articles", column = "id", on_delete = "cascade")]
+    pub article_id: usize,
+}
+
+#[derive(Table, Clone)]
+#[table_name = "teams"]
+pub struct Team {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[foreign_key(table = "players", column = "id")]
+    pub captain_id: Option<usize>,
+}
+
+#[derive(Table, Clone)]
+#[table_name = "players"]
+pub struct Player {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[foreign_key(table = "teams", column = "id")]
+    pub team_id: Option<usize>,
+}
+
+#[test]
+fn init_sorts_tables_by_foreign_keys() {
+    let tables: Vec<Box<dyn Table>> = vec![
+        Box::new(Comment::default()),
+        Box::new(Article::default()),
+        Box::new(Author::default()),
+    ];
+
+    let sorted: Vec<&str> = sqlite::init::sort_by_dependencies(&tables)
+        .unwrap()
+        .iter()
+        .map(|t| t.get_name())
+        .collect();
+    assert_eq!(sorted, vec!["authors", "articles", "comments"]);
+
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, tables).expect("Failed to init tables");
+}
+
+#[test]
+fn init_rejects_foreign_key_cycles() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let result = sqlite::init(
+        &conn,
+        vec![Box::new(Team::default()), Box::new(Player::default())],
+    );
+
+    match result {
+        Err(Error::InvalidQuery(message)) => {
+            assert!(message.contains("teams -> players -> teams"), "{}", message)
+        }
+        _ => panic!("expected a foreign key cycle error"),
+    }
+}
+
+#[derive(Table, Clone)]
+#[table_name = "reviews"]
+pub struct Review {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[foreign_key(table = "articles", column = "id", on_delete = "cascade")]
+    pub article_id: usize,
+    #[foreign_key(table = "authors", column = "id")]
+    pub reviewer_id: usize,
+    #[foreign_key(table = "authors", column = "id")]
+    pub editor_id: usize,
+}
+
+#[test]
+fn dependencies_list_referenced_tables() {
+    assert_eq!(
+        Review::default().dependencies(),
+        vec!["articles", "authors"]
+    );
+    assert_eq!(Article::default().dependencies(), vec!["authors"]);
+    assert!(Author::default().dependencies().is_empty());
+
+    let tables: Vec<Box<dyn Table>> = vec![
+        Box::new(Review::default()),
+        Box::new(Article::default()),
+        Box::new(Author::default()),
+    ];
+
+    let sorted: Vec<&str> = sqlite::init::sort_by_dependencies(&tables)
+        .unwrap()
+        .iter()
+        .map(|t| t.get_name())
+        .collect();
+    assert_eq!(sorted, vec!["authors", "articles", "reviews"]);
+}
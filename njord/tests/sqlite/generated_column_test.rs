@@ -0,0 +1,45 @@
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "order_lines"]
+pub struct OrderLine {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub price: f64,
+    pub quantity: i64,
+    #[generated("price * quantity", stored)]
+    pub total: f64,
+}
+
+#[test]
+fn create_generated_column() {
+    let statement = sqlite::init::generate_create_table_statement(&OrderLine::default());
+
+    assert!(statement.contains("\"total\" REAL GENERATED ALWAYS AS (price * quantity) STORED"));
+}
+
+#[test]
+fn read_computed_value_after_insert() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(OrderLine::default())]).unwrap();
+
+    sqlite::insert(
+        &conn,
+        vec![OrderLine {
+            id: AutoIncrementPrimaryKey::default(),
+            price: 2.5,
+            quantity: 4,
+            total: 0.0,
+        }],
+    )
+    .unwrap();
+
+    let lines = sqlite::select_all::<OrderLine>()
+        .from(OrderLine::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(lines[0].total, 10.0);
+}
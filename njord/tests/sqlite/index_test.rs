@@ -0,0 +1,67 @@
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "subscribers"]
+#[index(
+    name = "idx_subscribers_active_email",
+    columns = "email",
+    unique,
+    where = "active = 1"
+)]
+#[index(name = "idx_subscribers_name", columns = "last_name, first_name")]
+pub struct Subscriber {
+    pub id: usize,
+    pub first_name: String,
+    pub last_name: String,
+    pub email: String,
+    pub active: i32,
+}
+
+#[test]
+fn create_partial_index() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Subscriber::default())]).unwrap();
+
+    let mut stmt = conn
+        .prepare("SELECT name, sql FROM sqlite_master WHERE type = 'index' AND tbl_name = 'subscribers' ORDER BY name")
+        .unwrap();
+    let indexes: Vec<(String, String)> = stmt
+        .query_map((), |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(
+        indexes,
+        vec![
+            (
+                "idx_subscribers_active_email".to_string(),
+                "CREATE UNIQUE INDEX idx_subscribers_active_email ON subscribers (\"email\") WHERE active = 1"
+                    .to_string()
+            ),
+            (
+                "idx_subscribers_name".to_string(),
+                "CREATE INDEX idx_subscribers_name ON subscribers (\"last_name\", \"first_name\")".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn partial_unique_index_only_applies_to_matching_rows() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Subscriber::default())]).unwrap();
+
+    let subscriber = |id: usize, active: i32| Subscriber {
+        id,
+        first_name: "Ada".to_string(),
+        last_name: "Lovelace".to_string(),
+        email: "ada@example.com".to_string(),
+        active,
+    };
+
+    sqlite::insert(&conn, vec![subscriber(1, 0), subscriber(2, 1)]).unwrap();
+    assert!(sqlite::insert(&conn, vec![subscriber(3, 1)]).is_err());
+}
@@ -0,0 +1,203 @@
+use njord::column::Column;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "events"]
+pub struct Event {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+    #[default_sql = "CURRENT_TIMESTAMP"]
+    pub created_at: Option<String>,
+}
+
+fn event_columns<'a>() -> Vec<Column<'a>> {
+    vec![
+        Column::Text("id".to_string()),
+        Column::Text("name".to_string()),
+        Column::Text("created_at".to_string()),
+    ]
+}
+
+#[test]
+fn create_table_statement() {
+    assert_eq!(
+        sqlite::init::generate_create_table_statement(&Event::default()),
+        "CREATE TABLE IF NOT EXISTS events (\"id\" INTEGER PRIMARY KEY AUTOINCREMENT, \"name\" TEXT, \
+         \"created_at\" TEXT NULL DEFAULT CURRENT_TIMESTAMP);"
+    );
+}
+
+#[test]
+fn insert_with_default_sql() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Event::default())]).expect("Failed to init tables");
+
+    let rows = vec![
+        Event {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "deploy".to_string(),
+            created_at: None,
+        },
+        Event {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "backfill".to_string(),
+            created_at: Some("2024-01-01 00:00:00".to_string()),
+        },
+    ];
+    sqlite::insert(&conn, rows).expect("Failed to INSERT");
+
+    let result = sqlite::select(event_columns())
+        .from(Event::default())
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 2);
+            let created_at = r[0].created_at.as_ref().expect("created_at is NULL");
+            assert_eq!(created_at.len(), "YYYY-MM-DD HH:MM:SS".len());
+            assert_eq!(r[1].created_at.as_deref(), Some("2024-01-01 00:00:00"));
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn insert_batch_with_different_null_columns() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Event::default())]).expect("Failed to init tables");
+
+    let rows = vec![
+        Event {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "NULL".to_string(),
+            created_at: None,
+        },
+        Event {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "backfill".to_string(),
+            created_at: Some("2024-01-01 00:00:00".to_string()),
+        },
+        Event {
+            id: AutoIncrementPrimaryKey::new(Some(10)),
+            name: "restore".to_string(),
+            created_at: Some("2024-02-01 00:00:00".to_string()),
+        },
+    ];
+    sqlite::insert(&conn, rows).expect("Failed to INSERT");
+
+    let r = sqlite::select(event_columns())
+        .from(Event::default())
+        .build(&conn)
+        .expect("Failed to SELECT");
+
+    assert_eq!(r.len(), 3);
+    // A string with the text NULL is a value, not a missing one
+    assert_eq!(r[0].name, "NULL");
+    let created_at = r[0].created_at.as_ref().expect("created_at is NULL");
+    assert_eq!(created_at.len(), "YYYY-MM-DD HH:MM:SS".len());
+    assert_eq!(r[1].name, "backfill");
+    assert_eq!(r[1].created_at.as_deref(), Some("2024-01-01 00:00:00"));
+    assert_eq!(r[2].id.get(), Some(&10));
+    assert_eq!(r[2].created_at.as_deref(), Some("2024-02-01 00:00:00"));
+}
+
+macro_rules! batch_tables {
+    ($($name:ident => $table:literal, $index:literal);* $(;)?) => {
+        $(
+            #[derive(Table, Clone)]
+            #[table_name = $table]
+            #[index(name = $index, columns = "name")]
+            pub struct $name {
+                pub id: AutoIncrementPrimaryKey<usize>,
+                pub name: String,
+            }
+        )*
+
+        fn batch_tables() -> Vec<Box<dyn Table>> {
+            vec![$(Box::new($name::default())),*]
+        }
+    };
+}
+
+batch_tables! {
+    Batch01 => "batch_01", "batch_01_name";
+    Batch02 => "batch_02", "batch_02_name";
+    Batch03 => "batch_03", "batch_03_name";
+    Batch04 => "batch_04", "batch_04_name";
+    Batch05 => "batch_05", "batch_05_name";
+    Batch06 => "batch_06", "batch_06_name";
+    Batch07 => "batch_07", "batch_07_name";
+    Batch08 => "batch_08", "batch_08_name";
+    Batch09 => "batch_09", "batch_09_name";
+    Batch10 => "batch_10", "batch_10_name";
+    Batch11 => "batch_11", "batch_11_name";
+    Batch12 => "batch_12", "batch_12_name";
+    Batch13 => "batch_13", "batch_13_name";
+    Batch14 => "batch_14", "batch_14_name";
+    Batch15 => "batch_15", "batch_15_name";
+    Batch16 => "batch_16", "batch_16_name";
+    Batch17 => "batch_17", "batch_17_name";
+    Batch18 => "batch_18", "batch_18_name";
+    Batch19 => "batch_19", "batch_19_name";
+    Batch20 => "batch_20", "batch_20_name";
+}
+
+#[derive(Table, Clone)]
+#[table_name = "broken"]
+#[check("name IS NOT (")]
+pub struct Broken {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+}
+
+fn count_schema_objects(conn: &rusqlite::Connection, object_type: &str) -> i64 {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = ?1 AND name LIKE 'batch_%'",
+        [object_type],
+        |row| row.get(0),
+    )
+    .unwrap()
+}
+
+#[test]
+fn init_many_tables_in_one_batch() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    assert_eq!(
+        sqlite::init::generate_init_statements(&batch_tables())
+            .unwrap()
+            .len(),
+        40
+    );
+    sqlite::init(&conn, batch_tables()).expect("Failed to init tables");
+
+    assert_eq!(count_schema_objects(&conn, "table"), 20);
+    assert_eq!(count_schema_objects(&conn, "index"), 20);
+    sqlite::insert(
+        &conn,
+        vec![Batch20 {
+            id: AutoIncrementPrimaryKey::default(),
+            name: "last".to_string(),
+        }],
+    )
+    .expect("Failed to INSERT");
+
+    // calling init again is a no-op
+    sqlite::init(&conn, batch_tables()).expect("Failed to init tables again");
+    assert_eq!(count_schema_objects(&conn, "table"), 20);
+}
+
+#[test]
+fn init_failure_creates_no_tables() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let mut tables = batch_tables();
+    tables.push(Box::new(Broken::default()));
+
+    assert!(sqlite::init(&conn, tables).is_err());
+    assert_eq!(count_schema_objects(&conn, "table"), 0);
+    assert!(conn.is_autocommit());
+}
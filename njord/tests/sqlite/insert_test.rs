@@ -3,6 +3,9 @@ use njord::column::Column;
 use njord::condition::{Condition, Value};
 use njord::keys::AutoIncrementPrimaryKey;
 use njord::sqlite;
+use njord::table::Table;
+use njord::Error;
+use njord_derive::Table;
 use std::path::Path;
 use std::vec;
 
@@ -91,3 +94,279 @@ fn insert_row_with_single_quotes() {
         }
     }
 }
+
+#[test]
+fn insert_into_nonexistent_table() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    let table_row: User = User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: "mjovanc".to_string(),
+        email: "mjovanc@icloud.com".to_string(),
+        address: "Some Random Address 1".to_string(),
+    };
+
+    let result = sqlite::insert(&conn, vec![table_row]);
+
+    match result {
+        Err(Error::QueryFailed(source)) => assert!(source.to_string().contains("no such table")),
+        Err(e) => panic!("Expected Error::QueryFailed, got: {:?}", e),
+        Ok(_) => panic!("Expected INSERT into a nonexistent table to fail"),
+    }
+}
+
+#[derive(Table, Clone)]
+#[table_name = "archived_users"]
+pub struct ArchivedUser {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub username: String,
+    pub email: String,
+}
+
+#[test]
+fn insert_from_select() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        CREATE TABLE archived_users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1'),
+            ('otheruser', 'otheruser@icloud.com', 'Some Random Address 2'),
+            ('thirduser', 'thirduser@icloud.com', 'Some Random Address 1');",
+    )
+    .unwrap();
+
+    let select = sqlite::select::<User>(vec![
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+    ])
+    .from(User::default())
+    .where_clause(Condition::Eq(
+        "address".to_string(),
+        Value::Literal("Some Random Address 1".to_string()),
+    ));
+
+    let inserted = sqlite::insert_from(
+        &conn,
+        &ArchivedUser::default(),
+        vec!["username".to_string(), "email".to_string()],
+        select,
+    );
+    assert_eq!(inserted.unwrap(), 2);
+
+    let archived = sqlite::select_all::<ArchivedUser>()
+        .from(ArchivedUser::default())
+        .build(&conn)
+        .unwrap();
+    let usernames: Vec<&str> = archived.iter().map(|u| u.username.as_str()).collect();
+    assert_eq!(usernames, vec!["mjovanc", "thirduser"]);
+
+    let mismatch = sqlite::insert_from(
+        &conn,
+        &ArchivedUser::default(),
+        vec!["username".to_string()],
+        sqlite::select::<User>(vec![Column::all(), Column::all()]).from(User::default()),
+    );
+    assert!(matches!(mismatch, Err(Error::InvalidQuery(_))));
+}
+
+fn setup_unique_users() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL UNIQUE,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1');",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn user_with_email(email: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: "mjovanc".to_string(),
+        email: email.to_string(),
+        address: "Some Random Address 1".to_string(),
+    }
+}
+
+#[test]
+fn insert_or_ignore_skips_duplicate() {
+    let conn = setup_unique_users();
+
+    let plain = sqlite::insert(&conn, vec![user_with_email("new@icloud.com")]);
+    assert!(matches!(plain, Err(Error::Conflict(_))));
+
+    let result = sqlite::InsertQueryBuilder::new(vec![user_with_email("new@icloud.com")])
+        .or_ignore()
+        .build(&conn);
+    assert!(result.unwrap().starts_with("INSERT OR IGNORE INTO users"));
+
+    let users = sqlite::select_all::<User>()
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].email, "mjovanc@icloud.com");
+}
+
+#[test]
+fn insert_or_replace_overwrites_duplicate() {
+    let conn = setup_unique_users();
+
+    let result = sqlite::InsertQueryBuilder::new(vec![user_with_email("new@icloud.com")])
+        .or_replace()
+        .build(&conn);
+    assert!(result.unwrap().starts_with("INSERT OR REPLACE INTO users"));
+
+    let users = sqlite::select_all::<User>()
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].email, "new@icloud.com");
+}
+
+#[test]
+fn insert_returning_into() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(User::default())]).unwrap();
+
+    let table_rows = vec![
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: "mjovanc".to_string(),
+            email: "mjovanc@icloud.com".to_string(),
+            address: "Some Random Address 1".to_string(),
+        },
+        User {
+            id: AutoIncrementPrimaryKey::default(),
+            username: "otheruser".to_string(),
+            email: "other@example.com".to_string(),
+            address: "Some Random Address 2".to_string(),
+        },
+    ];
+
+    let users = sqlite::InsertQueryBuilder::new(table_rows)
+        .returning_into::<User>(&conn)
+        .unwrap();
+
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[0].id.get(), Some(&1));
+    assert_eq!(users[0].username, "mjovanc");
+    assert_eq!(users[1].id.get(), Some(&2));
+    assert_eq!(users[1].email, "other@example.com");
+}
+
+#[test]
+fn insert_continue_on_error_reports_duplicate() {
+    let conn = setup_unique_users();
+
+    let new_user = |username: &str| User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+        email: format!("{}@icloud.com", username),
+        address: "Some Random Address 1".to_string(),
+    };
+    let rows = vec![
+        new_user("first"),
+        user_with_email("duplicate@icloud.com"),
+        new_user("second"),
+    ];
+
+    let (inserted, failures) = sqlite::InsertQueryBuilder::new(rows)
+        .continue_on_error(&conn)
+        .unwrap();
+
+    assert_eq!(inserted, 2);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, 1);
+    assert!(matches!(failures[0].1, Error::Conflict(_)));
+
+    let users = sqlite::select_all::<User>()
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+    let usernames: Vec<&str> = users.iter().map(|u| u.username.as_str()).collect();
+    assert_eq!(usernames, vec!["mjovanc", "first", "second"]);
+    assert!(conn.is_autocommit());
+}
+
+#[test]
+fn insert_continue_on_error_rolls_back_when_release_fails() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "PRAGMA foreign_keys = ON;
+        CREATE TABLE addresses (name TEXT PRIMARY KEY);
+        CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL REFERENCES addresses (name) DEFERRABLE INITIALLY DEFERRED
+        );",
+    )
+    .unwrap();
+
+    // The deferred foreign key is only checked when the outer savepoint is released
+    let rows = vec![User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: "mjovanc".to_string(),
+        email: "mjovanc@icloud.com".to_string(),
+        address: "Unknown Address".to_string(),
+    }];
+    let result = sqlite::InsertQueryBuilder::new(rows).continue_on_error(&conn);
+
+    assert!(result.is_err());
+    assert!(conn.is_autocommit());
+
+    let users = sqlite::select_all::<User>()
+        .from(User::default())
+        .build(&conn)
+        .unwrap();
+    assert!(users.is_empty());
+}
+
+#[derive(Table, Clone)]
+#[table_name = "visits"]
+pub struct Visit {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[default_sql = "CURRENT_TIMESTAMP"]
+    pub visited_at: Option<String>,
+    #[default_sql = "0"]
+    pub duration: Option<i64>,
+}
+
+#[test]
+fn insert_default_values() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Visit::default())]).unwrap();
+
+    assert_eq!(sqlite::insert_default(&conn, &Visit::default()).unwrap(), 1);
+    assert_eq!(sqlite::insert_default(&conn, &Visit::default()).unwrap(), 2);
+
+    let visits = sqlite::select_all::<Visit>()
+        .from(Visit::default())
+        .build(&conn)
+        .unwrap();
+    assert_eq!(visits.len(), 2);
+    assert!(visits[0].visited_at.is_some());
+    assert_eq!(visits[0].duration, Some(0));
+}
@@ -0,0 +1,82 @@
+use njord::error::Error;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table)]
+#[table_name = "counters"]
+pub struct Counter {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub value: u64,
+}
+
+#[derive(Table)]
+#[table_name = "wide_counters"]
+pub struct WideCounter {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub value: u128,
+    pub delta: i128,
+}
+
+#[test]
+fn u64_above_i64_max_is_an_overflow_error() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Counter::default())]).unwrap();
+
+    let result = sqlite::insert(
+        &conn,
+        vec![Counter {
+            id: AutoIncrementPrimaryKey::default(),
+            value: u64::MAX,
+        }],
+    );
+
+    assert!(matches!(result, Err(Error::IntegerOverflow(_))));
+}
+
+#[test]
+fn u64_within_i64_range_is_stored() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Counter::default())]).unwrap();
+
+    sqlite::insert(
+        &conn,
+        vec![Counter {
+            id: AutoIncrementPrimaryKey::default(),
+            value: i64::MAX as u64,
+        }],
+    )
+    .unwrap();
+
+    let counters = sqlite::select_all::<Counter>()
+        .from(Counter::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(counters[0].value, i64::MAX as u64);
+}
+
+#[test]
+fn wide_integers_are_stored_as_text() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(WideCounter::default())]).unwrap();
+
+    sqlite::insert(
+        &conn,
+        vec![WideCounter {
+            id: AutoIncrementPrimaryKey::default(),
+            value: u64::MAX as u128,
+            delta: i128::MIN,
+        }],
+    )
+    .unwrap();
+
+    let counters = sqlite::select_all::<WideCounter>()
+        .from(WideCounter::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(counters[0].value, u64::MAX as u128);
+    assert_eq!(counters[0].delta, i128::MIN);
+}
@@ -0,0 +1,169 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::Connection;
+
+#[derive(Table, Clone)]
+#[table_name = "profiles"]
+pub struct Profile {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub username: String,
+    pub data: String,
+}
+
+fn setup() -> Connection {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            data TEXT NOT NULL
+        );",
+    )
+    .expect("Failed to create profiles");
+
+    let rows = vec![
+        Profile {
+            id: AutoIncrementPrimaryKey::default(),
+            username: "mjovanc".to_string(),
+            data: r#"{"address": {"city": "Stockholm"}, "age": 30}"#.to_string(),
+        },
+        Profile {
+            id: AutoIncrementPrimaryKey::default(),
+            username: "otheruser".to_string(),
+            data: r#"{"address": {"city": "Oslo"}, "age": 25}"#.to_string(),
+        },
+    ];
+    sqlite::insert(&conn, rows).expect("Failed to insert profiles");
+
+    conn
+}
+
+fn columns<'a>() -> Vec<Column<'a>> {
+    vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+        Column::Text("data".to_string()),
+    ]
+}
+
+#[test]
+fn select_json_extract_eq() {
+    let conn = setup();
+
+    let result = sqlite::select(columns())
+        .from(Profile::default())
+        .where_clause(Condition::json_extract_eq(
+            "data",
+            "address.city",
+            Value::Literal("Oslo".to_string()),
+        ))
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].username, "otheruser");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_json_extract_eq_numeric() {
+    let conn = setup();
+
+    let result = sqlite::select(columns())
+        .from(Profile::default())
+        .where_clause(Condition::json_extract_eq(
+            "data",
+            "$.age",
+            Value::Literal("30".to_string()),
+        ))
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].username, "mjovanc");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[derive(Table, Clone)]
+#[table_name = "articles"]
+pub struct ArticleTags {
+    pub title: String,
+    pub tag_count: i64,
+    pub first_tag: String,
+}
+
+#[test]
+fn select_json_array_length() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+    sqlite::ensure_json1(&conn).expect("JSON1 is not available");
+
+    sqlite::raw_execute(
+        &conn,
+        r#"CREATE TABLE articles (title TEXT NOT NULL, tags TEXT NOT NULL);
+        INSERT INTO articles (title, tags) VALUES
+            ('Njord 0.5', '["rust", "orm", "sqlite"]'),
+            ('Hello', '["intro"]'),
+            ('Draft', '[]');"#,
+    )
+    .expect("Failed to create articles");
+
+    let query = sqlite::select(vec![
+        Column::Text("title".to_string()),
+        Column::json_array_length("tags").alias("tag_count"),
+        Column::json_extract("tags", "$[0]").alias("first_tag"),
+    ])
+    .from(ArticleTags::default())
+    .where_clause(Condition::gt(
+        Column::json_array_length("tags"),
+        Value::Literal("0".to_string()),
+    ));
+
+    assert!(query.build_query().starts_with(
+        "SELECT \"title\", json_array_length(tags) AS tag_count, json_extract(tags, '$[0]') AS first_tag"
+    ));
+
+    match query.build(&conn) {
+        Ok(r) => {
+            let counts: Vec<(&str, i64, &str)> = r
+                .iter()
+                .map(|a| (a.title.as_str(), a.tag_count, a.first_tag.as_str()))
+                .collect();
+            assert_eq!(
+                counts,
+                vec![("Njord 0.5", 3, "rust"), ("Hello", 1, "intro")]
+            );
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+
+    let result = sqlite::select(vec![
+        Column::Text("title".to_string()),
+        Column::json_array_length("tags").alias("tag_count"),
+    ])
+    .from(ArticleTags::default())
+    .where_clause(Condition::json_array_contains(
+        "tags",
+        Value::Literal("orm".to_string()),
+    ))
+    .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].title, "Njord 0.5");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
@@ -0,0 +1,149 @@
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord::util::PageToken;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "events"]
+pub struct Event {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub kind: String,
+}
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Event::default())]).unwrap();
+
+    // Inserted out of order, so the pages must follow the keys, not the insertion order
+    for id in [4, 1, 7, 3, 6, 2, 5] {
+        sqlite::raw_execute(
+            &conn,
+            &format!(
+                "INSERT INTO events (id, kind) VALUES ({}, '{}');",
+                id,
+                if id % 2 == 0 { "click" } else { "view" }
+            ),
+        )
+        .unwrap();
+    }
+
+    conn
+}
+
+#[test]
+fn paginate_with_keyset_cursors() {
+    let conn = setup();
+
+    let mut ids = Vec::new();
+    let mut pages = 0;
+    let mut token: Option<PageToken> = None;
+    loop {
+        let after = match &token {
+            Some(token) => token.to_value().unwrap(),
+            None => Value::Null,
+        };
+        let page = sqlite::select_all::<Event>()
+            .from(Event::default())
+            .after("id", after)
+            .limit(3)
+            .build_page(&conn)
+            .unwrap();
+
+        pages += 1;
+        ids.extend(page.rows.iter().map(|event| *event.id.get().unwrap()));
+
+        // The token is handed to clients as text
+        match page.page_token() {
+            Some(next) => token = Some(next.to_string().parse().unwrap()),
+            None => break,
+        }
+    }
+
+    assert_eq!(ids, vec![1, 2, 3, 4, 5, 6, 7]);
+    // The last page has a single row
+    assert_eq!(pages, 3);
+}
+
+#[test]
+fn keyset_combines_with_where_clause() {
+    let conn = setup();
+
+    let page = sqlite::select_all::<Event>()
+        .from(Event::default())
+        .where_clause(Condition::eq("kind", Value::Literal("view".to_string())))
+        .after("id", Value::Literal("1".to_string()))
+        .limit(2)
+        .build_page(&conn)
+        .unwrap();
+
+    let ids: Vec<usize> = page.rows.iter().map(|e| *e.id.get().unwrap()).collect();
+    assert_eq!(ids, vec![3, 5]);
+
+    let token = page.page_token().unwrap();
+    assert_eq!(token.key().unwrap(), "5");
+    assert!("not a token!".parse::<PageToken>().is_err());
+}
+
+#[derive(Table, Clone)]
+#[table_name = "posts"]
+pub struct Post {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub created_at: String,
+}
+
+#[test]
+fn paginate_by_composite_keyset_with_equal_timestamps() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Post::default())]).unwrap();
+
+    // Several posts share a timestamp, so the id breaks the ties across page boundaries
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO posts (id, created_at) VALUES
+            (5, '2024-01-01'), (2, '2024-01-02'), (1, '2024-01-02'), (4, '2024-01-02'),
+            (3, '2024-01-03'), (6, '2024-01-01'), (7, '2024-01-02');",
+    )
+    .unwrap();
+
+    let query = sqlite::select_all::<Post>()
+        .from(Post::default())
+        .after_keys(vec![
+            ("created_at", Value::Literal("2024-01-02".to_string())),
+            ("id", Value::Literal("2".to_string())),
+        ]);
+    let (sql, params) = query.to_sql_with_params();
+    assert!(sql.contains("WHERE (\"created_at\", \"id\") > (?, ?)"));
+    assert!(sql.contains("ORDER BY \"created_at\", \"id\" ASC"));
+    assert_eq!(params.len(), 2);
+
+    let mut ids = Vec::new();
+    let mut token: Option<PageToken> = None;
+    loop {
+        let keys = match &token {
+            Some(token) => token.to_values().unwrap(),
+            None => vec![Value::Null, Value::Null],
+        };
+        let mut keys = keys.into_iter();
+        let page = sqlite::select_all::<Post>()
+            .from(Post::default())
+            .after_keys(vec![
+                ("created_at", keys.next().unwrap()),
+                ("id", keys.next().unwrap()),
+            ])
+            .limit(2)
+            .build_page(&conn)
+            .unwrap();
+
+        ids.extend(page.rows.iter().map(|post| *post.id.get().unwrap()));
+
+        match page.page_token() {
+            Some(next) => token = Some(next.to_string().parse().unwrap()),
+            None => break,
+        }
+    }
+
+    assert_eq!(ids, vec![5, 6, 1, 2, 4, 7, 3]);
+    assert!(token.unwrap().key().is_err());
+}
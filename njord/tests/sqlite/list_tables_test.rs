@@ -0,0 +1,76 @@
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::schema::ColumnDef;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table)]
+#[table_name = "accounts"]
+pub struct Account {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub email: String,
+    pub nickname: Option<String>,
+}
+
+#[test]
+fn list_created_table() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Account::default())]).unwrap();
+
+    let tables = sqlite::list_tables(&conn).unwrap();
+
+    assert_eq!(tables, vec!["accounts".to_string()]);
+}
+
+#[test]
+fn list_columns_of_table() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, email TEXT NOT NULL, nickname TEXT, balance INTEGER NOT NULL DEFAULT 0);",
+    )
+    .unwrap();
+
+    let columns = sqlite::table_columns(&conn, "accounts").unwrap();
+
+    assert_eq!(
+        columns,
+        vec![
+            ColumnDef {
+                name: "id".to_string(),
+                data_type: "INTEGER".to_string(),
+                nullable: true,
+                default_value: None,
+                primary_key: true,
+            },
+            ColumnDef {
+                name: "email".to_string(),
+                data_type: "TEXT".to_string(),
+                nullable: false,
+                default_value: None,
+                primary_key: false,
+            },
+            ColumnDef {
+                name: "nickname".to_string(),
+                data_type: "TEXT".to_string(),
+                nullable: true,
+                default_value: None,
+                primary_key: false,
+            },
+            ColumnDef {
+                name: "balance".to_string(),
+                data_type: "INTEGER".to_string(),
+                nullable: false,
+                default_value: Some("0".to_string()),
+                primary_key: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn unknown_table_has_no_columns() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    assert!(sqlite::table_columns(&conn, "missing").unwrap().is_empty());
+}
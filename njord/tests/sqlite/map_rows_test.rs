@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "cities"]
+pub struct City {
+    pub id: usize,
+    pub name: String,
+    pub population: i64,
+}
+
+#[test]
+fn map_rows_into_tuples() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE cities (id INTEGER PRIMARY KEY, name TEXT NOT NULL, population INTEGER NOT NULL);
+        INSERT INTO cities (id, name, population) VALUES
+            (1, 'Stockholm', 984748),
+            (2, 'Gothenburg', 604616),
+            (3, 'Malmo', 357377);",
+    )
+    .unwrap();
+
+    let cities: Vec<(String, i64)> = sqlite::select(vec![
+        Column::Text("name".to_string()),
+        Column::Text("population".to_string()),
+    ])
+    .from(City::default())
+    .order_by(HashMap::from([(vec!["id".to_string()], "ASC".to_string())]))
+    .map_rows(&conn, |row| Ok((row.get(0)?, row.get(1)?)))
+    .unwrap();
+
+    assert_eq!(
+        cities,
+        vec![
+            ("Stockholm".to_string(), 984748),
+            ("Gothenburg".to_string(), 604616),
+            ("Malmo".to_string(), 357377),
+        ]
+    );
+}
+
+#[test]
+fn map_rows_returns_closure_error() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE cities (id INTEGER PRIMARY KEY, name TEXT NOT NULL, population INTEGER NOT NULL);
+        INSERT INTO cities (id, name, population) VALUES (1, 'Stockholm', 984748);",
+    )
+    .unwrap();
+
+    let result = sqlite::select(vec![Column::Text("name".to_string())])
+        .from(City::default())
+        .map_rows(&conn, |row| row.get::<_, i64>(5));
+
+    assert!(result.is_err());
+}
+
+#[derive(Table, Clone)]
+#[table_name = "payments"]
+pub struct Payment {
+    pub id: usize,
+    pub payer: String,
+    pub amount: u32,
+}
+
+#[test]
+fn pluck_single_column() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Payment::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO payments (id, payer, amount) VALUES
+            (1, 'mjovanc', 250), (2, 'otheruser', 100), (3, 'mjovanc', 75);",
+    )
+    .unwrap();
+
+    let amounts = sqlite::select_all::<Payment>()
+        .from(Payment::default())
+        .where_clause(Condition::eq(
+            "payer",
+            Value::Literal("mjovanc".to_string()),
+        ))
+        .order_by(HashMap::from([(vec!["id".to_string()], "ASC".to_string())]))
+        .pluck::<u32>(&conn, "amount")
+        .unwrap();
+
+    assert_eq!(amounts, vec![250, 75]);
+}
@@ -0,0 +1,214 @@
+use std::fs;
+use std::path::PathBuf;
+
+use njord::migration::Migrator;
+use njord::sqlite;
+
+/// Creates a migrations directory with the given `(version, up.sql, down.sql)` migrations.
+fn create_migrations(name: &str, migrations: &[(&str, &str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("njord_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+
+    for (version, up, down) in migrations {
+        let migration_dir = dir.join(version);
+        fs::create_dir_all(&migration_dir).unwrap();
+        fs::write(migration_dir.join("up.sql"), up).unwrap();
+        fs::write(migration_dir.join("down.sql"), down).unwrap();
+    }
+
+    dir
+}
+
+fn table_exists(conn: &rusqlite::Connection, name: &str) -> bool {
+    conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [name],
+        |row| row.get::<usize, i64>(0),
+    )
+    .unwrap()
+        == 1
+}
+
+#[test]
+fn migrator_runs_pending_migrations() {
+    let dir = create_migrations(
+        "migrator_run",
+        &[
+            (
+                "00000000000002_create_posts",
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));",
+                "DROP TABLE posts;",
+            ),
+            (
+                "00000000000001_create_users",
+                "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+                "DROP TABLE users;",
+            ),
+        ],
+    );
+    let conn = sqlite::open_in_memory().unwrap();
+    let migrator = Migrator::new(&conn, &dir);
+
+    let pending: Vec<String> = migrator
+        .pending()
+        .unwrap()
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+    assert_eq!(
+        pending,
+        vec!["00000000000001_create_users", "00000000000002_create_posts"]
+    );
+
+    let applied = migrator.run().expect("Failed to run migrations");
+    assert_eq!(applied, pending);
+    assert!(table_exists(&conn, "users"));
+    assert!(table_exists(&conn, "posts"));
+    assert!(migrator.pending().unwrap().is_empty());
+
+    // running again is a no-op
+    assert!(migrator.run().unwrap().is_empty());
+
+    let rolled_back = migrator.rollback("00000000000001").unwrap();
+    assert_eq!(rolled_back, vec!["00000000000002_create_posts"]);
+    assert!(table_exists(&conn, "users"));
+    assert!(!table_exists(&conn, "posts"));
+    assert_eq!(
+        migrator.applied().unwrap(),
+        vec!["00000000000001_create_users"]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn migrator_rolls_back_failing_migration() {
+    let dir = create_migrations(
+        "migrator_failure",
+        &[(
+            "00000000000001_broken",
+            "CREATE TABLE tags (id INTEGER PRIMARY KEY); INSERT INTO missing VALUES (1);",
+            "DROP TABLE tags;",
+        )],
+    );
+    let conn = sqlite::open_in_memory().unwrap();
+    let migrator = Migrator::new(&conn, &dir);
+
+    assert!(migrator.run().is_err());
+    assert!(!table_exists(&conn, "tags"));
+    assert!(migrator.applied().unwrap().is_empty());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn migrator_runs_embedded_migrations() {
+    let conn = sqlite::open_in_memory().unwrap();
+    let migrator = Migrator::from_embedded(
+        &conn,
+        &[
+            (
+                "00000000000002_add_email",
+                "ALTER TABLE accounts ADD COLUMN email TEXT;",
+                "ALTER TABLE accounts DROP COLUMN email;",
+            ),
+            (
+                "00000000000001_create_accounts",
+                "CREATE TABLE accounts (id INTEGER PRIMARY KEY, name TEXT);",
+                "DROP TABLE accounts;",
+            ),
+        ],
+    );
+
+    let applied = migrator.run().expect("Failed to run migrations");
+    assert_eq!(
+        applied,
+        vec!["00000000000001_create_accounts", "00000000000002_add_email"]
+    );
+
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO accounts (name, email) VALUES ('mjovanc', 'mjovanc@icloud.com');",
+    )
+    .expect("Failed to INSERT into migrated table");
+}
+
+#[test]
+fn migrator_runs_next_steps_only() {
+    let conn = sqlite::open_in_memory().unwrap();
+    let migrator = Migrator::from_embedded(
+        &conn,
+        &[
+            (
+                "00000000000001_create_users",
+                "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+                "DROP TABLE users;",
+            ),
+            (
+                "00000000000002_create_posts",
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY);",
+                "DROP TABLE posts;",
+            ),
+            (
+                "00000000000003_create_comments",
+                "CREATE TABLE comments (id INTEGER PRIMARY KEY);",
+                "DROP TABLE comments;",
+            ),
+        ],
+    );
+
+    let applied = migrator.run_steps(1).expect("Failed to run migrations");
+    assert_eq!(applied, vec!["00000000000001_create_users"]);
+    assert!(table_exists(&conn, "users"));
+    assert!(!table_exists(&conn, "posts"));
+    assert_eq!(migrator.pending().unwrap().len(), 2);
+
+    let applied = migrator.run_steps(1).expect("Failed to run migrations");
+    assert_eq!(applied, vec!["00000000000002_create_posts"]);
+    assert!(!table_exists(&conn, "comments"));
+}
+
+const RESET_MIGRATIONS: [(&str, &str, &str); 2] = [
+    (
+        "00000000000001_create_users",
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        "DROP TABLE users;",
+    ),
+    (
+        "00000000000002_create_posts",
+        "CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER REFERENCES users(id));",
+        "DROP TABLE posts;",
+    ),
+];
+
+#[test]
+fn migrator_resets_all_migrations() {
+    let conn = sqlite::open_in_memory().unwrap();
+    let migrator = Migrator::from_embedded(&conn, &RESET_MIGRATIONS);
+    migrator.run().expect("Failed to run migrations");
+
+    let rolled_back = migrator.reset().expect("Failed to reset migrations");
+
+    assert_eq!(
+        rolled_back,
+        vec!["00000000000002_create_posts", "00000000000001_create_users"]
+    );
+    assert!(migrator.applied().unwrap().is_empty());
+    assert!(!table_exists(&conn, "users"));
+    assert!(!table_exists(&conn, "posts"));
+}
+
+#[test]
+fn migrator_clears_history_without_rolling_back() {
+    let conn = sqlite::open_in_memory().unwrap();
+    let migrator = Migrator::from_embedded(&conn, &RESET_MIGRATIONS);
+    migrator.run().expect("Failed to run migrations");
+
+    let cleared = migrator.clear_history().expect("Failed to clear history");
+
+    assert_eq!(cleared, 2);
+    assert!(migrator.applied().unwrap().is_empty());
+    assert_eq!(migrator.pending().unwrap().len(), 2);
+    assert!(table_exists(&conn, "users"));
+    assert!(table_exists(&conn, "posts"));
+}
@@ -1,10 +1,54 @@
+mod aggregate_test;
+mod alter_test;
+mod blob_test;
+mod build_json_test;
+mod check_test;
+mod collation_test;
+mod column_order_test;
+mod column_test;
+mod compress_test;
+mod condition_test;
+mod count_test;
+mod delete_by_pks_test;
 mod delete_test;
+mod dry_run_test;
+mod enum_test;
+mod dialect_test;
+mod distinct_test;
+mod exists_test;
+mod explain_test;
+mod foreign_key_test;
+mod generated_column_test;
+mod index_test;
+mod init_test;
 mod insert_test;
+mod integer_overflow_test;
+mod keyset_test;
+mod json_test;
+mod list_tables_test;
+mod map_rows_test;
+mod migration_test;
+mod null_test;
 mod open_test;
+mod order_by_expr_test;
 mod raw_test;
+mod read_only_test;
+mod rename_test;
+mod reserved_words_test;
+mod schema_test;
 mod select_joins_test;
+mod schema_diff_test;
 mod select_test;
+mod self_join_test;
+mod slow_query_test;
+mod soft_delete_test;
+mod strict_types_test;
+mod subquery_test;
+mod transaction_test;
+mod type_registry_test;
+mod unique_test;
 mod update_test;
+mod without_rowid_test;
 
 use njord::keys::{AutoIncrementPrimaryKey, PrimaryKey};
 use njord::table::Table;
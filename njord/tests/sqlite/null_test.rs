@@ -0,0 +1,123 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::Connection;
+
+#[derive(Table, Clone)]
+#[table_name = "contacts"]
+pub struct Contact {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+    pub phone: String,
+}
+
+fn setup() -> Connection {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE contacts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            phone TEXT
+        );
+        INSERT INTO contacts (name, phone) VALUES
+            ('mjovanc', '555-0100'),
+            ('otheruser', NULL);",
+    )
+    .expect("Failed to create contacts");
+
+    conn
+}
+
+fn columns<'a>() -> Vec<Column<'a>> {
+    vec![
+        Column::Text("id".to_string()),
+        Column::Text("name".to_string()),
+        Column::Text("phone".to_string()),
+    ]
+}
+
+#[test]
+fn select_null_safe_eq_null() {
+    let conn = setup();
+
+    let result = sqlite::select(columns())
+        .from(Contact::default())
+        .where_clause(Condition::NullSafeEq("phone".to_string(), Value::Null))
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].name, "otheruser");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_null_safe_eq_literal() {
+    let conn = setup();
+
+    let result = sqlite::select(columns())
+        .from(Contact::default())
+        .where_clause(Condition::NullSafeEq(
+            "phone".to_string(),
+            Value::Literal("555-0100".to_string()),
+        ))
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].name, "mjovanc");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_eq_null_is_null() {
+    let conn = setup();
+
+    let query = sqlite::select(columns())
+        .from(Contact::default())
+        .where_clause(Condition::eq("phone", Value::Null));
+
+    let (sql, params) = query.to_sql_with_params();
+    assert!(sql.contains("WHERE \"phone\" IS NULL"));
+    assert!(params.is_empty());
+
+    match query.build(&conn) {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].name, "otheruser");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_ne_null_is_not_null() {
+    let conn = setup();
+
+    let condition = Condition::ne("phone", Value::Null);
+    assert_eq!(condition.build(), "\"phone\" IS NOT NULL");
+
+    let result = sqlite::select(columns())
+        .from(Contact::default())
+        .where_clause(condition)
+        .build(&conn);
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].name, "mjovanc");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
@@ -0,0 +1,171 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord::util::JoinType;
+use njord_derive::Table;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Table, Clone)]
+#[table_name = "products"]
+pub struct Product {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub name: String,
+    pub category: String,
+}
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Product::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO products (name, category) VALUES
+            ('blue shirt', 'clothing'), ('shirt', 'clothing'), ('red shirt', 'clothing'),
+            ('crate', 'storage'), ('shirt box', 'storage');",
+    )
+    .unwrap();
+    conn
+}
+
+#[test]
+fn order_by_expression_with_bound_value() {
+    let conn = setup();
+
+    let mut order_by = HashMap::new();
+    order_by.insert(vec!["name".to_string()], "ASC".to_string());
+
+    // Exact matches first, then prefix matches, then the rest by name
+    let query = sqlite::select_all::<Product>()
+        .from(Product::default())
+        .where_clause(Condition::eq(
+            "category",
+            Value::Literal("clothing".to_string()),
+        ))
+        .order_by_expr(
+            "CASE WHEN name = ? THEN 0 WHEN name LIKE ? THEN 1 ELSE 2 END",
+            vec![
+                Value::Literal("shirt".to_string()),
+                Value::Literal("shirt%".to_string()),
+            ],
+            "ASC",
+        )
+        .order_by(order_by);
+
+    let (sql, params) = query.clone().to_sql_with_params();
+    assert!(sql.contains(
+        "ORDER BY CASE WHEN name = ? THEN 0 WHEN name LIKE ? THEN 1 ELSE 2 END ASC, \"name\" ASC"
+    ));
+    assert_eq!(
+        params
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>(),
+        vec!["clothing", "shirt", "shirt%"]
+    );
+
+    let products = query.build(&conn).unwrap();
+    let names: Vec<&str> = products.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["shirt", "blue shirt", "red shirt"]);
+}
+
+#[test]
+fn order_by_expression_with_distinct_by() {
+    let conn = setup();
+
+    // The first product of each category is the one ranked first by the expression
+    let products = sqlite::select_all::<Product>()
+        .from(Product::default())
+        .distinct_by(vec!["category".to_string()])
+        .order_by_expr(
+            "CASE WHEN name LIKE ? THEN 0 ELSE 1 END",
+            vec![Value::Literal("shirt%".to_string())],
+            "ASC",
+        )
+        .build(&conn)
+        .unwrap();
+
+    let mut names: Vec<&str> = products.iter().map(|p| p.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["shirt", "shirt box"]);
+}
+
+#[derive(Table, Clone)]
+#[table_name = "stock"]
+pub struct Stock {
+    pub product_id: usize,
+    pub warehouse: String,
+}
+
+#[test]
+fn order_by_expression_with_distinct_by_and_join() {
+    let conn = setup();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE stock (product_id INTEGER NOT NULL, warehouse TEXT NOT NULL);
+        INSERT INTO stock (product_id, warehouse) VALUES
+            (1, 'north'), (2, 'south'), (3, 'north'), (4, 'north'), (5, 'south');",
+    )
+    .unwrap();
+
+    // The window ORDER BY comes before the join in the query, so its value is bound first
+    let query = sqlite::select::<Product>(vec![
+        Column::Text("products.id".to_string()),
+        Column::Text("products.name".to_string()),
+        Column::Text("products.category".to_string()),
+    ])
+    .from(Product::default())
+    .join_table(JoinType::Inner, Arc::new(Stock::default()))
+    .on(
+        Condition::eq("stock.product_id", Value::Column("products.id".to_string())).and(
+            Condition::eq("stock.warehouse", Value::Literal("north".to_string())),
+        ),
+    )
+    .distinct_by(vec!["category".to_string()])
+    .order_by_expr(
+        "CASE WHEN name LIKE ? THEN 0 ELSE 1 END",
+        vec![Value::Literal("red%".to_string())],
+        "ASC",
+    );
+
+    let (sql, params) = query.clone().to_sql_with_params();
+    let window = sql.find("CASE WHEN name LIKE ?").unwrap();
+    let join = sql.find("\"stock\".\"warehouse\" = ?").unwrap();
+    assert!(window < join);
+    assert_eq!(
+        params
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>(),
+        vec!["red%", "north", "red%"]
+    );
+
+    let products = query.build(&conn).unwrap();
+    let mut names: Vec<&str> = products.iter().map(|p| p.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["crate", "red shirt"]);
+}
+
+#[test]
+fn order_by_expression_binds_values_with_quotes() {
+    let conn = setup();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO products (name, category) VALUES ('O''Neil shirt', 'clothing');",
+    )
+    .unwrap();
+
+    let products = sqlite::select_all::<Product>()
+        .from(Product::default())
+        .order_by_expr(
+            "CASE WHEN name = ? THEN 0 ELSE 1 END",
+            vec![Value::Literal("O'Neil shirt".to_string())],
+            "ASC",
+        )
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(products.len(), 6);
+    assert_eq!(products[0].name, "O'Neil shirt");
+}
@@ -1,7 +1,33 @@
+use std::collections::HashMap;
 use std::path::Path;
 
+use njord::condition::Value;
 use njord::sqlite;
+use njord::Error;
 use njord_derive::sql;
+use rusqlite::Connection;
+
+use super::User;
+
+fn setup() -> Connection {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1'),
+            ('otheruser', 'otheruser@icloud.com', 'Some Random Address 2');",
+    )
+    .expect("Failed to create users");
+
+    conn
+}
 
 #[test]
 fn execute_raw_sql() {
@@ -20,5 +46,199 @@ fn execute_raw_sql() {
 
     let results = sqlite::raw_execute(&conn, &sql);
 
-    assert!(!results.is_err());
+    assert!(results.is_ok());
+}
+
+#[test]
+fn query_raw_positional() {
+    let conn = setup();
+
+    let result = sqlite::query_raw::<User>(
+        &conn,
+        "SELECT id, username, email, address FROM users WHERE username = ? AND id = ?",
+        vec![
+            Value::Literal("otheruser".to_string()),
+            Value::Literal("2".to_string()),
+        ],
+    );
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].email, "otheruser@icloud.com");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn query_raw_named() {
+    let conn = setup();
+
+    let mut params = HashMap::new();
+    params.insert(
+        "username".to_string(),
+        Value::Literal("mjovanc".to_string()),
+    );
+    params.insert(
+        ":address".to_string(),
+        Value::Literal("Some Random Address 1".to_string()),
+    );
+
+    let result = sqlite::query_raw_named::<User>(
+        &conn,
+        "SELECT id, username, email, address FROM users WHERE username = :username AND address = :address",
+        params,
+    );
+
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].email, "mjovanc@icloud.com");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn query_raw_named_missing_parameter() {
+    let conn = setup();
+
+    let mut params = HashMap::new();
+    params.insert(
+        "username".to_string(),
+        Value::Literal("mjovanc".to_string()),
+    );
+
+    let result = sqlite::query_raw_named::<User>(
+        &conn,
+        "SELECT id, username, email, address FROM users WHERE username = :username AND email = :email",
+        params,
+    );
+
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+}
+
+#[test]
+fn execute_update_returns_affected_rows() {
+    let conn = setup();
+
+    let affected = sqlite::execute(
+        &conn,
+        "UPDATE users SET address = ? WHERE username LIKE ?",
+        vec![
+            Value::Literal("Updated Address".to_string()),
+            Value::Literal("%user%".to_string()),
+        ],
+    );
+
+    assert_eq!(affected.unwrap(), 1);
+
+    let affected = sqlite::execute(&conn, "UPDATE users SET email = NULL WHERE 1 = 0", vec![]);
+
+    assert_eq!(affected.unwrap(), 0);
+}
+
+#[test]
+fn multiple_statements_are_rejected_without_opt_in() {
+    let conn = setup();
+    let sql = "UPDATE users SET address = 'Moved'; DELETE FROM users;";
+
+    let result = sqlite::raw(sql).execute(&conn);
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+    let result = sqlite::execute(&conn, sql, vec![]);
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+    // nothing was executed
+    let users = sqlite::query_raw::<User>(&conn, "SELECT * FROM users", vec![]).unwrap();
+    assert_eq!(users.len(), 2);
+    assert!(users.iter().all(|u| u.address != "Moved"));
+
+    let affected = sqlite::raw(sql).allow_multiple().execute(&conn).unwrap();
+    assert_eq!(affected, 2);
+    let users = sqlite::query_raw::<User>(&conn, "SELECT * FROM users", vec![]).unwrap();
+    assert!(users.is_empty());
+}
+
+#[test]
+fn trigger_body_is_single_statement() {
+    let conn = setup();
+    sqlite::raw_execute(&conn, "CREATE TABLE audit (entry TEXT NOT NULL);").unwrap();
+
+    let trigger = "CREATE TRIGGER audit_users AFTER UPDATE ON users
+        BEGIN
+            INSERT INTO audit (entry) VALUES (CASE WHEN new.address = 'Moved' THEN 'moved' ELSE 'updated' END);
+            INSERT INTO audit (entry) VALUES (new.username);
+        END;";
+
+    // the trigger followed by another statement is still rejected
+    let result = sqlite::raw(&format!("{} DELETE FROM users;", trigger)).execute(&conn);
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+    sqlite::raw(trigger).execute(&conn).unwrap();
+
+    let affected = sqlite::execute(
+        &conn,
+        "UPDATE users SET address = 'Moved' WHERE username = 'mjovanc'",
+        vec![],
+    );
+    assert_eq!(affected.unwrap(), 1);
+
+    let mut stmt = conn.prepare("SELECT entry FROM audit").unwrap();
+    let entries: Vec<String> = stmt
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(entries, vec!["moved", "mjovanc"]);
+}
+
+#[test]
+fn semicolons_in_literals_and_comments_are_single_statement() {
+    let conn = setup();
+
+    let affected = sqlite::raw(
+        "-- move the users; both of them
+        UPDATE users SET address = 'Street 1; Floor 2' /* ; */ WHERE username != ?;",
+    )
+    .params(vec![Value::Literal("nobody".to_string())])
+    .execute(&conn);
+
+    assert_eq!(affected.unwrap(), 2);
+}
+
+#[test]
+fn numeric_looking_text_is_bound_as_text() {
+    let conn = setup();
+
+    sqlite::execute(
+        &conn,
+        "INSERT INTO users (username, email, address) VALUES (?, ?, ?)",
+        vec![
+            Value::Literal("0123".to_string()),
+            Value::Literal("1e3".to_string()),
+            Value::Literal("42".to_string()),
+        ],
+    )
+    .unwrap();
+
+    let users = sqlite::query_raw::<User>(
+        &conn,
+        "SELECT * FROM users WHERE username = ?",
+        vec![Value::Literal("0123".to_string())],
+    )
+    .unwrap();
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].email, "1e3");
+    assert_eq!(users[0].address, "42");
+
+    // Canonical numbers are still bound as numbers
+    let users = sqlite::query_raw::<User>(
+        &conn,
+        "SELECT * FROM users WHERE id > ?",
+        vec![Value::Literal("2".to_string())],
+    )
+    .unwrap();
+    assert_eq!(users.len(), 1);
 }
@@ -0,0 +1,109 @@
+use njord::condition::{Condition, Value};
+use njord::error::Error;
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite::{self, ReadOnlyConnection};
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "replicas"]
+pub struct Replica {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub region: String,
+}
+
+fn replica(region: &str) -> Replica {
+    Replica {
+        id: AutoIncrementPrimaryKey::default(),
+        region: region.to_string(),
+    }
+}
+
+/// Creates a database file with one replica and opens it in read-only mode.
+fn open_read_only(name: &str) -> (ReadOnlyConnection, std::path::PathBuf) {
+    let db_path = std::env::temp_dir().join(format!(
+        "njord_read_only_{}_{}.db",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&db_path);
+
+    let conn = sqlite::open(&db_path).unwrap();
+    sqlite::init(&conn, vec![Box::new(Replica::default())]).unwrap();
+    sqlite::insert(&conn, vec![replica("eu-north")]).unwrap();
+    drop(conn);
+
+    (ReadOnlyConnection::open(&db_path).unwrap(), db_path)
+}
+
+/// Selects the regions of the replicas of a read-only connection.
+fn regions(conn: &ReadOnlyConnection) -> Vec<String> {
+    sqlite::select_all::<Replica>()
+        .from(Replica::default())
+        .build(conn)
+        .unwrap()
+        .into_iter()
+        .map(|replica| replica.region)
+        .collect()
+}
+
+#[test]
+fn insert_on_read_only_file_fails() {
+    let (conn, db_path) = open_read_only("insert");
+
+    let result = sqlite::insert(&conn, vec![replica("us-east")]);
+    assert!(matches!(result, Err(Error::ReadOnly(_))));
+    assert_eq!(regions(&conn), vec!["eu-north".to_string()]);
+
+    drop(conn);
+    std::fs::remove_file(&db_path).unwrap();
+}
+
+#[test]
+fn update_on_read_only_file_fails() {
+    let (conn, db_path) = open_read_only("update");
+
+    let result = sqlite::update(replica("us-east"))
+        .set(vec!["region".to_string()])
+        .where_clause(Condition::Eq(
+            "region".to_string(),
+            Value::Literal("eu-north".to_string()),
+        ))
+        .build(&conn);
+    assert!(matches!(result, Err(Error::ReadOnly(_))));
+    assert_eq!(regions(&conn), vec!["eu-north".to_string()]);
+
+    drop(conn);
+    std::fs::remove_file(&db_path).unwrap();
+}
+
+#[test]
+fn delete_on_read_only_file_fails() {
+    let (conn, db_path) = open_read_only("delete");
+
+    let result = sqlite::delete().from(Replica::default()).build(&conn);
+    assert!(matches!(result, Err(Error::ReadOnly(_))));
+    assert_eq!(regions(&conn), vec!["eu-north".to_string()]);
+
+    drop(conn);
+    std::fs::remove_file(&db_path).unwrap();
+}
+
+#[test]
+fn ddl_on_read_only_file_fails() {
+    let (conn, db_path) = open_read_only("ddl");
+
+    let result = sqlite::raw_execute(&conn, "DROP TABLE replicas;");
+    assert!(matches!(result, Err(Error::ReadOnly(_))));
+
+    let result = sqlite::raw_execute(&conn, "CREATE TEMP TABLE audit (id INTEGER);");
+    assert!(matches!(result, Err(Error::ReadOnly(_))));
+
+    let result = sqlite::init(&conn, vec![Box::new(Replica::default())]);
+    assert!(matches!(result, Err(Error::ReadOnly(_))));
+
+    assert_eq!(regions(&conn), vec!["eu-north".to_string()]);
+
+    drop(conn);
+    std::fs::remove_file(&db_path).unwrap();
+}
@@ -0,0 +1,57 @@
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[rename_all = "snake_case"]
+#[allow(non_snake_case)]
+pub struct UserAccount {
+    pub id: usize,
+    pub createdAt: String,
+}
+
+#[derive(Table, Clone)]
+#[table_name = "accounts"]
+#[rename_all = "SCREAMING_SNAKE_CASE"]
+pub struct LegacyAccount {
+    pub id: usize,
+    #[column = "owner"]
+    pub owner_name: String,
+    pub created_at: String,
+}
+
+#[test]
+fn rename_all_snake_case() {
+    let account = UserAccount::default();
+
+    assert_eq!(account.get_name(), "user_account");
+    assert_eq!(account.get_column_fields(), vec!["id", "created_at"]);
+    assert!(account.get_columns().contains_key("created_at"));
+}
+
+#[test]
+fn rename_all_keeps_explicit_names() {
+    let account = LegacyAccount::default();
+
+    assert_eq!(account.get_name(), "accounts");
+    assert_eq!(account.get_column_fields(), vec!["ID", "owner", "CREATED_AT"]);
+}
+
+#[test]
+fn rename_all_round_trip() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(UserAccount::default())]).unwrap();
+
+    let account = UserAccount {
+        id: 1,
+        createdAt: "2024-01-01".to_string(),
+    };
+    sqlite::insert(&conn, vec![account]).unwrap();
+
+    let accounts = sqlite::select_all::<UserAccount>()
+        .from(UserAccount::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(accounts[0].createdAt, "2024-01-01");
+}
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use njord::condition::{Condition, Value};
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "purchases"]
+pub struct Purchase {
+    pub id: usize,
+    pub order: usize,
+    pub select: String,
+}
+
+#[test]
+fn reserved_word_columns() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Purchase::default())]).unwrap();
+
+    let purchases = vec![
+        Purchase {
+            id: 1,
+            order: 2,
+            select: "second".to_string(),
+        },
+        Purchase {
+            id: 2,
+            order: 1,
+            select: "first".to_string(),
+        },
+    ];
+    sqlite::insert(&conn, purchases).unwrap();
+
+    let mut order = HashMap::new();
+    order.insert(vec!["order".to_string()], "ASC".to_string());
+
+    let query = sqlite::select_all::<Purchase>()
+        .from(Purchase::default())
+        .where_clause(Condition::Ge(
+            "order".to_string(),
+            Value::Literal("1".to_string()),
+        ))
+        .order_by(order);
+    assert!(query.build_query().contains("\"order\", \"select\""));
+
+    match query.build(&conn) {
+        Ok(purchases) => {
+            let selects: Vec<&str> = purchases.iter().map(|p| p.select.as_str()).collect();
+            assert_eq!(selects, vec!["first", "second"]);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
@@ -0,0 +1,65 @@
+use njord::sqlite;
+use rusqlite::Connection;
+
+fn open(sql: &str) -> Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(&conn, sql).unwrap();
+    conn
+}
+
+#[test]
+fn diff_adds_missing_column() {
+    let dev = open("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, email TEXT);");
+    let prod = open("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);");
+
+    let statements = sqlite::diff_schemas(&dev, &prod).unwrap();
+    assert_eq!(
+        statements,
+        vec!["ALTER TABLE \"users\" ADD COLUMN \"email\" TEXT;"]
+    );
+
+    // Applying the statements makes the schemas match
+    sqlite::raw_execute(&prod, &statements.join("\n")).unwrap();
+    assert!(sqlite::diff_schemas(&dev, &prod).unwrap().is_empty());
+}
+
+#[test]
+fn diff_creates_missing_and_drops_extra_tables() {
+    let dev = open(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);
+        CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT);",
+    );
+    let prod = open(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY);
+        CREATE TABLE legacy (id INTEGER PRIMARY KEY);",
+    );
+
+    assert_eq!(
+        sqlite::diff_schemas(&dev, &prod).unwrap(),
+        vec![
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT);",
+            "DROP TABLE \"legacy\";",
+        ]
+    );
+}
+
+#[test]
+fn diff_drops_extra_columns_and_changes_types() {
+    let dev = open("CREATE TABLE items (id INTEGER PRIMARY KEY, price REAL NOT NULL);");
+    let prod = open(
+        "CREATE TABLE items (id INTEGER PRIMARY KEY, price INTEGER NOT NULL, note TEXT);
+        INSERT INTO items (id, price, note) VALUES (1, 5, 'cheap');",
+    );
+
+    let statements = sqlite::diff_schemas(&dev, &prod).unwrap();
+    sqlite::raw_execute(&prod, &statements.join("\n")).unwrap();
+
+    assert_eq!(
+        sqlite::table_columns(&prod, "items").unwrap(),
+        sqlite::table_columns(&dev, "items").unwrap()
+    );
+    let price: f64 = prod
+        .query_row("SELECT price FROM items WHERE id = 1", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(price, 5.0);
+}
@@ -0,0 +1,168 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::config::{ConfiguredConnection, ConnectionConfig};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::Connection;
+
+#[derive(Table, Clone)]
+#[table_name = "users"]
+#[schema = "reporting"]
+pub struct ReportingUser {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub username: String,
+}
+
+#[test]
+fn insert_and_select_with_schema() {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(&conn, "ATTACH DATABASE ':memory:' AS reporting;")
+        .expect("Failed to attach schema");
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE reporting.users (id INTEGER PRIMARY KEY AUTOINCREMENT, username TEXT NOT NULL);",
+    )
+    .expect("Failed to create table");
+
+    let table_row = ReportingUser {
+        id: AutoIncrementPrimaryKey::default(),
+        username: "mjovanc".to_string(),
+    };
+
+    let sql = sqlite::insert(&conn, vec![table_row]).expect("Failed to INSERT");
+    assert!(sql.contains("INSERT INTO reporting.users"));
+
+    let query = sqlite::select(vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+    ])
+    .from(ReportingUser::default());
+    assert!(query.build_query().contains("FROM reporting.users"));
+
+    let result = query.build(&conn);
+    match result {
+        Ok(r) => {
+            assert_eq!(r.len(), 1);
+            assert_eq!(r[0].username, "mjovanc");
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[derive(Table, Clone)]
+#[table_name = "users"]
+#[index(name = "idx_users_username", columns = "username")]
+pub struct User {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub username: String,
+}
+
+fn open_with_prefix(prefix: &str) -> ConfiguredConnection<Connection> {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+    let config = ConnectionConfig {
+        table_prefix: prefix.to_string(),
+        ..Default::default()
+    };
+    let conn = ConfiguredConnection::new(conn, config);
+    sqlite::init(&conn, vec![Box::new(User::default())]).expect("Failed to init tables");
+
+    conn
+}
+
+fn user(username: &str) -> User {
+    User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: username.to_string(),
+    }
+}
+
+#[test]
+fn init_with_table_prefix() {
+    let conn = open_with_prefix("app_");
+
+    assert_eq!(
+        sqlite::list_tables(&conn).expect("Failed to list tables"),
+        vec!["app_users".to_string()]
+    );
+
+    let indexes = sqlite::query_raw::<User>(
+        &conn,
+        "SELECT 0 AS id, name AS username FROM sqlite_master WHERE type = 'index'",
+        vec![],
+    )
+    .expect("Failed to list indexes");
+    assert_eq!(indexes.len(), 1);
+    assert_eq!(indexes[0].username, "app_idx_users_username");
+}
+
+#[test]
+fn insert_and_select_with_table_prefix() {
+    let app_conn = open_with_prefix("app_");
+    let admin_conn = open_with_prefix("admin_");
+
+    let sql = sqlite::insert(&app_conn, vec![user("mjovanc")]).expect("Failed to INSERT");
+    assert!(sql.contains("INSERT INTO app_users"));
+
+    // The prefix is set per connection, so the same query reads the table of each prefix
+    let query = sqlite::select(vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+    ])
+    .from(User::default());
+    assert!(query.build_query().contains("FROM users"));
+
+    let app_users = query.clone().build(&app_conn).expect("Failed to SELECT");
+    assert_eq!(app_users.len(), 1);
+    assert_eq!(app_users[0].username, "mjovanc");
+
+    let admin_users = query.build(&admin_conn).expect("Failed to SELECT");
+    assert!(admin_users.is_empty());
+}
+
+#[test]
+fn update_count_exists_and_delete_with_table_prefix() {
+    let conn = open_with_prefix("app_");
+    sqlite::insert(&conn, vec![user("mjovanc"), user("alice")]).expect("Failed to INSERT");
+
+    let mut renamed = user("bob");
+    renamed.id = AutoIncrementPrimaryKey::new(Some(2));
+    let updated = sqlite::update(renamed)
+        .set(vec!["username".to_string()])
+        .where_clause(Condition::eq("id", Value::Literal("2".to_string())))
+        .build(&conn)
+        .expect("Failed to UPDATE");
+    assert_eq!(updated, 1);
+
+    let count = sqlite::count(&conn, vec![])
+        .from(&User::default())
+        .build()
+        .expect("Failed to COUNT");
+    assert_eq!(count, 2);
+
+    let exists = sqlite::exists(&conn)
+        .from(&User::default())
+        .where_clause(Condition::eq("username", Value::Literal("bob".to_string())))
+        .build()
+        .expect("Failed to check EXISTS");
+    assert!(exists);
+
+    let deleted = sqlite::delete()
+        .from(User::default())
+        .where_clause(Condition::eq(
+            "username",
+            Value::Literal("mjovanc".to_string()),
+        ))
+        .build(&conn)
+        .expect("Failed to DELETE");
+    assert_eq!(deleted, 1);
+
+    let remaining = sqlite::select_all::<User>()
+        .from(User::default())
+        .build(&conn)
+        .expect("Failed to SELECT");
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].username, "bob");
+}
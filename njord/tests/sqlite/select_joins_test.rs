@@ -1,5 +1,7 @@
 use njord::condition::Condition;
+use njord::error::Error;
 use njord::sqlite;
+use njord::table::Table;
 use njord::util::JoinType;
 use njord::{column::Column, condition::Value};
 use std::path::Path;
@@ -23,7 +25,7 @@ fn select_inner_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "users.id".to_string(),
-        Value::Literal("products.user_id".to_string()),
+        Value::Column("products.user_id".to_string()),
     );
     match conn {
         Ok(ref c) => {
@@ -64,7 +66,7 @@ fn select_left_join() {
     // Assuming a hypothetical join condition: users.id = products.user_id
     let join_condition = Condition::Eq(
         "users.id".to_string(),
-        Value::Literal("products.user_id".to_string()),
+        Value::Column("products.user_id".to_string()),
     );
     match conn {
         Ok(ref c) => {
@@ -85,3 +87,239 @@ fn select_left_join() {
         Err(e) => panic!("Failed to SELECT: {:?}", e),
     }
 }
+
+#[derive(njord_derive::Table)]
+#[table_name = "accounts"]
+pub struct Account {
+    pub user_id: usize,
+    pub name: String,
+}
+
+#[derive(njord_derive::Table)]
+#[table_name = "orders"]
+pub struct Order {
+    pub id: usize,
+    pub user_id: usize,
+    pub total: f64,
+}
+
+#[test]
+fn select_join_using() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (user_id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, total REAL NOT NULL);
+        INSERT INTO accounts (user_id, name) VALUES (1, 'alice'), (2, 'bob'), (3, 'carol');
+        INSERT INTO orders (id, user_id, total) VALUES (1, 1, 10.0), (2, 1, 20.0), (3, 2, 5.0);",
+    )
+    .unwrap();
+
+    let columns = vec![
+        Column::Text("user_id".to_string()),
+        Column::Text("name".to_string()),
+    ];
+
+    let query = sqlite::select::<Account>(columns)
+        .from(Account::default())
+        .join_table(JoinType::Left, Arc::new(Order::default()))
+        .using(["user_id"]);
+
+    assert!(query
+        .build_query()
+        .contains("LEFT JOIN orders USING (\"user_id\")"));
+
+    match query.build(&conn) {
+        Ok(accounts) => {
+            let names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
+            assert_eq!(names, vec!["alice", "alice", "bob", "carol"]);
+        }
+        Err(e) => panic!("Failed to SELECT with JOIN USING: {:?}", e),
+    }
+}
+
+#[test]
+fn select_join_on() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (user_id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, total REAL NOT NULL);
+        INSERT INTO accounts (user_id, name) VALUES (1, 'alice'), (2, 'bob');
+        INSERT INTO orders (id, user_id, total) VALUES (1, 1, 10.0), (2, 1, 20.0), (3, 2, 5.0);",
+    )
+    .unwrap();
+
+    let columns = vec![
+        Column::Text("accounts.user_id".to_string()),
+        Column::Text("accounts.name".to_string()),
+    ];
+    let on = Condition::Eq(
+        "accounts.user_id".to_string(),
+        Value::Column("orders.user_id".to_string()),
+    )
+    .and(Condition::Gt(
+        "orders.total".to_string(),
+        Value::Literal("8".to_string()),
+    ));
+
+    let result = sqlite::select::<Account>(columns)
+        .from(Account::default())
+        .join_table(JoinType::Inner, Arc::new(Order::default()))
+        .on(on)
+        .build(&conn);
+
+    match result {
+        Ok(accounts) => assert_eq!(accounts.len(), 2),
+        Err(e) => panic!("Failed to SELECT with JOIN ON: {:?}", e),
+    }
+}
+
+#[derive(njord_derive::Table)]
+#[table_name = "accounts"]
+pub struct AccountOrder {
+    pub account_id: usize,
+    pub order_id: usize,
+}
+
+#[test]
+fn select_join_ambiguous_columns() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, name TEXT NOT NULL);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, total REAL NOT NULL);
+        INSERT INTO accounts (id, user_id, name) VALUES (7, 1, 'alice');
+        INSERT INTO orders (id, user_id, total) VALUES (42, 1, 10.0);",
+    )
+    .unwrap();
+
+    let on = Condition::Eq(
+        "accounts.user_id".to_string(),
+        Value::Column("orders.user_id".to_string()),
+    );
+
+    // Both tables have an `id` column, so the names collide in the result
+    let result = sqlite::select::<AccountOrder>(vec![
+        Column::Text("accounts.id".to_string()),
+        Column::Text("orders.id".to_string()),
+    ])
+    .from(AccountOrder::default())
+    .join_table(JoinType::Inner, Arc::new(Order::default()))
+    .on(&on)
+    .build(&conn);
+    assert!(matches!(result, Err(Error::AmbiguousColumn(ref column)) if column == "id"));
+
+    let result = sqlite::select::<AccountOrder>(vec![
+        Column::Text("accounts.id AS account_id".to_string()),
+        Column::Text("orders.id AS order_id".to_string()),
+    ])
+    .from(AccountOrder::default())
+    .join_table(JoinType::Inner, Arc::new(Order::default()))
+    .on(&on)
+    .build(&conn);
+
+    match result {
+        Ok(rows) => {
+            assert_eq!(rows[0].account_id, 7);
+            assert_eq!(rows[0].order_id, 42);
+        }
+        Err(e) => panic!("Failed to SELECT with JOIN: {:?}", e),
+    }
+}
+
+#[test]
+fn select_all_columns_of_joined_table() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (user_id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, total REAL NOT NULL);
+        INSERT INTO accounts (user_id, name) VALUES (1, 'alice'), (2, 'bob');
+        INSERT INTO orders (id, user_id, total) VALUES (1, 1, 10.0), (2, 1, 20.0), (3, 2, 5.0);",
+    )
+    .unwrap();
+
+    let query = sqlite::select::<Order>(vec![Column::table_all("orders")])
+        .from(Order::default())
+        .join_table(JoinType::Inner, Arc::new(Account::default()))
+        .on(Condition::Eq(
+            "accounts.user_id".to_string(),
+            Value::Column("orders.user_id".to_string()),
+        ))
+        .where_clause(Condition::Gt(
+            "orders.total".to_string(),
+            Value::Literal("8".to_string()),
+        ));
+
+    assert!(query
+        .build_query()
+        .starts_with("SELECT \"orders\".\"id\", \"orders\".\"user_id\", \"orders\".\"total\" FROM orders INNER JOIN accounts"));
+
+    match query.build(&conn) {
+        Ok(orders) => {
+            let totals: Vec<f64> = orders.iter().map(|o| o.total).collect();
+            assert_eq!(totals, vec![10.0, 20.0]);
+            assert!(orders.iter().all(|o| o.user_id == 1));
+        }
+        Err(e) => panic!("Failed to SELECT all columns of a joined table: {:?}", e),
+    }
+}
+
+#[test]
+fn select_join_binds_qualified_literals() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (user_id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+        CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL, total REAL NOT NULL);
+        INSERT INTO accounts (user_id, name) VALUES (1, 'alice'), (2, 'a.bob');
+        INSERT INTO orders (id, user_id, total) VALUES (1, 1, 10.0), (2, 2, 5.0);",
+    )
+    .unwrap();
+
+    let query = sqlite::select::<Order>(vec![Column::table_all("orders")])
+        .from(Order::default())
+        .join_table(JoinType::Inner, Arc::new(Account::default()))
+        .on(Condition::Eq(
+            "accounts.user_id".to_string(),
+            Value::Column("orders.user_id".to_string()),
+        )
+        .and(Condition::Eq(
+            "accounts.name".to_string(),
+            Value::Literal("a.bob".to_string()),
+        )));
+
+    let (sql, params) = query.to_sql_with_params();
+    assert!(sql.contains(
+        "ON (\"accounts\".\"user_id\" = \"orders\".\"user_id\") AND (\"accounts\".\"name\" = ?)"
+    ));
+    assert_eq!(
+        params
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>(),
+        vec!["a.bob"]
+    );
+
+    let orders = query.build(&conn).unwrap();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].id, 2);
+
+    // A qualified column compared with a value can't change the condition
+    let orders = sqlite::select::<Order>(vec![Column::table_all("orders")])
+        .from(Order::default())
+        .join_table(JoinType::Inner, Arc::new(Account::default()))
+        .on(Condition::Eq(
+            "accounts.user_id".to_string(),
+            Value::Literal("orders.user_id OR 1 = 1".to_string()),
+        ))
+        .build(&conn)
+        .unwrap();
+    assert!(orders.is_empty());
+}
@@ -1,6 +1,7 @@
 use njord::condition::Condition;
 use njord::keys::AutoIncrementPrimaryKey;
 use njord::sqlite;
+use njord::Error;
 use njord::{column::Column, condition::Value};
 use njord_derive::sql;
 use std::collections::HashMap;
@@ -582,3 +583,143 @@ fn raw_execute() {
         Err(e) => panic!("Failed to SELECT: {:?}", e),
     };
 }
+
+#[test]
+fn select_for_update_unsupported() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let result = sqlite::select(vec![Column::Text("id".to_string())])
+        .from(User::default())
+        .for_update()
+        .skip_locked()
+        .build(&conn);
+
+    assert!(matches!(result, Err(Error::Unsupported(_))));
+}
+
+#[test]
+fn select_with_capacity() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1'),
+            ('otheruser', 'otheruser@icloud.com', 'Some Random Address 2');",
+    )
+    .unwrap();
+
+    let result = sqlite::select_all::<User>()
+        .from(User::default())
+        .with_capacity(500)
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(result.len(), 2);
+    assert!(result.capacity() >= 500);
+
+    let result = sqlite::select_all::<User>()
+        .from(User::default())
+        .limit(10)
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(result.len(), 2);
+    assert!(result.capacity() >= 10);
+}
+
+#[test]
+fn select_limit_with_ties_unsupported() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let mut order_by = HashMap::new();
+    order_by.insert(vec!["email".to_string()], "ASC".to_string());
+
+    let result = sqlite::select_all::<User>()
+        .from(User::default())
+        .order_by(order_by)
+        .limit_with_ties(3)
+        .build(&conn);
+
+    assert!(matches!(result, Err(Error::Unsupported(_))));
+}
+
+#[test]
+fn select_build_raw() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1');",
+    )
+    .unwrap();
+
+    let columns = vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+    ];
+
+    let rows = sqlite::select::<User>(columns)
+        .from(User::default())
+        .build_raw(&conn)
+        .unwrap();
+
+    assert_eq!(
+        rows,
+        vec![vec![
+            rusqlite::types::Value::Integer(1),
+            rusqlite::types::Value::Text("mjovanc".to_string()),
+        ]]
+    );
+}
+
+#[test]
+fn select_where_opt() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Address 1'),
+            ('otheruser', 'other@example.com', 'Address 1'),
+            ('thirduser', 'third@example.com', 'Address 2');",
+    )
+    .unwrap();
+
+    let find_users = |username: Option<&str>, address: Option<&str>| {
+        sqlite::select_all::<User>()
+            .from(User::default())
+            .where_opt(username.map(|u| Condition::eq("username", Value::Literal(u.to_string()))))
+            .where_opt(address.map(|a| Condition::eq("address", Value::Literal(a.to_string()))))
+            .build(&conn)
+            .unwrap()
+            .len()
+    };
+
+    assert_eq!(find_users(None, None), 3);
+    assert_eq!(find_users(None, Some("Address 1")), 2);
+    assert_eq!(find_users(Some("otheruser"), Some("Address 1")), 1);
+    assert_eq!(find_users(Some("thirduser"), Some("Address 1")), 0);
+
+    let query = sqlite::select_all::<User>()
+        .from(User::default())
+        .where_opt(None)
+        .build_query();
+    assert!(!query.contains("WHERE"));
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::sqlite;
+use njord::table::Table;
+use njord::util::JoinType;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "employees"]
+pub struct Employee {
+    pub id: usize,
+    pub name: String,
+    pub manager_id: Option<usize>,
+}
+
+#[derive(Table)]
+#[table_name = "employees"]
+pub struct Reporting {
+    pub employee: String,
+    pub manager: String,
+}
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE employees (id INTEGER PRIMARY KEY, name TEXT NOT NULL, manager_id INTEGER);
+        INSERT INTO employees (id, name, manager_id) VALUES
+            (1, 'Alice', NULL),
+            (2, 'Bob', 1),
+            (3, 'Carol', 1),
+            (4, 'Dave', 2);",
+    )
+    .unwrap();
+
+    conn
+}
+
+#[test]
+fn self_join_with_aliases() {
+    let conn = setup();
+
+    let query = sqlite::select(vec![
+        Column::expr("a.name", vec![], "employee"),
+        Column::expr("b.name", vec![], "manager"),
+    ])
+    .from_as(Employee::default(), "a")
+    .join_table(JoinType::Inner, Arc::new(Employee::default()))
+    .alias("b")
+    .on(Condition::eq(
+        "a.manager_id",
+        Value::Column("b.id".to_string()),
+    ))
+    .where_clause(Condition::ne("b.name", Value::Literal("Bob".to_string())));
+
+    let sql = query.build_query();
+    assert!(sql.contains("FROM employees AS a INNER JOIN employees AS b ON"));
+
+    let mut rows: Vec<(String, String)> = query
+        .build_into::<Reporting>(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.employee, r.manager))
+        .collect();
+    rows.sort();
+
+    assert_eq!(
+        rows,
+        vec![
+            ("Bob".to_string(), "Alice".to_string()),
+            ("Carol".to_string(), "Alice".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn aliased_column_equals_string_value() {
+    let conn = setup();
+
+    let query = sqlite::select(vec![
+        Column::expr("a.name", vec![], "employee"),
+        Column::expr("b.name", vec![], "manager"),
+    ])
+    .from_as(Employee::default(), "a")
+    .join_table(JoinType::Inner, Arc::new(Employee::default()))
+    .alias("b")
+    .on(Condition::eq(
+        "a.manager_id",
+        Value::Column("b.id".to_string()),
+    ))
+    .where_clause(Condition::eq("b.name", Value::Literal("Bob".to_string())));
+
+    let (sql, params) = query.to_sql_with_params();
+    assert!(sql.contains("WHERE \"b\".\"name\" = ?"));
+    assert_eq!(
+        params
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>(),
+        vec!["Bob"]
+    );
+
+    let rows: Vec<(String, String)> = query
+        .build_into::<Reporting>(&conn)
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.employee, r.manager))
+        .collect();
+
+    assert_eq!(rows, vec![("Dave".to_string(), "Bob".to_string())]);
+}
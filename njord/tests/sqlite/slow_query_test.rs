@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use log::{Level, Log, Metadata, Record};
+use njord::config::{ConfiguredConnection, ConnectionConfig};
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::Connection;
+
+/// The messages of the slow queries logged by the tests.
+static SLOW_QUERIES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct SlowQueryLogger;
+
+impl Log for SlowQueryLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == "njord::slow_query"
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) && record.level() == Level::Warn {
+            SLOW_QUERIES.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: SlowQueryLogger = SlowQueryLogger;
+
+#[derive(Table, Clone)]
+#[table_name = "numbers"]
+pub struct Numbers {
+    pub count: usize,
+}
+
+/// Counts the rows of a recursive CTE of `rows` rows, which takes some milliseconds.
+fn count_rows(conn: &dyn sqlite::Executor, rows: usize) -> usize {
+    let sql = format!(
+        "WITH RECURSIVE numbers(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM numbers WHERE n < {}) SELECT COUNT(*) AS count FROM numbers",
+        rows
+    );
+
+    sqlite::query_raw::<Numbers>(conn, &sql, vec![]).unwrap()[0].count
+}
+
+fn open_with_threshold(threshold: Duration) -> ConfiguredConnection<Connection> {
+    let config = ConnectionConfig {
+        slow_query_threshold: Some(threshold),
+        ..Default::default()
+    };
+
+    ConfiguredConnection::new(sqlite::open_in_memory().unwrap(), config)
+}
+
+#[test]
+fn slow_query_threshold_is_per_connection() {
+    let _ = log::set_logger(&LOGGER);
+    log::set_max_level(log::LevelFilter::Warn);
+
+    let fast_threshold = open_with_threshold(Duration::from_millis(1));
+    let slow_threshold = open_with_threshold(Duration::from_secs(3600));
+
+    // The row counts tell the queries of the connections apart
+    assert_eq!(count_rows(&fast_threshold, 3000000), 3000000);
+    assert_eq!(count_rows(&slow_threshold, 3000001), 3000001);
+
+    let slow_queries = SLOW_QUERIES.lock().unwrap();
+    assert!(slow_queries
+        .iter()
+        .any(|message| message.starts_with("slow query took") && message.contains("n < 3000000)")));
+    assert!(!slow_queries
+        .iter()
+        .any(|message| message.contains("n < 3000001)")));
+}
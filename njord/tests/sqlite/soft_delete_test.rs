@@ -0,0 +1,97 @@
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "notes"]
+#[soft_delete]
+pub struct Note {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    pub deleted_at: Option<String>,
+}
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Note::default())]).unwrap();
+
+    for title in ["draft", "published"] {
+        sqlite::insert(
+            &conn,
+            vec![Note {
+                id: AutoIncrementPrimaryKey::default(),
+                title: title.to_string(),
+                deleted_at: None,
+            }],
+        )
+        .unwrap();
+    }
+
+    conn
+}
+
+fn delete_draft(conn: &rusqlite::Connection) {
+    sqlite::delete()
+        .from(Note::default())
+        .where_clause(Condition::eq("title", Value::Literal("draft".to_string())))
+        .build(conn)
+        .unwrap();
+}
+
+#[test]
+fn soft_deleted_row_is_excluded_by_default() {
+    let conn = setup();
+    delete_draft(&conn);
+
+    let notes = sqlite::select_all::<Note>()
+        .from(Note::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].title, "published");
+}
+
+#[test]
+fn soft_deleted_row_is_visible_with_deleted() {
+    let conn = setup();
+    delete_draft(&conn);
+
+    let notes = sqlite::select_all::<Note>()
+        .from(Note::default())
+        .where_clause(Condition::eq("title", Value::Literal("draft".to_string())))
+        .with_deleted()
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(notes.len(), 1);
+    assert!(notes[0].deleted_at.is_some());
+}
+
+#[test]
+fn soft_delete_keeps_the_first_deletion_time() {
+    let conn = setup();
+    conn.execute_batch("UPDATE notes SET deleted_at = '2024-01-01 00:00:00' WHERE title = 'draft'")
+        .unwrap();
+    delete_draft(&conn);
+
+    let notes = sqlite::select_all::<Note>()
+        .from(Note::default())
+        .with_deleted()
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes[0].deleted_at.as_deref(), Some("2024-01-01 00:00:00"));
+}
+
+#[test]
+fn select_query_filters_soft_deleted_rows() {
+    let query = sqlite::select_all::<Note>()
+        .from(Note::default())
+        .build_query();
+
+    assert!(query.contains("WHERE \"deleted_at\" IS NULL"));
+}
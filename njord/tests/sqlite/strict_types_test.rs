@@ -0,0 +1,59 @@
+use njord::column::Column;
+use njord::error::Error;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "measurements"]
+pub struct Measurement {
+    pub id: usize,
+    pub reading: i64,
+}
+
+fn setup() -> rusqlite::Connection {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE measurements (id INTEGER PRIMARY KEY, reading INTEGER NOT NULL);
+        INSERT INTO measurements (id, reading) VALUES (1, 10.5);",
+    )
+    .unwrap();
+
+    conn
+}
+
+fn columns<'a>() -> Vec<Column<'a>> {
+    vec![
+        Column::Text("id".to_string()),
+        Column::Text("reading".to_string()),
+    ]
+}
+
+#[test]
+fn strict_types_rejects_real_into_integer() {
+    let conn = setup();
+
+    let result = sqlite::select::<Measurement>(columns())
+        .from(Measurement::default())
+        .strict_types()
+        .build(&conn);
+
+    match result {
+        Err(Error::TypeMismatch(message)) => assert!(message.contains("reading")),
+        Err(e) => panic!("Expected a type mismatch, got: {:?}", e),
+        Ok(_) => panic!("Expected a type mismatch, but the SELECT succeeded"),
+    }
+}
+
+#[test]
+fn lenient_types_by_default() {
+    let conn = setup();
+
+    let result = sqlite::select::<Measurement>(columns())
+        .from(Measurement::default())
+        .build(&conn);
+
+    assert_eq!(result.unwrap().len(), 1);
+}
@@ -0,0 +1,145 @@
+use njord::column::Column;
+use njord::condition::{Condition, Value};
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+use rusqlite::Connection;
+
+use super::User;
+
+#[derive(Table, Clone)]
+#[table_name = "orders"]
+pub struct Order {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub user_id: usize,
+    pub amount: f64,
+}
+
+fn setup() -> Connection {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        CREATE TABLE orders (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            user_id INTEGER NOT NULL,
+            amount REAL NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1'),
+            ('otheruser', 'otheruser@icloud.com', 'Some Random Address 2'),
+            ('chasewillden', 'chase@icloud.com', 'Some Random Address 3');
+        INSERT INTO orders (user_id, amount) VALUES (1, 10.0), (3, 25.0), (3, 5.0);",
+    )
+    .expect("Failed to create tables");
+
+    conn
+}
+
+fn user_columns<'a>() -> Vec<Column<'a>> {
+    vec![
+        Column::Text("id".to_string()),
+        Column::Text("username".to_string()),
+        Column::Text("email".to_string()),
+        Column::Text("address".to_string()),
+    ]
+}
+
+#[test]
+fn select_where_in_subquery() {
+    let conn = setup();
+
+    let subquery = sqlite::select(vec![Column::Text("user_id".to_string())]).from(Order::default());
+
+    let query = sqlite::select(user_columns())
+        .from(User::default())
+        .where_in("id", subquery);
+
+    assert!(query
+        .build_query()
+        .contains("WHERE \"id\" IN (SELECT \"user_id\" FROM orders"));
+
+    match query.build(&conn) {
+        Ok(r) => {
+            let usernames: Vec<&str> = r.iter().map(|u| u.username.as_str()).collect();
+            assert_eq!(usernames, vec!["mjovanc", "chasewillden"]);
+        }
+        Err(e) => panic!("Failed to SELECT: {:?}", e),
+    }
+}
+
+#[test]
+fn select_where_in_subquery_multiple_columns() {
+    let conn = setup();
+
+    let subquery = sqlite::select(vec![
+        Column::Text("user_id".to_string()),
+        Column::Text("amount".to_string()),
+    ])
+    .from(Order::default());
+
+    let result = sqlite::select(user_columns())
+        .from(User::default())
+        .where_in("id", subquery)
+        .build(&conn);
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn select_above_average_amount() {
+    let conn = setup();
+
+    let average = sqlite::select(vec![Column::avg("amount")]).from(Order::default());
+
+    let query = sqlite::select_all::<Order>()
+        .from(Order::default())
+        .where_clause(Condition::gt_subquery("amount", average).unwrap());
+
+    assert!(query
+        .build_query()
+        .contains("WHERE \"amount\" > (SELECT AVG(amount) FROM orders"));
+
+    let orders = query.build(&conn).unwrap();
+
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].amount, 25.0);
+}
+
+#[test]
+fn scalar_subquery_params_are_merged() {
+    let conn = setup();
+
+    let largest_of_user = sqlite::select(vec![Column::Text("MAX(amount)".to_string())])
+        .from(Order::default())
+        .where_clause(Condition::eq("user_id", Value::Literal("3".to_string())));
+
+    let query = sqlite::select_all::<Order>()
+        .from(Order::default())
+        .where_clause(Condition::lt_subquery("amount", largest_of_user).unwrap());
+
+    let (sql, params) = query.to_sql_with_params();
+    assert!(sql.contains("WHERE \"amount\" < (SELECT MAX(amount) FROM orders"));
+    assert!(sql.contains("WHERE \"user_id\" = ?"));
+    assert_eq!(params.len(), 1);
+
+    assert_eq!(query.build(&conn).unwrap().len(), 2);
+}
+
+#[test]
+fn scalar_subquery_must_select_one_column() {
+    let subquery = sqlite::select(vec![
+        Column::Text("user_id".to_string()),
+        Column::Text("amount".to_string()),
+    ])
+    .from(Order::default());
+
+    assert!(Condition::gt_subquery("amount", subquery).is_err());
+}
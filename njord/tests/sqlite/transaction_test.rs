@@ -0,0 +1,94 @@
+use njord::sqlite;
+use njord::util::IsolationLevel;
+use rusqlite::Connection;
+
+fn setup() -> Connection {
+    let conn = sqlite::open_in_memory().expect("Failed to open in-memory database");
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE accounts (id INTEGER PRIMARY KEY, balance INTEGER NOT NULL);
+        INSERT INTO accounts (id, balance) VALUES (1, 100), (2, 0);",
+    )
+    .expect("Failed to create accounts");
+
+    conn
+}
+
+fn balances(conn: &Connection) -> Vec<i64> {
+    let mut stmt = conn
+        .prepare("SELECT balance FROM accounts ORDER BY id")
+        .unwrap();
+    let rows = stmt.query_map([], |row| row.get(0)).unwrap();
+    rows.collect::<rusqlite::Result<Vec<i64>>>().unwrap()
+}
+
+#[test]
+fn transaction_begin_statement_by_isolation_level() {
+    let conn = setup();
+
+    let begin = |isolation| {
+        sqlite::transaction(&conn)
+            .isolation(isolation)
+            .build_query()
+    };
+
+    assert_eq!(
+        sqlite::transaction(&conn).build_query(),
+        "BEGIN DEFERRED TRANSACTION"
+    );
+    assert_eq!(
+        begin(IsolationLevel::ReadCommitted),
+        "BEGIN DEFERRED TRANSACTION"
+    );
+    assert_eq!(
+        begin(IsolationLevel::RepeatableRead),
+        "BEGIN IMMEDIATE TRANSACTION"
+    );
+    assert_eq!(
+        begin(IsolationLevel::Serializable),
+        "BEGIN EXCLUSIVE TRANSACTION"
+    );
+}
+
+#[test]
+fn serializable_transaction_commits() {
+    let conn = setup();
+
+    let result = sqlite::transaction(&conn)
+        .isolation(IsolationLevel::Serializable)
+        .run(|conn| {
+            sqlite::raw_execute(
+                conn,
+                "UPDATE accounts SET balance = balance - 40 WHERE id = 1",
+            )?;
+            sqlite::raw_execute(
+                conn,
+                "UPDATE accounts SET balance = balance + 40 WHERE id = 2",
+            )?;
+            Ok(40)
+        });
+
+    assert_eq!(result.unwrap(), 40);
+    assert!(conn.is_autocommit());
+    assert_eq!(balances(&conn), vec![60, 40]);
+}
+
+#[test]
+fn failed_transaction_rolls_back() {
+    let conn = setup();
+
+    let result: Result<(), njord::Error> = sqlite::transaction(&conn)
+        .isolation(IsolationLevel::RepeatableRead)
+        .run(|conn| {
+            sqlite::raw_execute(
+                conn,
+                "UPDATE accounts SET balance = balance - 40 WHERE id = 1",
+            )?;
+            sqlite::raw_execute(conn, "UPDATE missing SET balance = 40")
+        });
+
+    assert!(result.is_err());
+    assert!(conn.is_autocommit());
+    assert_eq!(balances(&conn), vec![100, 0]);
+}
@@ -0,0 +1,146 @@
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord::types::TypeRegistry;
+use njord::Error;
+use njord_derive::Table;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Email(String);
+
+#[derive(Table, Clone)]
+#[table_name = "subscribers"]
+pub struct Subscriber {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[mapped]
+    pub email: Email,
+    #[mapped]
+    pub backup_email: Option<Email>,
+}
+
+fn register_email() {
+    TypeRegistry::register::<Email>(
+        "TEXT",
+        |email| email.0.clone(),
+        |value| match value.contains('@') {
+            true => Ok(Email(value.to_string())),
+            false => Err(format!("`{}` is not an email address", value)),
+        },
+    );
+}
+
+#[test]
+fn mapped_column_type() {
+    register_email();
+
+    let columns = Subscriber::default().get_columns();
+
+    assert_eq!(columns.get("email").map(String::as_str), Some("TEXT"));
+    assert_eq!(
+        columns.get("backup_email").map(String::as_str),
+        Some("TEXT NULL")
+    );
+}
+
+#[test]
+fn mapped_fields_round_trip() {
+    register_email();
+
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Subscriber::default())]).unwrap();
+
+    let subscribers = [
+        Subscriber {
+            id: AutoIncrementPrimaryKey::default(),
+            email: Email("mjovanc@icloud.com".to_string()),
+            backup_email: Some(Email("marcus@njord.rs".to_string())),
+        },
+        Subscriber {
+            id: AutoIncrementPrimaryKey::default(),
+            email: Email("otheruser@icloud.com".to_string()),
+            backup_email: None,
+        },
+    ];
+    for subscriber in subscribers {
+        sqlite::insert(&conn, vec![subscriber]).unwrap();
+    }
+
+    let result = sqlite::select_all::<Subscriber>()
+        .from(Subscriber::default())
+        .build(&conn)
+        .unwrap();
+
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].email, Email("mjovanc@icloud.com".to_string()));
+    assert_eq!(
+        result[0].backup_email,
+        Some(Email("marcus@njord.rs".to_string()))
+    );
+    assert_eq!(result[1].email, Email("otheruser@icloud.com".to_string()));
+    assert_eq!(result[1].backup_email, None);
+}
+
+#[test]
+fn invalid_mapped_value_is_decode_error() {
+    register_email();
+
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Subscriber::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO subscribers (email) VALUES ('not an email');",
+    )
+    .unwrap();
+
+    let result = sqlite::select_all::<Subscriber>()
+        .from(Subscriber::default())
+        .build(&conn);
+
+    assert!(matches!(result, Err(Error::Decode(_))));
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Phone(String);
+
+#[derive(Table, Clone)]
+#[table_name = "contacts"]
+pub struct Contact {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    #[mapped]
+    pub phone: Option<Phone>,
+}
+
+#[test]
+fn unregistered_mapped_type_is_invalid_query() {
+    let conn = sqlite::open_in_memory().unwrap();
+
+    let result = sqlite::init(&conn, vec![Box::new(Contact::default())]);
+    assert!(
+        matches!(result, Err(Error::InvalidQuery(ref message)) if message.contains("`phone`"))
+    );
+
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE contacts (id INTEGER PRIMARY KEY AUTOINCREMENT, phone TEXT NULL);",
+    )
+    .unwrap();
+
+    let contact = Contact {
+        id: AutoIncrementPrimaryKey::default(),
+        phone: Some(Phone("555-0100".to_string())),
+    };
+    let result = sqlite::insert(&conn, vec![contact.clone()]);
+    assert!(matches!(result, Err(Error::InvalidQuery(_))));
+
+    let result = sqlite::update(contact)
+        .set(vec!["phone".to_string()])
+        .build(&conn);
+    assert!(result.is_err());
+
+    // Nothing was written with a placeholder value
+    let count = sqlite::count(&conn, vec![])
+        .from(&Contact::default())
+        .build()
+        .unwrap();
+    assert_eq!(count, 0);
+}
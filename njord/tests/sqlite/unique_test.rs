@@ -0,0 +1,60 @@
+use njord::keys::AutoIncrementPrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord::Error;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "seats"]
+#[unique(columns = "row_number, seat_number")]
+#[unique(columns = "holder, event")]
+pub struct Seat {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub row_number: usize,
+    pub seat_number: usize,
+    pub holder: String,
+    pub event: String,
+    #[unique]
+    pub ticket: String,
+}
+
+fn seat(row_number: usize, seat_number: usize, holder: &str, ticket: &str) -> Seat {
+    Seat {
+        id: AutoIncrementPrimaryKey::default(),
+        row_number,
+        seat_number,
+        holder: holder.to_string(),
+        event: "concert".to_string(),
+        ticket: ticket.to_string(),
+    }
+}
+
+#[test]
+fn create_table_statement_with_unique_constraints() {
+    assert_eq!(
+        sqlite::init::generate_create_table_statement(&Seat::default()),
+        "CREATE TABLE IF NOT EXISTS seats (\"id\" INTEGER PRIMARY KEY AUTOINCREMENT, \
+         \"row_number\" INTEGER, \"seat_number\" INTEGER, \"holder\" TEXT, \"event\" TEXT, \
+         \"ticket\" TEXT, UNIQUE (\"row_number\", \"seat_number\"), UNIQUE (\"holder\", \"event\"), \
+         UNIQUE (\"ticket\"));"
+    );
+}
+
+#[test]
+fn insert_violating_composite_unique_fails() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Seat::default())]).expect("Failed to init tables");
+
+    assert!(sqlite::insert(&conn, vec![seat(1, 1, "alice", "t1")]).is_ok());
+    assert!(sqlite::insert(&conn, vec![seat(1, 2, "bob", "t2")]).is_ok());
+    assert!(sqlite::insert(&conn, vec![seat(2, 1, "carol", "t3")]).is_ok());
+
+    let same_seat = sqlite::insert(&conn, vec![seat(1, 1, "dave", "t4")]);
+    assert!(matches!(same_seat, Err(Error::Conflict(_))));
+
+    let same_holder = sqlite::insert(&conn, vec![seat(3, 1, "alice", "t5")]);
+    assert!(matches!(same_holder, Err(Error::Conflict(_))));
+
+    let same_ticket = sqlite::insert(&conn, vec![seat(3, 1, "erin", "t1")]);
+    assert!(matches!(same_ticket, Err(Error::Conflict(_))));
+}
@@ -4,6 +4,9 @@ use njord::condition::{Condition, Value};
 use njord::keys::AutoIncrementPrimaryKey;
 use njord::sqlite::select::SelectQueryBuilder;
 use njord::sqlite::{self};
+use njord::table::Table;
+use njord::Error;
+use njord_derive::Table;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -90,3 +93,104 @@ fn update_with_sub_queries() {
         Err(e) => panic!("Failed to UPDATE: {:?}", e),
     };
 }
+
+#[test]
+fn update_returning_into() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "CREATE TABLE users (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            username TEXT NOT NULL,
+            email TEXT NOT NULL,
+            address TEXT NOT NULL
+        );
+        INSERT INTO users (username, email, address) VALUES
+            ('mjovanc', 'mjovanc@icloud.com', 'Some Random Address 1'),
+            ('otheruser', 'other@example.com', 'Some Random Address 2');",
+    )
+    .unwrap();
+
+    let table_row = User {
+        id: AutoIncrementPrimaryKey::default(),
+        username: "mjovanc".to_string(),
+        email: "new@example.com".to_string(),
+        address: String::new(),
+    };
+
+    let users = sqlite::update(table_row)
+        .set(vec!["email".to_string()])
+        .where_clause(Condition::Eq(
+            "username".to_string(),
+            Value::Literal("mjovanc".to_string()),
+        ))
+        .returning_into::<User>(&conn)
+        .unwrap();
+
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].id.get(), Some(&1));
+    assert_eq!(users[0].email, "new@example.com");
+    assert_eq!(users[0].address, "Some Random Address 1");
+}
+
+#[derive(Table, Clone)]
+#[table_name = "documents"]
+pub struct Document {
+    pub id: AutoIncrementPrimaryKey<usize>,
+    pub title: String,
+    #[version]
+    pub version: i64,
+}
+
+fn read_document(conn: &rusqlite::Connection) -> Document {
+    sqlite::select_all::<Document>()
+        .from(Document::default())
+        .build(conn)
+        .unwrap()
+        .remove(0)
+}
+
+#[test]
+fn update_by_pk_increments_version() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Document::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO documents (title, version) VALUES ('draft', 1)",
+    )
+    .unwrap();
+
+    let mut document = read_document(&conn);
+    document.title = "final".to_string();
+    sqlite::update_by_pk(&conn, &mut document).unwrap();
+
+    assert_eq!(document.version, 2);
+    let stored = read_document(&conn);
+    assert_eq!(stored.title, "final");
+    assert_eq!(stored.version, 2);
+}
+
+#[test]
+fn update_by_pk_with_stale_version_fails() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Document::default())]).unwrap();
+    sqlite::raw_execute(
+        &conn,
+        "INSERT INTO documents (title, version) VALUES ('draft', 1)",
+    )
+    .unwrap();
+
+    // both writers read the same version of the row
+    let mut first = read_document(&conn);
+    let mut second = read_document(&conn);
+
+    first.title = "first".to_string();
+    sqlite::update_by_pk(&conn, &mut first).unwrap();
+
+    second.title = "second".to_string();
+    let result = sqlite::update_by_pk(&conn, &mut second);
+
+    assert!(matches!(result, Err(Error::StaleData(_))));
+    assert_eq!(second.version, 1);
+    assert_eq!(read_document(&conn).title, "first");
+}
@@ -0,0 +1,50 @@
+use njord::condition::{Condition, Value};
+use njord::keys::PrimaryKey;
+use njord::sqlite;
+use njord::table::Table;
+use njord_derive::Table;
+
+#[derive(Table, Clone)]
+#[table_name = "settings"]
+#[without_rowid]
+pub struct Setting {
+    pub name: PrimaryKey<String>,
+    pub value: String,
+}
+
+#[test]
+fn create_without_rowid_table() {
+    assert_eq!(
+        sqlite::init::generate_create_table_statement(&Setting::default()),
+        "CREATE TABLE IF NOT EXISTS settings (\"name\" TEXT PRIMARY KEY, \"value\" TEXT) WITHOUT ROWID;"
+    );
+}
+
+#[test]
+fn insert_and_select_without_rowid() {
+    let conn = sqlite::open_in_memory().unwrap();
+    sqlite::init(&conn, vec![Box::new(Setting::default())]).unwrap();
+
+    let settings = vec![
+        Setting {
+            name: PrimaryKey::new("theme".to_string()),
+            value: "dark".to_string(),
+        },
+        Setting {
+            name: PrimaryKey::new("language".to_string()),
+            value: "sv".to_string(),
+        },
+    ];
+    sqlite::insert(&conn, settings).expect("Failed to INSERT");
+
+    let result = sqlite::select_all::<Setting>()
+        .from(Setting::default())
+        .where_clause(Condition::eq("name", Value::Literal("theme".to_string())))
+        .build(&conn)
+        .unwrap();
+    assert_eq!(result.len(), 1);
+    assert_eq!(result[0].value, "dark");
+
+    // a WITHOUT ROWID table has no implicit rowid column
+    assert!(conn.prepare("SELECT rowid FROM settings").is_err());
+}
@@ -2,7 +2,7 @@ use clap::ArgMatches;
 use std::fs;
 use std::path::Path;
 
-use crate::migration::{generate, rollback, run};
+use crate::migration::{diff, generate, list, reset, rollback, run};
 
 /// Initializes Njord with an empty migrations directory and a `njord.toml` config file.
 ///
@@ -142,14 +142,33 @@ pub fn handle_migration_subcommand(sub_matches: &ArgMatches) {
             let name = generate_matches.get_one::<String>("name");
             let env = generate_matches.get_one::<String>("env");
             let dry_run = generate_matches.get_one::<String>("dry-run");
+            let blank = generate_matches.get_flag("blank");
 
-            generate(name, env, dry_run)
+            generate(name, env, dry_run, blank)
         }
         Some(("run", run_matches)) => {
             let env = run_matches.get_one::<String>("env");
             let log_level = run_matches.get_one::<String>("log-level");
+            let steps = run_matches.get_one::<usize>("steps").copied();
 
-            run(env, log_level)
+            run(env, log_level, steps)
+        }
+        Some(("list", list_matches)) => {
+            let env = list_matches.get_one::<String>("env");
+            let pending_only = list_matches.get_flag("pending-only");
+            list(env, pending_only)
+        }
+        Some(("diff", diff_matches)) => {
+            let from = diff_matches.get_one::<String>("from").unwrap();
+            let to = diff_matches.get_one::<String>("to").unwrap();
+
+            diff(from, to)
+        }
+        Some(("reset", reset_matches)) => {
+            let env = reset_matches.get_one::<String>("env");
+            let force = reset_matches.get_flag("force");
+
+            reset(env, force)
         }
         Some(("rollback", rollback_matches)) => {
             let env = rollback_matches.get_one::<String>("env");
@@ -35,6 +35,11 @@ fn main() {
                         .arg(Arg::new("dir")
                             .help("Specifies the target directory for generated migration changes.")
                             .value_name("path"))
+
+                        .arg(Arg::new("blank")
+                            .long("blank")
+                            .action(clap::ArgAction::SetTrue)
+                            .help("Creates empty up.sql and down.sql files instead of templates."))
                 )
                 .subcommand(
                     clap::command!("run")
@@ -45,7 +50,55 @@ fn main() {
                         
                         .arg(Arg::new("log-level")
                             .help("Sets the logging level (e.g., standard, debug).")
-                            .value_name("log-level")),
+                            .value_name("log-level"))
+
+                        .arg(Arg::new("steps")
+                            .long("steps")
+                            .value_parser(clap::value_parser!(usize))
+                            .help("Only applies the next n pending migrations.")
+                            .value_name("n")),
+                )
+                .subcommand(
+                    clap::command!("list")
+                        .about("Lists all migrations in order, marking applied ones with [x] and pending ones with [ ].")
+
+                        .arg(Arg::new("env")
+                            .long("env")
+                            .help("Target a specific environment."))
+
+                        .arg(Arg::new("pending-only")
+                            .long("pending-only")
+                            .action(clap::ArgAction::SetTrue)
+                            .help("Only lists the migrations that have not been applied yet.")),
+                )
+                .subcommand(
+                    clap::command!("diff")
+                        .about("Prints the statements that change the schema of one environment to match another.")
+
+                        .arg(Arg::new("from")
+                            .long("from")
+                            .required(true)
+                            .help("The environment with the wanted schema (e.g. dev).")
+                            .value_name("env"))
+
+                        .arg(Arg::new("to")
+                            .long("to")
+                            .required(true)
+                            .help("The environment to compare (e.g. prod).")
+                            .value_name("env")),
+                )
+                .subcommand(
+                    clap::command!("reset")
+                        .about("Rolls back all applied migrations, so all migrations are pending again.")
+
+                        .arg(Arg::new("env")
+                            .long("env")
+                            .help("Target a specific environment."))
+
+                        .arg(Arg::new("force")
+                            .long("force")
+                            .action(clap::ArgAction::SetTrue)
+                            .help("Only clears the migration history, without running down.sql.")),
                 )
                 .subcommand(
                     clap::command!("rollback")
@@ -1,9 +1,12 @@
-use std::{fs, path::Path};
+use std::path::Path;
 
+use njord::migration::{Migration, Migrator};
 use njord::sqlite;
-use rusqlite::{Connection, Error, ErrorCode};
 
-use crate::util::{create_migration_files, get_local_migration_versions, get_migrations_directory_path, get_next_migration_version, MigrationHistory, read_config, version_not_in_database};
+use crate::util::{
+    create_migration_files, get_environment_database_path, get_migrations_directory_path,
+    get_next_migration_version, read_config,
+};
 
 /// Generates migration files with the specified name, environment, and dry-run option.
 ///
@@ -12,13 +15,19 @@ use crate::util::{create_migration_files, get_local_migration_versions, get_migr
 /// * `name` - Optional parameter representing the name of the migration file.
 /// * `env` - Optional parameter specifying the environment (e.g., development, test, staging, production).
 /// * `dry_run` - Optional parameter indicating whether to simulate the migration without applying changes.
+/// * `blank` - Whether to create empty `up.sql` and `down.sql` files instead of templates.
 ///
 /// # Example
 ///
 /// ```rust
-/// generate(Some("example_name"), Some("development"), Some("true"));
+/// generate(Some("example_name"), Some("development"), Some("true"), false);
 /// ```
-pub fn generate(name: Option<&String>, env: Option<&String>, dry_run: Option<&String>) {
+pub fn generate(
+    name: Option<&String>,
+    env: Option<&String>,
+    dry_run: Option<&String>,
+    blank: bool,
+) {
     if let Ok(config) = read_config() {
         if let Some(migrations_dir) = get_migrations_directory_path(&config) {
             // get the next migration version based on existing ones
@@ -26,7 +35,7 @@ pub fn generate(name: Option<&String>, env: Option<&String>, dry_run: Option<&St
                 let migration_name = name.map(|s| s.as_str()).unwrap_or("example_name");
 
                 // create migration files
-                if let Err(err) = create_migration_files(&migrations_dir, &next_version, migration_name)
+                if let Err(err) = create_migration_files(&migrations_dir, &next_version, migration_name, blank)
                 {
                     eprintln!("Error creating migration files: {}", err);
                     return;
@@ -54,67 +63,37 @@ pub fn generate(name: Option<&String>, env: Option<&String>, dry_run: Option<&St
 ///
 /// * `env` - Optional parameter specifying the target environment for applying migrations.
 /// * `log_level` - Optional parameter setting the logging level (e.g., standard, debug).
+/// * `steps` - Optional parameter limiting the number of pending migrations to apply.
 ///
 /// # Example
 ///
 /// ```rust
-/// run(Some("production"), Some("debug"));
+/// run(Some("production"), Some("debug"), Some(1));
 /// ```
-pub fn run(env: Option<&String>, log_level: Option<&String>) {
+pub fn run(env: Option<&String>, log_level: Option<&String>, steps: Option<usize>) {
     let db_relative_path = "./sqlite.db";
     let db_path = Path::new(&db_relative_path);
     let conn = sqlite::open(db_path);
 
     if let Ok(config) = read_config() {
         if let Some(migrations_dir) = get_migrations_directory_path(&config) {
-
             match conn {
                 Ok(conn) => {
                     println!("Database connection established successfully.");
 
-                    // obtain the latest version from the "migration_history" table
-                    if let Ok(latest_db_version) = get_latest_migration_version(&conn) {
-                        println!("latest_db_version: {}", latest_db_version);
-
-                        // get all local migration changes
-                        let local_versions = match get_local_migration_versions(&migrations_dir) {
-                            Ok(versions) => versions,
-                            Err(err) => {
-                                eprintln!("Error retrieving local migration versions: {}", err);
-                                return;
-                            }
-                        };
-
-                        for value in &local_versions {
-                            println!("Value: {}", value);
-                        }
-
-                        // go over each migration schema version changes if they should be executed
-                        // TODO: need to check the ordering here
-                        // for example it can be run with 00000000000001_init_tables first and
-                        // then run 00000000000000_njord_initial_setup which is not incremental
-                        for local_version in &local_versions {
-                            match version_not_in_database(&conn, &local_version) {
-                                Ok(_) => {
-                                    println!("Migration {} not found in database. Executing migration...", local_version);
-
-                                    let migrations_dir = format!("migrations/{}", local_version);
-                                    println!("migrations_dir: {}", migrations_dir);
-
-                                    let db_relative_path = "./sqlite.db";
-                                    let db_path = Path::new(&db_relative_path);
-                                    let conn = sqlite::open(db_path);
-
-                                    match conn {
-                                        Ok(c) => execute_pending_migration(c, &migrations_dir, &local_version).unwrap(),
-                                        Err(_) => {}
-                                    }
-                                }
-                                Err(_) => {}
+                    let migrator = Migrator::new(&conn, &migrations_dir);
+                    let result = match steps {
+                        Some(steps) => migrator.run_steps(steps),
+                        None => migrator.run(),
+                    };
+                    match result {
+                        Ok(applied) if applied.is_empty() => println!("No pending migrations."),
+                        Ok(applied) => {
+                            for version in applied {
+                                println!("Migration {} applied successfully.", version);
                             }
                         }
-                    } else {
-                        eprintln!("Error obtaining latest migration version.");
+                        Err(err) => eprintln!("Error applying migrations: {}", err),
                     }
                 }
                 Err(err) => eprintln!("Error establishing database connection: {}", err),
@@ -128,8 +107,71 @@ pub fn run(env: Option<&String>, log_level: Option<&String>) {
     }
 }
 
+/// Lists all migrations in version order with their applied state.
+///
+/// Every migration is printed on its own line, prefixed with `[x]` if it is applied
+/// and `[ ]` if it is pending.
+///
+/// # Arguments
+///
+/// * `env` - Optional parameter specifying the target environment.
+/// * `pending_only` - Only list the migrations that have not been applied yet.
+///
+/// # Example
+///
+/// ```rust
+/// list(Some("development"), true);
+/// ```
+pub fn list(env: Option<&String>, pending_only: bool) {
+    let db_relative_path = "./sqlite.db";
+    let db_path = Path::new(&db_relative_path);
+    let conn = sqlite::open(db_path);
+
+    if let Ok(config) = read_config() {
+        if let Some(migrations_dir) = get_migrations_directory_path(&config) {
+            match conn {
+                Ok(conn) => {
+                    let migrator = Migrator::new(&conn, &migrations_dir);
+                    match migrator.status() {
+                        Ok(status) => {
+                            for line in format_migration_list(&status, pending_only) {
+                                println!("{}", line);
+                            }
+                        }
+                        Err(err) => eprintln!("Error listing migrations for env '{:?}': {}", env, err),
+                    }
+                }
+                Err(err) => eprintln!("Error establishing database connection: {}", err),
+            };
+        }
+    }
+}
+
+/// Formats the lines printed by `list`.
+///
+/// # Arguments
+///
+/// * `status` - The migrations in version order, together with whether they are applied.
+/// * `pending_only` - Only include the migrations that have not been applied yet.
+///
+/// # Returns
+///
+/// A `Vec<String>` containing a `[x] version` or `[ ] version` line for every migration.
+fn format_migration_list(status: &[(Migration, bool)], pending_only: bool) -> Vec<String> {
+    status
+        .iter()
+        .filter(|(_, applied)| !(pending_only && *applied))
+        .map(|(migration, applied)| {
+            let marker = if *applied { "[x]" } else { "[ ]" };
+            format!("{} {}", marker, migration.version)
+        })
+        .collect()
+}
+
 /// Rolls back migration changes to a specific version, with optional environment and log level.
 ///
+/// All applied migrations newer than the target version are rolled back, newest first.
+///
 /// # Arguments
 ///
 /// * `env` - Optional parameter specifying the target environment for rolling back migrations.
@@ -147,22 +189,26 @@ pub fn rollback(env: Option<&String>, to: Option<&String>, log_level: Option<&St
         let db_path = Path::new(&db_relative_path);
         let conn = sqlite::open(db_path);
 
-        match conn {
-            Ok(conn) => {
-                println!("Database connection established successfully.");
-
-                // construct paths to migration directories
-                let migrations_dir = format!("migrations/{}", target_version);
+        if let Ok(config) = read_config() {
+            if let Some(migrations_dir) = get_migrations_directory_path(&config) {
+                match conn {
+                    Ok(conn) => {
+                        println!("Database connection established successfully.");
 
-                // execute down.sql for the specified version
-                if let Err(down_err) = execute_sql_from_file(&conn, &migrations_dir, "down.sql") {
-                    eprintln!("Error executing down.sql: {}", down_err);
-                } else {
-                    println!("down.sql executed successfully.");
-                }
+                        let migrator = Migrator::new(&conn, &migrations_dir);
+                        match migrator.rollback(target_version) {
+                            Ok(rolled_back) => {
+                                for version in rolled_back {
+                                    println!("Migration {} rolled back successfully.", version);
+                                }
+                            }
+                            Err(err) => eprintln!("Error rolling back migrations: {}", err),
+                        }
+                    }
+                    Err(err) => eprintln!("Error establishing database connection: {}", err),
+                };
             }
-            Err(err) => eprintln!("Error establishing database connection: {}", err),
-        };
+        }
 
         println!(
             "Rolling back migration with env '{:?}' to '{:?}' log_level '{:?}'",
@@ -173,115 +219,137 @@ pub fn rollback(env: Option<&String>, to: Option<&String>, log_level: Option<&St
     }
 }
 
-/// Retrieves the latest migration version from the "migration_history" table.
-///
-/// # Arguments
+/// Resets the migration state, so all migrations are pending again.
 ///
-/// * `conn` - A reference to a `rusqlite::Connection`.
+/// All applied migrations are rolled back newest first. With `force`, no `down.sql` is
+/// executed and only the migration history is cleared, leaving the schema as is.
 ///
-/// # Returns
+/// # Arguments
 ///
-/// A `Result` containing the latest migration version as a `String` or a `rusqlite::Error` if an error occurs.
+/// * `env` - Optional parameter specifying the target environment.
+/// * `force` - Only clear the migration history.
 ///
-/// # Note
+/// # Example
 ///
-/// This function queries the "migration_history" table to obtain the latest version.
-fn get_latest_migration_version(conn: &Connection) -> Result<String, Error> {
-    let query = "SELECT version FROM migration_history ORDER BY version DESC LIMIT 1;";
-    let result: Result<String, Error> = conn.query_row(query, [], |row| row.get(0));
+/// ```rust
+/// reset(Some("test"), false);
+/// ```
+pub fn reset(env: Option<&String>, force: bool) {
+    let db_relative_path = "./sqlite.db";
+    let db_path = Path::new(&db_relative_path);
+    let conn = sqlite::open(db_path);
 
-    match result {
-        Ok(version) => Ok(version),
-        Err(err) => {
-            match err {
-                Error::SqliteFailure(error, _) if error.code == ErrorCode::Unknown => {
-                    Ok("00000000000000_njord_initial_setup".to_string())
-                }
-                _ => {
-                    eprintln!("Error getting latest migration version: {}", err);
-                    Err(err)
+    if let Ok(config) = read_config() {
+        if let Some(migrations_dir) = get_migrations_directory_path(&config) {
+            match conn {
+                Ok(conn) => {
+                    println!("Database connection established successfully.");
+
+                    let migrator = Migrator::new(&conn, &migrations_dir);
+                    if force {
+                        match migrator.clear_history() {
+                            Ok(cleared) => println!("Cleared {} applied migrations.", cleared),
+                            Err(err) => eprintln!("Error clearing migration history: {}", err),
+                        }
+                    } else {
+                        match migrator.reset() {
+                            Ok(rolled_back) => {
+                                for version in rolled_back {
+                                    println!("Migration {} rolled back successfully.", version);
+                                }
+                            }
+                            Err(err) => eprintln!("Error resetting migrations: {}", err),
+                        }
+                    }
                 }
-            }
+                Err(err) => eprintln!("Error establishing database connection: {}", err),
+            };
+
+            println!("Resetting migrations with env '{:?}'", env);
         }
     }
 }
 
-/// Executes SQL content from a file on the provided database connection.
-///
-/// # Arguments
+/// Prints the statements that change the schema of one environment to match another.
 ///
-/// * `conn` - A reference to a `rusqlite::Connection`.
-/// * `migrations_dir` - A string slice representing the path to the migrations directory.
-/// * `file_name` - A string slice representing the name of the SQL file to execute.
+/// The databases of both environments are introspected, nothing is applied.
 ///
-/// # Returns
+/// # Arguments
 ///
-/// A `Result` indicating success (`Ok(())`) or a `rusqlite::Error` if an error occurs.
+/// * `from` - The environment with the wanted schema, e.g. `dev`.
+/// * `to` - The environment to compare, e.g. `prod`.
 ///
-/// # Note
+/// # Example
 ///
-/// This function reads the SQL content from the specified file and executes it on the provided database connection.
-fn execute_sql_from_file(
-    conn: &Connection,
-    migrations_dir: &str,
-    file_name: &str,
-) -> Result<(), Error> {
-    let file_path = Path::new(migrations_dir).join(file_name);
-
-    let sql_content = match fs::read_to_string(&file_path) {
-        Ok(content) => content,
-        Err(err) => {
-            eprintln!("Error reading {}: {}", file_path.display(), err);
-            return Err(Error::QueryReturnedNoRows); // placeholder error
-        }
-    };
+/// ```rust
+/// diff("dev", "prod");
+/// ```
+pub fn diff(from: &str, to: &str) {
+    if let Ok(config) = read_config() {
+        let from_conn = match get_environment_database_path(&config, from) {
+            Some(path) => sqlite::open(&path),
+            None => return eprintln!("Error: Environment '{}' is not configured.", from),
+        };
+        let to_conn = match get_environment_database_path(&config, to) {
+            Some(path) => sqlite::open(&path),
+            None => return eprintln!("Error: Environment '{}' is not configured.", to),
+        };
 
-    match conn.execute_batch(&sql_content) {
-        Ok(_) => {
-            println!("{} executed successfully.", file_path.display());
-            Ok(())
-        }
-        Err(err) => {
-            eprintln!("Error executing {}: {}", file_path.display(), err);
-            Err(err)
+        match (from_conn, to_conn) {
+            (Ok(from_conn), Ok(to_conn)) => match sqlite::diff_schemas(&from_conn, &to_conn) {
+                Ok(statements) if statements.is_empty() => {
+                    println!("The schemas of '{}' and '{}' match.", from, to)
+                }
+                Ok(statements) => {
+                    for statement in statements {
+                        println!("{}", statement);
+                    }
+                }
+                Err(err) => eprintln!("Error comparing schemas: {}", err),
+            },
+            (Err(err), _) | (_, Err(err)) => {
+                eprintln!("Error establishing database connection: {}", err)
+            }
         }
     }
 }
 
-/// Executes migration changes in directories that have not been applied yet.
-///
-/// # Arguments
-///
-/// * `conn` - A reference to a `rusqlite::Connection`.
-/// * `migrations_dir` - A string slice representing the path to the migrations directory.
-/// * `next_version` - A string representing the next migration version.
-///
-/// # Errors
-///
-/// Returns a `rusqlite::Error` if there is an issue executing the SQL scripts or inserting into the database.
-fn execute_pending_migration(
-    conn: Connection,
-    migrations_dir: &str,
-    next_version: &str,
-) -> Result<(), Error> {
-    match execute_sql_from_file(&conn, &migrations_dir, "up.sql") {
-        Ok(_) => {
-            println!("up.sql executed successfully.");
-            // insert new row with the version into the database
-            let row = MigrationHistory { version: next_version.to_string() };
-            sqlite::insert(&conn, vec![row])?;
-        }
-        Err(up_err) => {
-            eprintln!("Error executing up.sql: {}", up_err);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            // TODO: we need to make sure that this is only run on error when it's not "table already exists" kind of errors
-            if let Err(down_err) = execute_sql_from_file(&conn, &migrations_dir, "down.sql") {
-                eprintln!("Error executing down.sql: {}", down_err);
-            } else {
-                println!("down.sql executed successfully.");
-            }
-        }
+    const MIGRATIONS: [(&str, &str, &str); 2] = [
+        (
+            "00000000000001_create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+            "DROP TABLE users;",
+        ),
+        (
+            "00000000000002_create_posts",
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY);",
+            "DROP TABLE posts;",
+        ),
+    ];
+
+    #[test]
+    fn list_applied_and_pending_migrations() {
+        let conn = sqlite::open_in_memory().unwrap();
+        Migrator::from_embedded(&conn, &MIGRATIONS[..1]).run().unwrap();
+
+        // listed in version order, regardless of the order they are given in
+        let migrator = Migrator::from_embedded(&conn, &[MIGRATIONS[1], MIGRATIONS[0]]);
+        let status = migrator.status().unwrap();
+
+        assert_eq!(
+            format_migration_list(&status, false),
+            vec![
+                "[x] 00000000000001_create_users",
+                "[ ] 00000000000002_create_posts",
+            ]
+        );
+        assert_eq!(
+            format_migration_list(&status, true),
+            vec!["[ ] 00000000000002_create_posts"]
+        );
     }
-    Ok(())
 }
-
@@ -3,11 +3,7 @@ use serde::Deserialize;
 use std::error::Error as StdError;
 use std::path::{Path, PathBuf};
 use std::{env, fs};
-use std::collections::HashSet;
-use rusqlite::{Connection, Error};
 use toml::Value as TomlConfig;
-use njord_derive::Table;
-use njord::table::Table;
 
 #[derive(Debug)]
 pub enum ConfigError {
@@ -34,12 +30,6 @@ pub struct SchemaFile {
     file: String,
 }
 
-#[derive(Table)]
-#[table_name = "migration_history"]
-pub struct MigrationHistory {
-    pub version: String,
-}
-
 impl fmt::Display for ConfigError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -141,69 +131,19 @@ pub fn get_next_migration_version(migrations_dir: &Path) -> Result<String, std::
     }
 }
 
-/// Retrieves local migration versions from the specified directory.
-///
-/// This function reads the names of files in the specified `migrations_dir`
-/// directory, filters out non-UTF-8 filenames and collects the valid filenames
-/// into a `HashSet<String>`.
-///
-/// # Arguments
-///
-/// * `migrations_dir` - The path to the directory containing migration versions.
-///
-/// # Returns
-///
-/// A `Result` containing a `HashSet<String>` of local migration versions if
-/// successful, or an `std::io::Error` if there is an issue reading the directory.
-pub fn get_local_migration_versions(migrations_dir: &Path) -> Result<HashSet<String>, std::io::Error> {
-    let entries = fs::read_dir(migrations_dir)?;
-    let local_versions: HashSet<String> = entries
-        .filter_map(|entry| {
-            entry.ok()
-                .and_then(|e| e.file_name().to_str().map(String::from))
-        })
-        .collect();
-
-    Ok(local_versions)
-}
-
-/// Checks if a specific version exists in the migration history table.
-///
-/// # Arguments
-///
-/// * `conn` - A reference to a `rusqlite::Connection`.
-/// * `version` - A string slice representing the version to check for.
-///
-/// # Returns
-///
-/// `true` if the version exists, `false` otherwise.
-///
-/// # Errors
-///
-/// Returns a `rusqlite::Error` if there is an issue with the database query.
-pub fn version_not_in_database(conn: &Connection, version: &str) -> Result<bool, Error> {
-    let query = "SELECT EXISTS(SELECT 1 FROM migration_history WHERE version = ?)";
-    let result: Result<i32, Error> = conn.query_row(query, &[&version], |row| row.get(0));
-
-    match result {
-        Ok(1) => Ok(false),
-        Ok(0) => Ok(true),
-        Err(err) => Err(err),
-        _ => Ok(false),
-    }
-}
-
 /// Creates migration files in the specified directory.
 ///
 /// This function creates migration files in the specified directory based on
 /// the provided `version` and `name`. It creates two files: `up.sql` and
-/// `down.sql` within the migration directory.
+/// `down.sql` within the migration directory, each with a commented template
+/// unless `blank` is set, in which case the files are left empty.
 ///
 /// # Arguments
 ///
 /// * `migrations_dir` - The path to the directory where migration files will be created.
 /// * `version` - The version of the migration.
 /// * `name` - The name of the migration.
+/// * `blank` - Whether to create empty files instead of templates.
 ///
 /// # Errors
 ///
@@ -213,6 +153,7 @@ pub fn create_migration_files(
     migrations_dir: &Path,
     version: &str,
     name: &str,
+    blank: bool,
 ) -> Result<(), std::io::Error> {
     let mut dir_path = PathBuf::from(migrations_dir);
     dir_path.push(format!("{}_{}", version, name));
@@ -224,8 +165,19 @@ pub fn create_migration_files(
     let up_sql_path = dir_path.join("up.sql");
     let down_sql_path = dir_path.join("down.sql");
 
-    fs::File::create(up_sql_path)?;
-    fs::File::create(down_sql_path)?;
+    if blank {
+        fs::File::create(up_sql_path)?;
+        fs::File::create(down_sql_path)?;
+    } else {
+        fs::write(
+            up_sql_path,
+            format!("-- {}: write the SQL to apply the migration here\n", name),
+        )?;
+        fs::write(
+            down_sql_path,
+            format!("-- {}: write the SQL to revert `up.sql` here\n", name),
+        )?;
+    }
 
     Ok(())
 }
@@ -253,3 +205,59 @@ pub fn get_migrations_directory_path(config: &TomlConfig) -> Option<PathBuf> {
 
     migrations_dir
 }
+
+/// Retrieves the path to the database of an environment from the configuration.
+///
+/// Environments are configured as tables under `environments`, e.g.
+/// `[environments.dev]` with `database = "dev.db"`.
+///
+/// # Arguments
+///
+/// * `config` - A reference to a `toml::Value` representing the configuration.
+/// * `env` - The name of the environment.
+///
+/// # Returns
+///
+/// An `Option<PathBuf>` containing the path to the database of the environment, or `None`
+/// if the environment is not configured.
+pub fn get_environment_database_path(config: &TomlConfig, env: &str) -> Option<PathBuf> {
+    config
+        .get("environments")
+        .and_then(|environments| environments.get(env))
+        .and_then(|environment| environment.get("database"))
+        .and_then(|database| database.as_str())
+        .map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_blank_migration_files() {
+        let migrations_dir =
+            env::temp_dir().join(format!("njord_cli_blank_{}", std::process::id()));
+
+        create_migration_files(&migrations_dir, "00000000000001", "create_users", true).unwrap();
+
+        let migration_dir = migrations_dir.join("00000000000001_create_users");
+        assert_eq!(fs::metadata(migration_dir.join("up.sql")).unwrap().len(), 0);
+        assert_eq!(fs::metadata(migration_dir.join("down.sql")).unwrap().len(), 0);
+
+        fs::remove_dir_all(&migrations_dir).unwrap();
+    }
+
+    #[test]
+    fn environment_database_path() {
+        let config: TomlConfig = toml::from_str(
+            "[environments.dev]\ndatabase = \"dev.db\"\n\n[environments.prod]\ndatabase = \"prod.db\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_environment_database_path(&config, "prod"),
+            Some(PathBuf::from("prod.db"))
+        );
+        assert_eq!(get_environment_database_path(&config, "staging"), None);
+    }
+}
@@ -37,7 +37,11 @@ use syn::{parse_macro_input, DeriveInput, FieldsNamed};
 use proc_macro2::{Delimiter, TokenTree as TokenTree2};
 use quote::quote;
 
-use util::{extract_table_name, has_default_impl};
+use util::{
+    column_name, extract_checks, extract_collation, extract_default_sql, extract_enum_repr, extract_foreign_key, extract_generated,
+    extract_indexes, extract_rename_all, extract_schema, extract_table_name, extract_unique_constraints, generic_argument, has_default_impl,
+    is_bytes_type, is_compressed, is_mapped, rename, sql_type,
+};
 
 mod util;
 
@@ -50,6 +54,7 @@ mod util;
 /// # Example
 ///
 /// ```rust
+/// use njord::table::Table;
 /// use njord_derive::Table;
 ///
 /// #[derive(Table)]
@@ -57,20 +62,93 @@ mod util;
 /// struct User {
 ///     id: i32,
 ///     name: String,
-///     email: Option<String>,
+///     email: String,
 /// }
 /// ```
 ///
 /// The `Table` trait will provide:
 /// - `get_name()` - Returns the table name.
+/// - `get_schema()` - Returns the schema set with `#[schema = "..."]`, if any.
 /// - `get_columns()` - Returns column names and their SQL types.
+/// - `get_column_defaults()` - Returns the defaults set with `#[default_sql = "..."]` on fields.
+/// - `get_foreign_keys()` - Returns the references set with `#[foreign_key(...)]` on fields.
+/// - `dependencies()` - Returns the tables referenced by the foreign keys.
+/// - `get_checks()` - Returns the constraints set with `#[check("...")]` on the struct or fields.
+/// - `get_unique_constraints()` - Returns the constraints set with `#[unique]` on the struct or fields.
 /// - `get_column_fields()` - Returns the field names as a vector.
 /// - `get_column_values()` - Returns the field values as strings.
 /// - `set_column_value()` - Updates a field value by column name.
 /// - `is_auto_increment_primary_key()` - Checks if a value is an auto-increment primary key.
 ///
+/// `Option<T>` fields map to nullable columns. A `None` value is left out of INSERT
+/// statements, so the column gets its database-side default or NULL.
+///
+/// The collation of a text column can be set with `#[collation = "NOCASE"]`.
+///
+/// Column names are the field names, unless set with `#[column = "..."]` on a field.
+/// With `#[rename_all = "snake_case"]` (or `camelCase`, `SCREAMING_SNAKE_CASE`) on the
+/// struct, the struct and field names are converted to the convention for the table
+/// and column names that are not set explicitly.
+///
+/// `Vec<u8>` fields map to BLOB columns, their values are hex encoded strings.
+///
+/// A `String` or `Vec<u8>` field with `#[compress]` is stored gzip compressed in a BLOB
+/// column, it is compressed on insert and update and decompressed on select. Conditions
+/// can't compare compressed columns with plain values.
+///
+/// `i128` and `u128` fields map to TEXT columns, as SQLite integers are 64-bit signed.
+///
+/// Fields of other types are converted with `Display` and `FromStr`, e.g. enums deriving
+/// `NjordEnum`. Unlike for primitive types, a value they cannot parse is a decode error.
+///
+/// A field with `#[mapped]` is converted with the mapping registered for its type in
+/// `njord::types::TypeRegistry`, which also gives the SQL type of its column. Creating,
+/// inserting or updating a table whose mapped type is not registered is an error.
+///
+/// Indexes are declared on the struct with
+/// `#[index(name = "idx_users_email", columns = "email", unique, where = "active = 1")]`,
+/// where `where` creates a partial index.
+///
+/// A column is unique with `#[unique]` on its field, a combination of columns with
+/// `#[unique(columns = "first_name, last_name")]` on the struct, which can be repeated.
+///
+/// With `#[soft_delete]` on the struct, deleting a row sets its `deleted_at` field, which
+/// must be an `Option`, to the current time and selects leave out the deleted rows.
+///
+/// With `#[without_rowid]` on the struct, SQLite creates the table as a `WITHOUT ROWID`
+/// table, which requires a `PrimaryKey` field.
+///
+/// An integer field with `#[version]` is used for optimistic locking, updates by primary
+/// key only match the row if its version is unchanged and increment it.
+///
+/// A field with `#[generated("price * quantity", stored)]` is a generated column, computed
+/// by the database and left out of inserts and updates. Without `stored`, it is virtual.
+///
+/// A field can reference another table with
+/// `#[foreign_key(table = "users", column = "id", on_delete = "set_null")]`.
+///
 /// Additional traits like `Default`, `Display`, and `FromStr` are also implemented if applicable.
-#[proc_macro_derive(Table, attributes(table_name))]
+#[proc_macro_derive(
+    Table,
+    attributes(
+        table_name,
+        schema,
+        default_sql,
+        foreign_key,
+        generated,
+        collation,
+        compress,
+        mapped,
+        check,
+        index,
+        unique,
+        column,
+        rename_all,
+        soft_delete,
+        version,
+        without_rowid
+    )
+)]
 pub fn table_derive(input: TokenStream) -> TokenStream {
     let cloned_input = input.clone();
     let derive_input: DeriveInput = parse_macro_input!(cloned_input);
@@ -78,14 +156,35 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
         ident, data, attrs, ..
     } = derive_input.clone();
 
-    let table_name = extract_table_name(&attrs);
+    let rename_all = extract_rename_all(&attrs);
+    let table_name = match &rename_all {
+        Some(convention) if !attrs.iter().any(|attr| attr.path().is_ident("table_name")) => {
+            rename(&ident.to_string(), convention)
+        }
+        _ => extract_table_name(&attrs),
+    };
+    let schema = extract_schema(&attrs);
 
     let mut name_stream = TokenStream2::default();
+    let mut schema_stream = TokenStream2::default();
     let mut columns_stream = TokenStream2::default();
+    let mut column_defaults_stream = TokenStream2::default();
+    let mut foreign_keys_stream = TokenStream2::default();
+    let mut dependencies_stream = TokenStream2::default();
+    let mut generated_columns_stream = TokenStream2::default();
+    let mut checks_stream = TokenStream2::default();
+    let mut indexes_stream = TokenStream2::default();
+    let mut unique_constraints_stream = TokenStream2::default();
+    let mut soft_delete_stream = TokenStream2::default();
+    let mut primary_key_stream = TokenStream2::default();
+    let mut version_stream = TokenStream2::default();
+    let mut without_rowid_stream = TokenStream2::default();
     let mut column_fields_stream = TokenStream2::default();
     let mut column_values_stream = TokenStream2::default();
+    let mut null_columns_stream = TokenStream2::default();
     let mut set_column_values_stream = TokenStream2::default();
     let mut is_auto_increment_primary_key_stream = TokenStream2::default();
+    let mut mapped_types_stream = TokenStream2::default();
 
     let mut display_impl = TokenStream2::default();
     let mut from_str_impl = TokenStream2::default();
@@ -94,15 +193,256 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
     if let syn::Data::Struct(s) = data {
         if let syn::Fields::Named(FieldsNamed { named, .. }) = s.fields {
             let field_names = named.iter().map(|f| &f.ident);
-            let field_names_clone2 = field_names.clone();
-            let field_names_clone3 = field_names.clone();
-            let field_names_clone4 = field_names.clone();
-            let field_types = named.iter().map(|f| &f.ty);
-            let field_types_clone = named.iter().map(|f| &f.ty);
+            let column_names: Vec<String> = named
+                .iter()
+                .map(|f| column_name(f, rename_all.as_deref()))
+                .collect();
+            for f in named.iter().filter(|f| is_compressed(f)) {
+                if sql_type(&f.ty).as_deref() != Some("TEXT") && !is_bytes_type(&f.ty) {
+                    panic!("The `compress` attribute requires a `String` or `Vec<u8>` field.");
+                }
+            }
+            let column_types = named.iter().map(|f| {
+                let field_name = column_name(f, rename_all.as_deref());
+                let collation = extract_collation(&f.attrs);
+                if is_mapped(f) {
+                    let (mapped_type, nullable) = match generic_argument(&f.ty, "Option") {
+                        Some(inner_type) => (inner_type, " NULL"),
+                        None => (&f.ty, ""),
+                    };
+                    // Unregistered types are rejected by `check_mapped_types` before the
+                    // column type is used
+                    return quote! {
+                        ::njord::types::TypeRegistry::sql_type::<#mapped_type>()
+                            .map(|column_type| format!("{}{}", column_type, #nullable))
+                            .unwrap_or_default()
+                    };
+                }
+                let column_type = match is_compressed(f) {
+                    true => Some("BLOB".to_string()),
+                    false => sql_type(&f.ty),
+                };
+                match column_type {
+                    Some(column_type) => {
+                        let column_type = match collation {
+                            Some(collation) => format!("{} COLLATE {}", column_type, collation),
+                            None => column_type,
+                        };
+                        quote! { #column_type }
+                    }
+                    None => quote! {
+                        {
+                            eprintln!("Warning: Unknown data type for column '{}'", #field_name);
+                            "UNKNOWN_TYPE"
+                        }
+                    },
+                }
+            });
+            let column_defaults = named.iter().filter_map(|f| {
+                let column = column_name(f, rename_all.as_deref());
+                extract_default_sql(&f.attrs).map(|default_sql| {
+                    quote! {
+                        defaults.insert(#column.to_string(), #default_sql.to_string());
+                    }
+                })
+            });
+            let foreign_keys = named.iter().filter_map(|f| {
+                let column = column_name(f, rename_all.as_deref());
+                extract_foreign_key(&f.attrs, &f.ty).map(|(_, references)| {
+                    quote! {
+                        foreign_keys.insert(#column.to_string(), #references.to_string());
+                    }
+                })
+            });
+            let generated_columns = named.iter().filter_map(|f| {
+                let column = column_name(f, rename_all.as_deref());
+                extract_generated(&f.attrs).map(|generated| {
+                    quote! {
+                        generated_columns.insert(#column.to_string(), #generated.to_string());
+                    }
+                })
+            });
+            // `Vec<u8>` fields are represented as hex strings, so binary data survives the
+            // conversion to and from the string values of the `Table` trait
+            let encode_hex = quote! {
+                value.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()
+            };
+            let decode_hex = quote! {
+                (0..value.len())
+                    .step_by(2)
+                    .map(|i| value.get(i..i + 2).and_then(|byte| u8::from_str_radix(byte, 16).ok()))
+                    .collect::<Option<Vec<u8>>>()
+            };
             let field_values = named.iter().map(|f| {
                 let field_name = &f.ident;
-                quote! { self.#field_name.to_string() }
+                if is_mapped(f) {
+                    let to_sql = quote! {
+                        ::njord::types::TypeRegistry::to_sql(value).unwrap_or_default()
+                    };
+                    return match generic_argument(&f.ty, "Option") {
+                        Some(_) => quote! {
+                            match &self.#field_name {
+                                Some(value) => #to_sql,
+                                None => "NULL".to_string(),
+                            }
+                        },
+                        None => quote! {
+                            {
+                                let value = &self.#field_name;
+                                #to_sql
+                            }
+                        },
+                    };
+                }
+                if is_compressed(f) {
+                    return quote! {
+                        {
+                            let value = ::njord::util::compress(&self.#field_name);
+                            #encode_hex
+                        }
+                    };
+                }
+                match generic_argument(&f.ty, "Option") {
+                    Some(inner_type) if is_bytes_type(inner_type) => quote! {
+                        match &self.#field_name {
+                            Some(value) => #encode_hex,
+                            None => "NULL".to_string(),
+                        }
+                    },
+                    Some(_) => quote! {
+                        match &self.#field_name {
+                            Some(value) => value.to_string(),
+                            None => "NULL".to_string(),
+                        }
+                    },
+                    None if is_bytes_type(&f.ty) => quote! {
+                        {
+                            let value = &self.#field_name;
+                            #encode_hex
+                        }
+                    },
+                    None => quote! { self.#field_name.to_string() },
+                }
             }); // field_values
+            let null_columns = named.iter().filter_map(|f| {
+                let field_name = &f.ident;
+                let column = column_name(f, rename_all.as_deref());
+                if generic_argument(&f.ty, "Option").is_some() {
+                    Some(quote! { (#column, self.#field_name.is_none()) })
+                } else if generic_argument(&f.ty, "AutoIncrementPrimaryKey").is_some() {
+                    Some(quote! { (#column, self.#field_name.get().is_none()) })
+                } else {
+                    None
+                }
+            }); // null_columns
+            let set_field_values = named.iter().map(|f| {
+                let field_name = &f.ident;
+                let field_type = &f.ty;
+                let column = column_name(f, rename_all.as_deref());
+                // Invalid values of custom types like enums are decode errors, while values
+                // of primitive types are converted leniently, keeping the default value
+                let is_custom = match generic_argument(field_type, "Option") {
+                    Some(inner_type) => sql_type(inner_type).is_none(),
+                    None => sql_type(field_type).is_none(),
+                };
+                let error = if is_custom {
+                    quote! {
+                        return Err(format!("failed to convert value `{}` for column `{}`", value, column))
+                    }
+                } else {
+                    quote! {
+                        eprintln!("Error: Failed to convert value for column '{}'", column)
+                    }
+                };
+                if is_mapped(f) {
+                    let convert_error = quote! {
+                        |e| format!("failed to convert value `{}` for column `{}`: {}", value, column, e)
+                    };
+                    return match generic_argument(field_type, "Option") {
+                        Some(inner_type) => quote! {
+                            #column => {
+                                if value.is_empty() {
+                                    self.#field_name = None;
+                                } else {
+                                    let val = ::njord::types::TypeRegistry::from_sql::<#inner_type>(value)
+                                        .map_err(#convert_error)?;
+                                    self.#field_name = Some(val);
+                                }
+                            }
+                        },
+                        // A NULL value of a field that is not an `Option` keeps the default value
+                        None => quote! {
+                            #column => {
+                                if !value.is_empty() {
+                                    self.#field_name = ::njord::types::TypeRegistry::from_sql::<#field_type>(value)
+                                        .map_err(#convert_error)?;
+                                }
+                            }
+                        },
+                    };
+                }
+                if is_compressed(f) {
+                    let from_bytes = match is_bytes_type(field_type) {
+                        true => quote! { Ok::<Vec<u8>, String>(bytes) },
+                        false => quote! { String::from_utf8(bytes).map_err(|e| e.to_string()) },
+                    };
+                    // A NULL value keeps the default value
+                    return quote! {
+                        #column => {
+                            if !value.is_empty() {
+                                let val = #decode_hex
+                                    .ok_or_else(|| format!("invalid value `{}` for column `{}`", value, column))
+                                    .and_then(|bytes| ::njord::util::decompress(&bytes))
+                                    .and_then(|bytes| #from_bytes)?;
+                                self.#field_name = val;
+                            }
+                        }
+                    };
+                }
+                match generic_argument(field_type, "Option") {
+                    Some(inner_type) if is_bytes_type(inner_type) => quote! {
+                        #column => {
+                            if value.is_empty() {
+                                self.#field_name = None;
+                            } else if let Some(val) = #decode_hex {
+                                self.#field_name = Some(val);
+                            } else {
+                                #error;
+                            }
+                        }
+                    },
+                    Some(inner_type) => quote! {
+                        #column => {
+                            if value.is_empty() {
+                                self.#field_name = None;
+                            } else if let Ok(val) = value.parse::<#inner_type>() {
+                                self.#field_name = Some(val);
+                            } else {
+                                #error;
+                            }
+                        }
+                    },
+                    None if is_bytes_type(field_type) => quote! {
+                        #column => {
+                            if let Some(val) = #decode_hex {
+                                self.#field_name = val;
+                            } else {
+                                #error;
+                            }
+                        }
+                    },
+                    // A NULL value of a field that is not an `Option` keeps the default value
+                    None => quote! {
+                        #column => {
+                            if let Ok(val) = value.parse::<#field_type>() {
+                                self.#field_name = val;
+                            } else if !value.is_empty() {
+                                #error;
+                            }
+                        }
+                    },
+                }
+            }); // set_field_values
 
             // Implement the std::fmt::Display trait
             display_impl.extend(quote! {
@@ -138,39 +478,216 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
                 }
             }); // name_stream
 
+            // Implement the get_schema() function, only if a schema was given
+            if let Some(schema) = &schema {
+                let clean_schema = schema.trim_matches(|c| c == '\\' || c == '"');
+                schema_stream.extend(quote! {
+                    fn get_schema(&self) -> Option<&str> {
+                        Some(#clean_schema)
+                    }
+                });
+            } // schema_stream
+
             // Implement the get_columns() function
             columns_stream.extend(quote! {
                 fn get_columns(&self) -> std::collections::HashMap<String, String> {
                     let mut columns = std::collections::HashMap::new();
                     #(
-                        let column_type = match stringify!(#field_types) {
-                            "i64" | "i32" | "i16" | "i8" | "u64" | "u32" | "u16" | "u8" | "usize" => "INTEGER",
-                            "String" => "TEXT",
-                            "f64" | "f32" => "REAL",
-                            "Vec<u8>" => "BLOB",
-                            "Option<i64>" | "Option<i32>" | "Option<i16>" | "Option<i8>" | "Option<u64>" | "Option<u32>" | "Option<u16>" | "Option<u8>" | "Option<usize>" => "INTEGER NULL",
-                            "Option<String>" => "TEXT NULL",
-                            "Option<f64>" | "Option<f32>" => "REAL NULL",
-                            "Option<Vec<u8>>" => "BLOB NULL",
-                            "bool" => "TEXT",
-                            _ => {
-                                eprintln!("Warning: Unknown data type for column '{}'", stringify!(#field_names));
-                                "UNKNOWN_TYPE"
-                            }
-                        };
                         columns.insert(
-                            stringify!(#field_names).to_string(),
-                            column_type.to_string(),
+                            #column_names.to_string(),
+                            #column_types.to_string(),
                         );
                     )*
                     columns
                 }
             }); // columns_stream
 
+            // Implement the get_column_defaults() function
+            column_defaults_stream.extend(quote! {
+                fn get_column_defaults(&self) -> std::collections::HashMap<String, String> {
+                    #[allow(unused_mut)]
+                    let mut defaults = std::collections::HashMap::new();
+                    #(#column_defaults)*
+                    defaults
+                }
+            }); // column_defaults_stream
+
+            // Implement the dependencies() function, listing every referenced table once
+            let mut dependencies = Vec::new();
+            for f in named.iter() {
+                if let Some((table, _)) = extract_foreign_key(&f.attrs, &f.ty) {
+                    if !dependencies.contains(&table) {
+                        dependencies.push(table);
+                    }
+                }
+            }
+            dependencies_stream.extend(quote! {
+                fn dependencies(&self) -> Vec<&'static str> {
+                    vec![#(#dependencies),*]
+                }
+            }); // dependencies_stream
+
+            // Implement the get_foreign_keys() function
+            foreign_keys_stream.extend(quote! {
+                fn get_foreign_keys(&self) -> std::collections::HashMap<String, String> {
+                    #[allow(unused_mut)]
+                    let mut foreign_keys = std::collections::HashMap::new();
+                    #(#foreign_keys)*
+                    foreign_keys
+                }
+            }); // foreign_keys_stream
+
+            // Implement the get_generated_columns() function
+            generated_columns_stream.extend(quote! {
+                fn get_generated_columns(&self) -> std::collections::HashMap<String, String> {
+                    #[allow(unused_mut)]
+                    let mut generated_columns = std::collections::HashMap::new();
+                    #(#generated_columns)*
+                    generated_columns
+                }
+            }); // generated_columns_stream
+
+            // Implement the check_mapped_types() function, if any field is mapped
+            let mapped_types: Vec<TokenStream2> = named
+                .iter()
+                .filter(|f| is_mapped(f))
+                .map(|f| {
+                    let column = column_name(f, rename_all.as_deref());
+                    let mapped_type = generic_argument(&f.ty, "Option").unwrap_or(&f.ty);
+                    quote! {
+                        if !::njord::types::TypeRegistry::is_registered::<#mapped_type>() {
+                            return Err(::njord::error::Error::InvalidQuery(format!(
+                                "no type mapping registered for column `{}`",
+                                #column
+                            )));
+                        }
+                    }
+                })
+                .collect();
+            if !mapped_types.is_empty() {
+                mapped_types_stream.extend(quote! {
+                    fn check_mapped_types(&self) -> Result<(), ::njord::error::Error> {
+                        #(#mapped_types)*
+                        Ok(())
+                    }
+                });
+            } // mapped_types_stream
+
+            // Implement the get_checks() function, struct level checks come first
+            let checks = extract_checks(&attrs)
+                .into_iter()
+                .chain(named.iter().flat_map(|f| extract_checks(&f.attrs)));
+            checks_stream.extend(quote! {
+                fn get_checks(&self) -> Vec<String> {
+                    vec![#(#checks.to_string()),*]
+                }
+            }); // checks_stream
+
+            // Implement the get_unique_constraints() function, struct level constraints come first
+            let unique_constraints = extract_unique_constraints(&attrs).into_iter().chain(
+                named
+                    .iter()
+                    .filter(|f| f.attrs.iter().any(|attr| attr.path().is_ident("unique")))
+                    .map(|f| vec![column_name(f, rename_all.as_deref())]),
+            );
+            let unique_constraints = unique_constraints.map(|columns| {
+                quote! { vec![#(#columns.to_string()),*] }
+            });
+            unique_constraints_stream.extend(quote! {
+                fn get_unique_constraints(&self) -> Vec<Vec<String>> {
+                    vec![#(#unique_constraints),*]
+                }
+            }); // unique_constraints_stream
+
+            // Implement the get_indexes() function, only if an index was given
+            let indexes = extract_indexes(&attrs);
+            if !indexes.is_empty() {
+                let indexes = indexes.iter().map(|(name, columns, unique, predicate)| {
+                    let predicate = match predicate {
+                        Some(predicate) => quote! { Some(#predicate.to_string()) },
+                        None => quote! { None },
+                    };
+                    quote! {
+                        ::njord::table::Index {
+                            name: #name.to_string(),
+                            columns: vec![#(#columns.to_string()),*],
+                            unique: #unique,
+                            predicate: #predicate,
+                        }
+                    }
+                });
+                indexes_stream.extend(quote! {
+                    fn get_indexes(&self) -> Vec<::njord::table::Index> {
+                        vec![#(#indexes),*]
+                    }
+                });
+            } // indexes_stream
+
+            // Implement the get_soft_delete_column() function, only for soft deleted tables
+            if attrs.iter().any(|attr| attr.path().is_ident("soft_delete")) {
+                let has_deleted_at = named.iter().any(|f| {
+                    column_name(f, rename_all.as_deref()) == "deleted_at"
+                        && generic_argument(&f.ty, "Option").is_some()
+                });
+                if !has_deleted_at {
+                    panic!("The `soft_delete` attribute requires a `deleted_at: Option<...>` field.");
+                }
+                soft_delete_stream.extend(quote! {
+                    fn get_soft_delete_column(&self) -> Option<&str> {
+                        Some("deleted_at")
+                    }
+                });
+            } // soft_delete_stream
+
+            // Implement the get_primary_key_column() function, only if a primary key was given
+            let primary_key = named.iter().find(|f| {
+                generic_argument(&f.ty, "PrimaryKey").is_some()
+                    || generic_argument(&f.ty, "AutoIncrementPrimaryKey").is_some()
+            });
+            if let Some(field) = primary_key {
+                let column = column_name(field, rename_all.as_deref());
+                primary_key_stream.extend(quote! {
+                    fn get_primary_key_column(&self) -> Option<&str> {
+                        Some(#column)
+                    }
+                });
+            } // primary_key_stream
+
+            // Implement the get_version_column() function, only if a version field was given
+            let version = named
+                .iter()
+                .find(|f| f.attrs.iter().any(|attr| attr.path().is_ident("version")));
+            if let Some(field) = version {
+                if sql_type(&field.ty).as_deref() != Some("INTEGER") {
+                    panic!("The `version` attribute requires an integer field.");
+                }
+                let column = column_name(field, rename_all.as_deref());
+                version_stream.extend(quote! {
+                    fn get_version_column(&self) -> Option<&str> {
+                        Some(#column)
+                    }
+                });
+            } // version_stream
+
+            // Implement the is_without_rowid() function, only for WITHOUT ROWID tables
+            if attrs.iter().any(|attr| attr.path().is_ident("without_rowid")) {
+                let has_primary_key = named
+                    .iter()
+                    .any(|f| generic_argument(&f.ty, "PrimaryKey").is_some());
+                if !has_primary_key {
+                    panic!("The `without_rowid` attribute requires a `PrimaryKey` field.");
+                }
+                without_rowid_stream.extend(quote! {
+                    fn is_without_rowid(&self) -> bool {
+                        true
+                    }
+                });
+            } // without_rowid_stream
+
             // Implement the get_column_fields() function
             column_fields_stream.extend(quote! {
                 fn get_column_fields(&self) -> Vec<String> {
-                    vec![#(stringify!(#field_names_clone2).to_string()),*]
+                    vec![#(#column_names.to_string()),*]
                 }
             }); // column_fields_stream
 
@@ -181,21 +698,31 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
                 }
             }); // column_values_stream
 
+            null_columns_stream.extend(quote! {
+                fn get_null_columns(&self) -> Vec<String> {
+                    let columns: Vec<(&str, bool)> = vec![#(#null_columns),*];
+                    columns
+                        .into_iter()
+                        .filter(|(_, is_null)| *is_null)
+                        .map(|(column, _)| column.to_string())
+                        .collect()
+                }
+            }); // null_columns_stream
+
             set_column_values_stream.extend(quote! {
                 fn set_column_value(&mut self, column: &str, value: &str) {
+                    if let Err(e) = self.try_set_column_value(column, value) {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+
+                fn try_set_column_value(&mut self, column: &str, value: &str) -> Result<(), String> {
                     match column {
-                        #(
-                            stringify!(#field_names_clone3) => {
-                                if let Ok(val) = value.parse::<#field_types_clone>() {
-                                    self.#field_names_clone3 = val;
-                                } else {
-                                    eprintln!("Error: Failed to convert value for column '{}'", column);
-                                }
-                            }
-                        )*
+                        #(#set_field_values)*
 
                         _ => eprintln!("Warning: Unknown column '{}'", column),
                     }
+                    Ok(())
                 }
             }); // set_column_values_stream
 
@@ -213,7 +740,7 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
                         fn default() -> Self {
                             Self {
                                 #(
-                                    #field_names_clone4: Default::default(),
+                                    #field_names: Default::default(),
                                 )*
                             }
                         }
@@ -228,11 +755,25 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
     let output = quote! {
         impl Table for #ident {
             #name_stream
+            #schema_stream
             #columns_stream
+            #column_defaults_stream
+            #foreign_keys_stream
+            #dependencies_stream
+            #generated_columns_stream
+            #checks_stream
+            #indexes_stream
+            #unique_constraints_stream
+            #soft_delete_stream
+            #primary_key_stream
+            #version_stream
+            #without_rowid_stream
             #column_fields_stream
             #column_values_stream
+            #null_columns_stream
             #set_column_values_stream
             #is_auto_increment_primary_key_stream
+            #mapped_types_stream
         }
 
         #default_impl
@@ -243,6 +784,98 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
     output.into()
 }
 
+/// Derives `Display` and `FromStr` for an enum, so it can be used as a field of a table.
+///
+/// By default, the enum is stored by its variant names. With `#[njord_enum(repr = "i32")]`,
+/// it is stored by its integer discriminants instead. Decoding a value that matches no
+/// variant fails with a decode error.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(NjordEnum, Default)]
+/// #[njord_enum(repr = "i32")]
+/// enum Status {
+///     #[default]
+///     Active = 1,
+///     Suspended = 2,
+/// }
+/// ```
+///
+/// Only enums with unit variants are supported.
+#[proc_macro_derive(NjordEnum, attributes(njord_enum))]
+pub fn njord_enum_derive(input: TokenStream) -> TokenStream {
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
+
+    let syn::Data::Enum(data) = data else {
+        panic!("`NjordEnum` can only be derived for enums.");
+    };
+    if data
+        .variants
+        .iter()
+        .any(|v| !matches!(v.fields, syn::Fields::Unit))
+    {
+        panic!("`NjordEnum` requires an enum with unit variants only.");
+    }
+
+    let variants: Vec<&syn::Ident> = data.variants.iter().map(|v| &v.ident).collect();
+    let names: Vec<String> = variants.iter().map(|v| v.to_string()).collect();
+
+    let (display, from_str) = match extract_enum_repr(&attrs) {
+        Some(repr) => (
+            quote! {
+                match self {
+                    #(#ident::#variants => write!(f, "{}", #ident::#variants as #repr),)*
+                }
+            },
+            quote! {
+                let discriminant = s
+                    .parse::<#repr>()
+                    .map_err(|_| format!("invalid discriminant `{}` of `{}`", s, stringify!(#ident)))?;
+                #(
+                    if discriminant == #ident::#variants as #repr {
+                        return Ok(#ident::#variants);
+                    }
+                )*
+                Err(format!("unknown discriminant `{}` of `{}`", s, stringify!(#ident)))
+            },
+        ),
+        None => (
+            quote! {
+                match self {
+                    #(#ident::#variants => write!(f, "{}", #names),)*
+                }
+            },
+            quote! {
+                match s {
+                    #(#names => Ok(#ident::#variants),)*
+                    _ => Err(format!("unknown variant `{}` of `{}`", s, stringify!(#ident))),
+                }
+            },
+        ),
+    };
+
+    let output = quote! {
+        impl std::fmt::Display for #ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                #display
+            }
+        }
+
+        impl std::str::FromStr for #ident {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                #from_str
+            }
+        }
+    };
+
+    output.into()
+}
+
 /// A procedural macro `sql!` that takes SQL-like syntax and transforms it into a formatted string.
 ///
 /// # Example
@@ -254,7 +887,7 @@ pub fn table_derive(input: TokenStream) -> TokenStream {
 /// let query = sql! {
 ///     SELECT * FROM user WHERE id = {id}
 /// };
-/// assert_eq!(query, "SELECT * FROM user WHERE id = 1");
+/// assert_eq!(query, "SELECT * FROM user WHERE id = '1'");
 /// ```
 ///
 /// This macro supports embedding expressions within SQL queries, ensuring proper formatting
@@ -298,8 +931,8 @@ pub fn sql(input: TokenStream) -> TokenStream {
                 let needs_quotes = !expr_str.contains("as")
                     && !expr_str.contains("::")
                     && !expr_str.starts_with("Some")
-                    && !expr_str.parse::<f64>().is_ok()
-                    && !expr_str.parse::<i64>().is_ok();
+                    && expr_str.parse::<f64>().is_err()
+                    && expr_str.parse::<i64>().is_err();
 
                 if needs_quotes {
                     sql_parts.push("'{}'".to_string());
@@ -98,6 +98,552 @@ pub fn extract_table_name(attrs: &[syn::Attribute]) -> String {
     "default_table_name".to_string()
 }
 
+/// Extracts the schema name from attributes.
+///
+/// This function searches for the `schema` attribute in the given attributes and returns
+/// the specified schema name if found.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a struct.
+///
+/// # Returns
+///
+/// An `Option<String>` containing the schema name, or `None` if not specified.
+pub fn extract_schema(attrs: &[syn::Attribute]) -> Option<String> {
+    for attr in attrs {
+        if attr.path().is_ident("schema") {
+            match &attr.meta {
+                Meta::NameValue(val) => {
+                    let expr = &val.value;
+                    let expr_token_stream = quote! { #expr };
+
+                    return Some(expr_token_stream.to_string());
+                }
+                _ => panic!("Incorrect format for using the `schema` attribute."),
+            };
+        }
+    }
+
+    None
+}
+
+/// Extracts the database-side default expression of a field from attributes.
+///
+/// This function searches for the `default_sql` attribute in the given field attributes,
+/// e.g. `#[default_sql = "CURRENT_TIMESTAMP"]`, and returns the SQL expression if found.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a field.
+///
+/// # Returns
+///
+/// An `Option<String>` containing the SQL expression, or `None` if not specified.
+pub fn extract_default_sql(attrs: &[syn::Attribute]) -> Option<String> {
+    extract_str_attribute(attrs, "default_sql")
+}
+
+/// Extracts the collation of a field from attributes.
+///
+/// This function searches for the `collation` attribute in the given field attributes,
+/// e.g. `#[collation = "NOCASE"]`, and returns the collation name if found.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a field.
+///
+/// # Returns
+///
+/// An `Option<String>` containing the collation name, or `None` if not specified.
+pub fn extract_collation(attrs: &[syn::Attribute]) -> Option<String> {
+    extract_str_attribute(attrs, "collation")
+}
+
+/// Extracts the check constraints from attributes.
+///
+/// This function collects every `check` attribute in the given attributes,
+/// e.g. `#[check("amount >= 0")]`, and returns the constraint expressions.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a struct or field.
+///
+/// # Returns
+///
+/// A `Vec<String>` containing the check expressions in declaration order.
+pub fn extract_checks(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("check"))
+        .map(|attr| match attr.parse_args::<syn::LitStr>() {
+            Ok(lit) => lit.value(),
+            Err(_) => panic!("Incorrect format for using the `check` attribute."),
+        })
+        .collect()
+}
+
+/// Extracts the generated column definition of a field from attributes.
+///
+/// This function searches for the `generated` attribute in the given field attributes,
+/// e.g. `#[generated("price * quantity", stored)]`, and returns the `GENERATED ALWAYS AS`
+/// clause of the column if found. Without `stored`, the column is virtual.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a field.
+///
+/// # Returns
+///
+/// An `Option<String>` containing the `GENERATED ALWAYS AS` clause, or `None` if not specified.
+pub fn extract_generated(attrs: &[syn::Attribute]) -> Option<String> {
+    let attr = attrs.iter().find(|attr| attr.path().is_ident("generated"))?;
+
+    let (expression, stored) = attr
+        .parse_args_with(|input: syn::parse::ParseStream| {
+            let expression = input.parse::<syn::LitStr>()?.value();
+            let mut stored = false;
+            if input.parse::<Option<syn::Token![,]>>()?.is_some() {
+                let storage = input.parse::<syn::Ident>()?;
+                if storage != "stored" {
+                    return Err(syn::Error::new(storage.span(), "expected `stored`"));
+                }
+                stored = true;
+            }
+            Ok((expression, stored))
+        })
+        .unwrap_or_else(|e| panic!("Incorrect format for using the `generated` attribute: {}", e));
+
+    Some(format!(
+        "GENERATED ALWAYS AS ({}) {}",
+        expression,
+        if stored { "STORED" } else { "VIRTUAL" }
+    ))
+}
+
+/// Extracts the integer representation of an enum from attributes.
+///
+/// This function searches for the `njord_enum` attribute in the given enum attributes,
+/// e.g. `#[njord_enum(repr = "i32")]`.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of an enum.
+///
+/// # Returns
+///
+/// An `Option<syn::Ident>` containing the integer type, or `None` if the enum is stored
+/// by its variant names.
+pub fn extract_enum_repr(attrs: &[syn::Attribute]) -> Option<syn::Ident> {
+    let attr = attrs.iter().find(|attr| attr.path().is_ident("njord_enum"))?;
+
+    let mut repr = None;
+    attr.parse_nested_meta(|meta| {
+        if !meta.path.is_ident("repr") {
+            return Err(meta.error("expected `repr`"));
+        }
+        let value = meta.value()?.parse::<syn::LitStr>()?;
+        if !matches!(
+            value.value().as_str(),
+            "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32"
+        ) {
+            return Err(syn::Error::new(value.span(), "expected an integer type like `i32`"));
+        }
+        repr = Some(syn::Ident::new(&value.value(), value.span()));
+        Ok(())
+    })
+    .unwrap_or_else(|e| panic!("Incorrect format for using the `njord_enum` attribute: {}", e));
+
+    repr
+}
+
+/// Extracts the naming convention of the table and column names from attributes.
+///
+/// This function searches for the `rename_all` attribute in the given struct attributes,
+/// e.g. `#[rename_all = "snake_case"]`. Supported conventions are `snake_case`,
+/// `camelCase` and `SCREAMING_SNAKE_CASE`.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a struct.
+///
+/// # Returns
+///
+/// An `Option<String>` containing the naming convention, or `None` if not specified.
+pub fn extract_rename_all(attrs: &[syn::Attribute]) -> Option<String> {
+    let rename_all = extract_str_attribute(attrs, "rename_all")?;
+
+    match rename_all.as_str() {
+        "snake_case" | "camelCase" | "SCREAMING_SNAKE_CASE" => Some(rename_all),
+        _ => panic!(
+            "Unsupported `rename_all` convention `{}`, expected `snake_case`, `camelCase` or `SCREAMING_SNAKE_CASE`.",
+            rename_all
+        ),
+    }
+}
+
+/// Returns the column name of a field.
+///
+/// The name set with `#[column = "..."]` takes precedence, otherwise the field name is
+/// converted to the `rename_all` convention, if any.
+///
+/// # Arguments
+///
+/// * `field` - A reference to the `syn::Field`.
+/// * `rename_all` - The naming convention of the struct.
+///
+/// # Returns
+///
+/// A `String` containing the column name.
+pub fn column_name(field: &syn::Field, rename_all: Option<&str>) -> String {
+    if let Some(column) = extract_str_attribute(&field.attrs, "column") {
+        return column;
+    }
+
+    let field_name = field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default();
+    match rename_all {
+        Some(convention) => rename(&field_name, convention),
+        None => field_name,
+    }
+}
+
+/// Converts a `CamelCase`, `camelCase` or `snake_case` name to the given convention.
+///
+/// # Arguments
+///
+/// * `name` - The name to convert.
+/// * `convention` - One of `snake_case`, `camelCase` or `SCREAMING_SNAKE_CASE`.
+///
+/// # Returns
+///
+/// A `String` containing the converted name.
+pub fn rename(name: &str, convention: &str) -> String {
+    // Split the name into lowercase words at underscores and uppercase letters
+    let mut words: Vec<String> = Vec::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if c == '_' {
+            words.push(String::new());
+            previous_lowercase = false;
+            continue;
+        }
+        if words.is_empty() || (c.is_uppercase() && previous_lowercase) {
+            words.push(String::new());
+        }
+        previous_lowercase = c.is_lowercase() || c.is_ascii_digit();
+        words.last_mut().unwrap().extend(c.to_lowercase());
+    }
+    words.retain(|word| !word.is_empty());
+
+    match convention {
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) if index > 0 => first.to_uppercase().chain(chars).collect(),
+                    _ => word.clone(),
+                }
+            })
+            .collect(),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        _ => words.join("_"),
+    }
+}
+
+/// Extracts the indexes from attributes.
+///
+/// This function collects every `index` attribute in the given struct attributes, e.g.
+/// `#[index(name = "idx_users_email", columns = "email", unique, where = "active = 1")]`.
+/// The `columns` are separated by commas, `unique` and `where` are optional.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a struct.
+///
+/// # Returns
+///
+/// A `Vec` of `(name, columns, unique, predicate)` tuples in declaration order.
+pub fn extract_indexes(attrs: &[syn::Attribute]) -> Vec<(String, Vec<String>, bool, Option<String>)> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("index"))
+        .map(|attr| {
+            let mut name = None;
+            let mut columns = Vec::new();
+            let mut unique = false;
+            let mut predicate = None;
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("unique") {
+                    unique = true;
+                    return Ok(());
+                }
+
+                let value = meta.value()?.parse::<syn::LitStr>()?.value();
+                if meta.path.is_ident("name") {
+                    name = Some(value);
+                } else if meta.path.is_ident("columns") {
+                    columns = value.split(',').map(|c| c.trim().to_string()).collect();
+                } else if meta.path.is_ident("where") {
+                    predicate = Some(value);
+                } else {
+                    return Err(meta.error("expected `name`, `columns`, `unique` or `where`"));
+                }
+                Ok(())
+            })
+            .unwrap_or_else(|e| panic!("Incorrect format for using the `index` attribute: {}", e));
+
+            let Some(name) = name else {
+                panic!("The `index` attribute requires a `name`.");
+            };
+            if columns.iter().all(|c| c.is_empty()) {
+                panic!("The `index` attribute requires `columns`.");
+            }
+
+            (name, columns, unique, predicate)
+        })
+        .collect()
+}
+
+/// Extracts the uniqueness constraints from attributes.
+///
+/// This function collects every `unique` attribute in the given struct attributes, e.g.
+/// `#[unique(columns = "first_name, last_name")]`, where the `columns` are separated by commas.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a struct.
+///
+/// # Returns
+///
+/// A `Vec` containing the columns of every constraint in declaration order.
+pub fn extract_unique_constraints(attrs: &[syn::Attribute]) -> Vec<Vec<String>> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("unique"))
+        .map(|attr| {
+            let mut columns = Vec::new();
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("columns") {
+                    let value = meta.value()?.parse::<syn::LitStr>()?.value();
+                    columns = value.split(',').map(|c| c.trim().to_string()).collect();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `columns`"))
+                }
+            })
+            .unwrap_or_else(|e| panic!("Incorrect format for using the `unique` attribute: {}", e));
+
+            if columns.iter().all(|c| c.is_empty()) {
+                panic!("The `unique` attribute requires `columns`.");
+            }
+
+            columns
+        })
+        .collect()
+}
+
+/// Extracts the string literal of a `#[name = "..."]` attribute.
+fn extract_str_attribute(attrs: &[syn::Attribute], name: &str) -> Option<String> {
+    for attr in attrs {
+        if attr.path().is_ident(name) {
+            match &attr.meta {
+                Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(lit),
+                            ..
+                        }),
+                    ..
+                }) => return Some(lit.value()),
+                _ => panic!("Incorrect format for using the `{}` attribute.", name),
+            };
+        }
+    }
+
+    None
+}
+
+/// Extracts the foreign key of a field from attributes.
+///
+/// This function searches for the `foreign_key` attribute in the given field attributes,
+/// e.g. `#[foreign_key(table = "users", column = "id", on_delete = "set_null")]`, and
+/// returns the referenced table and the `REFERENCES` clause of the column if found.
+///
+/// Supported `on_delete` actions are `cascade`, `set_null`, `set_default`, `restrict`
+/// and `no_action`. The `set_null` action requires the field to be an `Option<T>`.
+///
+/// # Arguments
+///
+/// * `attrs` - A slice of `syn::Attribute` representing the attributes of a field.
+/// * `ty` - A reference to the `syn::Type` of the field.
+///
+/// # Returns
+///
+/// An `Option` containing the referenced table and the `REFERENCES` clause, or `None`
+/// if not specified.
+pub fn extract_foreign_key(attrs: &[syn::Attribute], ty: &syn::Type) -> Option<(String, String)> {
+    for attr in attrs {
+        if attr.path().is_ident("foreign_key") {
+            let mut table = None;
+            let mut column = None;
+            let mut on_delete = None;
+
+            attr.parse_nested_meta(|meta| {
+                let value = meta.value()?.parse::<syn::LitStr>()?.value();
+                if meta.path.is_ident("table") {
+                    table = Some(value);
+                } else if meta.path.is_ident("column") {
+                    column = Some(value);
+                } else if meta.path.is_ident("on_delete") {
+                    on_delete = Some(value);
+                } else {
+                    return Err(meta.error("expected `table`, `column` or `on_delete`"));
+                }
+                Ok(())
+            })
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Incorrect format for using the `foreign_key` attribute: {}",
+                    e
+                )
+            });
+
+            let (Some(table), Some(column)) = (table, column) else {
+                panic!("The `foreign_key` attribute requires `table` and `column`.");
+            };
+
+            let mut clause = format!("REFERENCES {}({})", table, column);
+            if let Some(on_delete) = on_delete {
+                let action = match on_delete.as_str() {
+                    "cascade" => "CASCADE",
+                    "set_null" => "SET NULL",
+                    "set_default" => "SET DEFAULT",
+                    "restrict" => "RESTRICT",
+                    "no_action" => "NO ACTION",
+                    _ => panic!("Unknown `on_delete` action '{}'.", on_delete),
+                };
+
+                if on_delete == "set_null" && !is_option_type(ty) {
+                    panic!("The `on_delete = \"set_null\"` action requires an `Option<T>` field.");
+                }
+
+                clause.push_str(&format!(" ON DELETE {}", action));
+            }
+
+            return Some((table, clause));
+        }
+    }
+
+    None
+}
+
+/// Maps a Rust field type to its SQL column type.
+///
+/// `Option<T>` fields map to nullable columns, `PrimaryKey<T>` and `AutoIncrementPrimaryKey<T>`
+/// to primary key columns.
+///
+/// # Arguments
+///
+/// * `ty` - A reference to the `syn::Type` of the field.
+///
+/// # Returns
+///
+/// An `Option<String>` containing the SQL column type, or `None` if the type is unknown.
+pub fn sql_type(ty: &syn::Type) -> Option<String> {
+    if let Some(inner) = generic_argument(ty, "Option") {
+        return sql_type(inner).map(|t| format!("{} NULL", t));
+    }
+    if generic_argument(ty, "AutoIncrementPrimaryKey").is_some() {
+        return Some("INTEGER PRIMARY KEY AUTOINCREMENT".to_string());
+    }
+    if let Some(inner) = generic_argument(ty, "PrimaryKey") {
+        return sql_type(inner).map(|t| format!("{} PRIMARY KEY", t));
+    }
+
+    let type_str = quote! { #ty }.to_string().replace(' ', "");
+    let column_type = match type_str.as_str() {
+        "i64" | "i32" | "i16" | "i8" | "u64" | "u32" | "u16" | "u8" | "usize" => "INTEGER",
+        // SQLite integers are 64-bit signed, wider integers are stored as text
+        "i128" | "u128" => "TEXT",
+        "String" => "TEXT",
+        "f64" | "f32" => "REAL",
+        "Vec<u8>" => "BLOB",
+        "bool" => "TEXT",
+        _ => return None,
+    };
+
+    Some(column_type.to_string())
+}
+
+/// Returns the generic argument of a type like `Option<T>` if the type has the given name.
+///
+/// # Arguments
+///
+/// * `ty` - A reference to the `syn::Type` to be checked.
+/// * `name` - The name of the generic type, e.g. `Option`.
+///
+/// # Returns
+///
+/// An `Option` containing the generic argument `T`, or `None` if the type does not match.
+pub fn generic_argument<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+    if let syn::Type::Path(type_path) = ty {
+        let segment = type_path.path.segments.last()?;
+        if segment.ident == name {
+            if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(syn::GenericArgument::Type(inner)) = args.args.first() {
+                    return Some(inner);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Checks if the given type is a `Vec<u8>` type, which maps to a BLOB column.
+///
+/// # Arguments
+///
+/// * `ty` - A reference to the `syn::Type` to be checked.
+///
+/// # Returns
+///
+/// A boolean indicating whether the type is a `Vec<u8>` type.
+pub fn is_bytes_type(ty: &syn::Type) -> bool {
+    quote! { #ty }.to_string().replace(' ', "") == "Vec<u8>"
+}
+
+/// Checks if the field has the `#[compress]` attribute, storing it gzip compressed.
+///
+/// # Arguments
+///
+/// * `field` - A reference to the `syn::Field` to be checked.
+///
+/// # Returns
+///
+/// A boolean indicating whether the field is compressed.
+pub fn is_compressed(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path().is_ident("compress"))
+}
+
+/// Checks if the field has the `#[mapped]` attribute, converting it with the mapping
+/// registered for its type.
+///
+/// # Arguments
+///
+/// * `field` - A reference to the `syn::Field` to be checked.
+///
+/// # Returns
+///
+/// A boolean indicating whether the field is mapped.
+pub fn is_mapped(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path().is_ident("mapped"))
+}
+
 /// Checks if the given type is an Option<T> type.
 ///
 /// # Arguments
@@ -107,12 +653,10 @@ pub fn extract_table_name(attrs: &[syn::Attribute]) -> String {
 /// # Returns
 ///
 /// A boolean indicating whether the type is an Option<T> type.
-/// ```
-#[allow(dead_code)]
 pub fn is_option_type(ty: &syn::Type) -> bool {
     if let syn::Type::Path(type_path) = ty {
         let segments = &type_path.path.segments;
-        if segments.len() == 1 && segments.first().unwrap().ident.to_string() == "Option" {
+        if segments.len() == 1 && segments.first().unwrap().ident == "Option" {
             if let syn::PathArguments::AngleBracketed(args) = &segments.first().unwrap().arguments {
                 if args.args.len() == 1 {
                     return true;
@@ -7,6 +7,8 @@ use serde::Deserialize;
 #[derive(Table, Deserialize, Debug)]
 #[table_name = "neo"]
 pub struct NearEarthObject {
+    // The primary key is generated, the `id` of the JSON is the NEO id
+    #[serde(skip_deserializing)]
     pub id: AutoIncrementPrimaryKey<usize>,
     #[serde(rename = "id")]
     pub neo_id: String,
@@ -86,7 +86,7 @@ async fn insert() -> Result<(), Box<dyn std::error::Error>> {
         "jdbc:sqlserver://localhost;encrypt=true;username=sa;password=Njord_passw0rd;databaseName=NjordDatabase;";
     let mut conn = mssql::open(connection_string).await?;
 
-    let _ = match mssql::insert(&mut conn, near_earth_objects).await {
+    match mssql::insert(&mut conn, near_earth_objects).await {
         Ok(_) => println!("Near Earth Objects inserted successfully"),
         Err(err) => eprintln!("Error: {:?}", err),
     };
@@ -36,11 +36,11 @@ async fn main() -> Result<(), Error> {
 
 fn select() -> Result<(), Box<dyn std::error::Error>> {
     let connection_string = "//localhost:1521/FREEPDB1";
-    let mut conn = oracle::open("njord_user", "njord_password", connection_string).unwrap();
+    let conn = oracle::open("njord_user", "njord_password", connection_string).unwrap();
 
     let results = oracle::select(vec![Column::Text("id".to_string())])
         .from(NeoId::default())
-        .build(&mut conn);
+        .build(&conn);
 
     match results {
         Ok(data) => println!("Selected: {:#?}", data.len()),
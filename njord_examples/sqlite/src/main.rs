@@ -1,6 +1,7 @@
 use crate::schema::NearEarthObject;
 use njord::keys::AutoIncrementPrimaryKey;
-use njord::sqlite::{self, SqliteError};
+use njord::sqlite;
+use njord::Error;
 use reqwest::header::ACCEPT;
 use serde::Deserialize;
 use serde_json::Value;
@@ -24,7 +25,7 @@ pub struct NearEarthObjectResponse {
 }
 
 #[tokio::main]
-async fn main() -> Result<(), SqliteError> {
+async fn main() -> Result<(), Error> {
     // Setting up a SQLite DB and Connection
     let db_relative_path = "./njord_examples/sqlite/neo.db";
     let db_path = Path::new(&db_relative_path);